@@ -35,6 +35,20 @@ impl Metrics {
     pub fn c_players_total_incr() {
         increment_counter!("players_total");
     }
+
+    pub fn c_hands_played_total_incr(labels: metrics_labels::GameRoom) {
+        if let Some(room_code) = labels.room_code {
+            let labels = [("room_code", room_code)];
+            increment_counter!("hands_played_total", &labels);
+        }
+    }
+
+    pub fn h_pot_size_chips(labels: metrics_labels::GameRoom, pot: u64) {
+        if let Some(room_code) = labels.room_code {
+            let labels = [("room_code", room_code)];
+            histogram!("pot_size_chips", pot as f64, &labels);
+        }
+    }
 }
 
 pub mod metrics_labels {
@@ -69,4 +83,23 @@ pub mod metrics_labels {
             room_code: room_code.map(|s| s.as_ref().to_string()),
         }
     }
+
+    /// Labels for gameplay metrics, which are skipped for rooms without a code.
+    pub fn room_gameplay(room_code: Option<String>) -> GameRoom {
+        GameRoom { room_code }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn room_gameplay_labels_use_room_code() {
+            let labels = room_gameplay(Some("ABCD".to_string()));
+            assert_eq!(labels.room_code.as_deref(), Some("ABCD"));
+
+            let labels = room_gameplay(None);
+            assert_eq!(labels.room_code, None);
+        }
+    }
 }
@@ -6,6 +6,60 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone)]
 pub struct Deck(Vec<Card>);
 
+#[cfg(test)]
+thread_local! {
+    /// Hands evaluated on this thread, so tests can check showdowns do not
+    /// score the same hand more than once.
+    pub(crate) static EVALUATIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// The game being dealt, which decides the deck and a few hand rankings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Variant {
+    #[default]
+    Standard,
+    /// 36-card deck without 2 to 5, where A-6-7-8-9 is the lowest straight
+    /// and a flush beats a full house.
+    ShortDeck,
+    /// Four hole cards, of which a hand must use exactly two along with three
+    /// from the board.
+    Omaha,
+}
+
+impl Variant {
+    /// How many hole cards each player is dealt.
+    pub fn hole_cards(self) -> usize {
+        match self {
+            Variant::Standard | Variant::ShortDeck => 2,
+            Variant::Omaha => 4,
+        }
+    }
+
+    /// How many cards the variant's deck starts with.
+    pub fn deck_size(self) -> usize {
+        match self {
+            Variant::Standard | Variant::Omaha => 52,
+            Variant::ShortDeck => 36,
+        }
+    }
+
+    fn low_ace_rank(self) -> u64 {
+        match self {
+            Variant::Standard | Variant::Omaha => CardValue::Two as u64 + 1,
+            Variant::ShortDeck => CardValue::Six as u64 + 1,
+        }
+    }
+
+    /// Hand strength for comparison, swapping flush and full house in short-deck.
+    fn rank(self, strength: HandStrength) -> u8 {
+        match (self, strength) {
+            (Variant::ShortDeck, HandStrength::Flush) => HandStrength::FullHouse as u8,
+            (Variant::ShortDeck, HandStrength::FullHouse) => HandStrength::Flush as u8,
+            (_, strength) => strength as u8,
+        }
+    }
+}
+
 impl Deck {
     pub fn ordered() -> Self {
         let suites = vec![
@@ -40,11 +94,48 @@ impl Deck {
         }
         Deck(deck)
     }
+    /// Shuffled deck that always has the same order for the same seed, so a
+    /// reported game can be replayed. ChaCha8 is used over `StdRng` since its
+    /// output is fixed across rand releases.
+    pub fn seeded(seed: u64) -> Self {
+        let Deck(mut deck) = Self::ordered();
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+        deck.shuffle(&mut rng);
+        Self(deck)
+    }
     pub fn pop(&mut self) -> Card {
         self.0.pop().expect("deck is empty")
     }
-    pub fn is_fresh(&self) -> bool {
-        self.0.len() == 52
+    /// Cuts the deck, moving the top `at` cards to the bottom. Does nothing
+    /// when `at` is 0 or not inside the deck.
+    pub fn cut(&mut self, at: usize) {
+        if at == 0 || at >= self.0.len() {
+            return;
+        }
+        // cards are dealt from the end, so the top of the deck is the back
+        self.0.rotate_right(at);
+    }
+    /// Drops the cards the variant does not play with, keeping the order.
+    pub fn for_variant(self, variant: Variant) -> Self {
+        match variant {
+            Variant::Standard | Variant::Omaha => self,
+            Variant::ShortDeck => {
+                let Deck(deck) = self;
+                Self(
+                    deck.into_iter()
+                        .filter(|c| c.value >= CardValue::Six)
+                        .collect(),
+                )
+            }
+        }
+    }
+    /// Whether no card has been dealt from a deck of the given variant.
+    pub fn is_fresh(&self, variant: Variant) -> bool {
+        self.0.len() == variant.deck_size()
+    }
+    /// Remaining cards in the order they will be dealt.
+    pub fn remaining(&self) -> impl Iterator<Item = &Card> {
+        self.0.iter().rev()
     }
 }
 
@@ -98,8 +189,60 @@ impl Display for HandStrength {
 
 impl Card {
     pub fn evaluate_hand(player_cards: &(Self, Self), table_cards: &[Self]) -> EvaluatedHand {
-        let mut all_cards = vec![player_cards.0, player_cards.1];
-        all_cards.extend_from_slice(table_cards);
+        Self::evaluate_hand_for(
+            Variant::Standard,
+            &[player_cards.0, player_cards.1],
+            table_cards,
+        )
+    }
+
+    /// Scores the best five-card hand, in Omaha using exactly two of the hole
+    /// cards and three from the board.
+    pub fn evaluate_hand_for(
+        variant: Variant,
+        player_cards: &[Self],
+        table_cards: &[Self],
+    ) -> EvaluatedHand {
+        #[cfg(test)]
+        EVALUATIONS.with(|evaluations| evaluations.set(evaluations.get() + 1));
+
+        match variant {
+            Variant::Omaha => {
+                let boards = Self::combinations(table_cards, 3);
+                Self::combinations(player_cards, 2)
+                    .into_iter()
+                    .flat_map(|hole| {
+                        boards.iter().map(move |board| {
+                            Self::evaluate_cards(variant, [hole.as_slice(), board].concat())
+                        })
+                    })
+                    .max()
+                    .expect("not enough cards to evaluate hand")
+            }
+            Variant::Standard | Variant::ShortDeck => {
+                Self::evaluate_cards(variant, [player_cards, table_cards].concat())
+            }
+        }
+    }
+
+    /// Every way of picking `k` of the cards, keeping their order.
+    fn combinations(cards: &[Self], k: usize) -> Vec<Vec<Self>> {
+        match (k, cards) {
+            (0, _) => vec![vec![]],
+            (_, []) => vec![],
+            (_, [first, rest @ ..]) => {
+                let mut with_first = Self::combinations(rest, k - 1);
+                for combination in &mut with_first {
+                    combination.insert(0, *first);
+                }
+                with_first.extend(Self::combinations(rest, k));
+                with_first
+            }
+        }
+    }
+
+    fn evaluate_cards(variant: Variant, mut all_cards: Vec<Self>) -> EvaluatedHand {
+        let hand = |strength, cards| EvaluatedHand(strength, cards, variant);
         all_cards.sort_by_key(|c| 14 - c.value as u64); // reverse sort, high cards first
         assert!(all_cards.len() >= 5, "not enough cards to evaluate hand");
 
@@ -119,18 +262,9 @@ impl Card {
             cards
         };
 
-        let mut with_high_low_ace: Vec<_> = deduped_values
-            .iter()
-            .map(|c| (c.value as u64 + 2, c.value))
-            .chain(
-                // handle the case where Ace is low
-                deduped_values
-                    .iter()
-                    .filter(|c| c.value == CardValue::Ace)
-                    .map(|c| (1, c.value)),
-            )
-            .collect();
-        with_high_low_ace.sort_by_key(|(v, _)| 14 - v);
+        let low_ace = variant.low_ace_rank();
+        let with_high_low_ace =
+            Self::with_high_low_ace(deduped_values.iter().map(|c| c.value), low_ace);
 
         // check for royal flush
         // example: [Ace, King, Queen, Jack, Ten] of the same suite
@@ -144,22 +278,22 @@ impl Card {
                 CardValue::Ten,
             ];
             if cards[..5] == royal_flush_cards {
-                return EvaluatedHand(HandStrength::RoyalFlush, royal_flush_cards);
+                return hand(HandStrength::RoyalFlush, royal_flush_cards);
             }
         }
 
         // check for straight flush
-        // example: [8, 7, 6, 5, 4] of the same suite
-        for (_, cards) in by_suite.iter().filter(|(_, cards)| cards.len() >= 5) {
-            let cards = cards.iter().map(|c| c.value).collect::<Vec<_>>();
-            for w in cards.windows(5) {
-                if (w[0] as u64) - (w[4] as u64) == 4 {
-                    return EvaluatedHand(
-                        HandStrength::StraightFlush,
-                        [w[0], w[1], w[2], w[3], w[4]],
-                    );
-                }
-            }
+        // example: [8, 7, 6, 5, 4] (or [5, 4, 3, 2, Ace]) of the same suite
+        let straight_flush = by_suite
+            .iter()
+            .filter(|(_, cards)| cards.len() >= 5)
+            .filter_map(|(_, cards)| {
+                let suited = Self::with_high_low_ace(cards.iter().map(|c| c.value), low_ace);
+                Self::highest_straight(&suited)
+            })
+            .max_by_key(|(high, _)| *high);
+        if let Some((_, cards)) = straight_flush {
+            return hand(HandStrength::StraightFlush, cards);
         }
 
         // check for four of a kind
@@ -170,56 +304,53 @@ impl Card {
                 .find(|v| v.value != *value)
                 .expect("four of a kind should have a card of a different value");
             let (value, other) = (*value, other.value);
-            return EvaluatedHand(
+            return hand(
                 HandStrength::FourOfAKind,
                 [value, value, value, value, other],
             );
         }
 
-        // check for full house
+        let flush = by_suite
+            .values()
+            .find(|cards| cards.len() >= 5)
+            .map(|cards| [cards[0], cards[1], cards[2], cards[3], cards[4]].map(|c| c.value));
+
+        // check for full house, which ranks below a flush in short-deck
         // example: [King, King, King, 2, 2]
-        for (value, _) in by_value.iter().filter(|(_, cards)| cards.len() == 3) {
+        let flush_beats_full_house = variant == Variant::ShortDeck && flush.is_some();
+        let trips = by_value.iter().rev().filter(|(_, cards)| cards.len() == 3);
+        for (value, _) in trips.filter(|_| !flush_beats_full_house) {
             for (other, _) in by_value
                 .iter()
+                .rev()
                 .filter(|(other_value, cards)| cards.len() >= 2 && *other_value != value)
             {
                 let (value, other) = (*value, *other);
-                return EvaluatedHand(HandStrength::FullHouse, [value, value, value, other, other]);
+                return hand(HandStrength::FullHouse, [value, value, value, other, other]);
             }
         }
 
         // check for flush
         // example: [King, 10, 8, 7, 2] of the same suite
-        for (_, cards) in by_suite.iter().filter(|(_, cards)| cards.len() >= 5) {
-            let cards = cards.iter().map(|c| c.value).collect::<Vec<_>>();
-            return EvaluatedHand(
-                HandStrength::Flush,
-                [cards[0], cards[1], cards[2], cards[3], cards[4]],
-            );
+        if let Some(cards) = flush {
+            return hand(HandStrength::Flush, cards);
         }
 
         // check for straight
         // example: [8, 7, 6, 5, 4] (or [5, 4, 3, 2, Ace] for the wheel straight)
-        for w in with_high_low_ace.windows(5) {
-            let card1_value = w[0].0;
-            let card5_value = w[4].0;
-            if (card1_value - card5_value) == 4 {
-                return EvaluatedHand(
-                    HandStrength::Straight,
-                    [w[0].1, w[1].1, w[2].1, w[3].1, w[4].1],
-                );
-            }
+        if let Some((_, cards)) = Self::highest_straight(&with_high_low_ace) {
+            return hand(HandStrength::Straight, cards);
         }
 
         // check for three of a kind
         // example: [King, King, King, 7, 2]
-        for (value, _) in by_value.iter().filter(|(_, cards)| cards.len() == 3) {
+        if let Some((value, _)) = by_value.iter().rev().find(|(_, cards)| cards.len() == 3) {
             let cards = all_cards
                 .iter()
                 .filter(|c| c.value != *value)
                 .map(|c| c.value)
                 .collect::<Vec<_>>();
-            return EvaluatedHand(
+            return hand(
                 HandStrength::ThreeOfAKind,
                 [*value, *value, *value, cards[0], cards[1]],
             );
@@ -227,9 +358,10 @@ impl Card {
 
         // check for two pair
         // example: [King, King, 7, 7, 2]
-        for (value_1, _) in by_value.iter().filter(|(_, cards)| cards.len() == 2) {
+        for (value_1, _) in by_value.iter().rev().filter(|(_, cards)| cards.len() == 2) {
             for (value_2, _) in by_value
                 .iter()
+                .rev()
                 .filter(|(value, cards)| cards.len() == 2 && value_1 != *value)
             {
                 let other = all_cards
@@ -237,7 +369,7 @@ impl Card {
                     .find(|c| c.value != *value_1 && c.value != *value_2)
                     .expect("two pair should have a card of a different value");
                 let (value_1, value_2, other) = (*value_1, *value_2, other.value);
-                return EvaluatedHand(
+                return hand(
                     HandStrength::TwoPair,
                     [value_1, value_1, value_2, value_2, other],
                 );
@@ -252,7 +384,7 @@ impl Card {
                 .filter(|c| c.value != *value)
                 .map(|c| c.value)
                 .collect::<Vec<_>>();
-            return EvaluatedHand(
+            return hand(
                 HandStrength::OnePair,
                 [*value, *value, cards[0], cards[1], cards[2]],
             );
@@ -260,7 +392,7 @@ impl Card {
 
         // fallback to high card
         // example: [King, 10, 8, 7, 2]
-        EvaluatedHand(
+        hand(
             HandStrength::HighCard,
             [
                 deduped_values[0].value,
@@ -271,6 +403,35 @@ impl Card {
             ],
         )
     }
+
+    /// Pairs each value with its rank, counting an Ace as both high and low, sorted high to low.
+    fn with_high_low_ace(
+        values: impl Iterator<Item = CardValue>,
+        low_ace: u64,
+    ) -> Vec<(u64, CardValue)> {
+        let values: Vec<_> = values.collect();
+        let mut with_high_low_ace: Vec<_> = values
+            .iter()
+            .map(|v| (*v as u64 + 2, *v))
+            .chain(
+                // handle the case where Ace is low
+                values
+                    .iter()
+                    .filter(|v| **v == CardValue::Ace)
+                    .map(|v| (low_ace, *v)),
+            )
+            .collect();
+        with_high_low_ace.sort_by_key(|(v, _)| 14 - v);
+        with_high_low_ace
+    }
+
+    /// Finds the highest run of five consecutive ranks, returning its top rank and cards.
+    fn highest_straight(values: &[(u64, CardValue)]) -> Option<(u64, [CardValue; 5])> {
+        values
+            .windows(5)
+            .find(|w| w[0].0 - w[4].0 == 4)
+            .map(|w| (w[0].0, [w[0].1, w[1].1, w[2].1, w[3].1, w[4].1]))
+    }
 }
 
 #[derive(
@@ -316,8 +477,8 @@ pub enum CardValue {
     Ace,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord)]
-pub struct EvaluatedHand(HandStrength, [CardValue; 5]);
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvaluatedHand(HandStrength, [CardValue; 5], Variant);
 
 impl EvaluatedHand {
     pub fn strength(&self) -> HandStrength {
@@ -328,99 +489,355 @@ impl EvaluatedHand {
     }
 }
 
+impl Ord for EvaluatedHand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let (self_strength, self_hand) = (self.2.rank(self.0), &self.1);
+        let (other_strength, other_hand) = (other.2.rank(other.0), &other.1);
+
+        self_strength
+            .cmp(&other_strength)
+            .then_with(|| self_hand.cmp(other_hand))
+    }
+}
+
 impl PartialOrd for EvaluatedHand {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        let (self_strength, self_hand) = (self.0 as u8, &self.1);
-        let (other_strength, other_hand) = (other.0 as u8, &other.1);
-
-        match self_strength.partial_cmp(&other_strength) {
-            Some(std::cmp::Ordering::Equal) => self_hand
-                .iter()
-                .zip(other_hand.iter())
-                .find_map(|(self_card_value, other_card_value)| {
-                    self_card_value
-                        .partial_cmp(other_card_value)
-                        .filter(|x| !matches!(x, std::cmp::Ordering::Equal))
-                })
-                .or(Some(std::cmp::Ordering::Equal)),
-            x => x,
-        }
+        Some(self.cmp(other))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use helpers::{cards_1p, cards_2p};
+    use helpers::{cards_1p, cards_2p, parse_shorthand};
 
     use super::*;
 
+    #[test]
+    fn deck_seeded_is_reproducible() {
+        let order =
+            |deck: Deck| -> Vec<_> { deck.remaining().map(|c| (c.suite, c.value)).collect() };
+
+        assert_eq!(order(Deck::seeded(42)), order(Deck::seeded(42)));
+        assert_ne!(order(Deck::seeded(42)), order(Deck::seeded(43)));
+        assert_eq!(order(Deck::seeded(42)).len(), 52);
+    }
+
+    #[test]
+    fn deck_seeded_order_is_pinned() {
+        // a seed from a bug report has to deal the same cards after a dependency bump
+        let top: Vec<_> = Deck::seeded(42)
+            .remaining()
+            .take(5)
+            .map(|c| (c.suite, c.value))
+            .collect();
+        let expected: Vec<_> = ["Kh", "10c", "9h", "9s", "Kc"]
+            .into_iter()
+            .map(parse_shorthand)
+            .map(|c| (c.suite, c.value))
+            .collect();
+        assert_eq!(top, expected);
+    }
+
+    #[test]
+    fn deck_cut_rotates_order() {
+        let order =
+            |deck: &Deck| -> Vec<_> { deck.remaining().map(|c| (c.suite, c.value)).collect() };
+        let original = order(&Deck::ordered());
+
+        let mut deck = Deck::ordered();
+        deck.cut(10);
+        let cut = order(&deck);
+        assert_eq!(cut.len(), 52);
+        assert_eq!(cut[..42], original[10..]);
+        assert_eq!(cut[42..], original[..10]);
+
+        let mut deck = Deck::ordered();
+        deck.cut(0);
+        deck.cut(52);
+        assert_eq!(order(&deck), original);
+    }
+
+    #[test]
+    fn deck_short_deck_drops_two_to_five() {
+        let deck = Deck::default().for_variant(Variant::ShortDeck);
+        assert_eq!(deck.remaining().count(), 36);
+        assert!(deck.remaining().all(|c| c.value >= CardValue::Six));
+    }
+
+    #[test]
+    fn cards_evaluate_hand_short_deck_wheel() {
+        let (player_cards, table_cards) = cards_1p("Ah 6d", "7h 8c 9s Kd Qd");
+        let hole_cards = [player_cards.0, player_cards.1];
+        let hand = Card::evaluate_hand_for(Variant::ShortDeck, &hole_cards, &table_cards);
+        assert_eq!(hand.strength(), HandStrength::Straight);
+        assert_eq!(
+            hand.cards(),
+            &[
+                CardValue::Nine,
+                CardValue::Eight,
+                CardValue::Seven,
+                CardValue::Six,
+                CardValue::Ace
+            ]
+        );
+
+        let hand = Card::evaluate_hand(&player_cards, &table_cards);
+        assert_eq!(hand.strength(), HandStrength::HighCard);
+    }
+
+    #[test]
+    fn cards_evaluate_hand_omaha_uses_exactly_two_hole_cards() {
+        let cards = |s: &str| {
+            s.split_whitespace()
+                .map(parse_shorthand)
+                .collect::<Vec<_>>()
+        };
+        let table_cards = cards("2h 7h 9h Jh Kc");
+
+        let hand = Card::evaluate_hand_for(Variant::Omaha, &cards("Ah 3c 4d 5s"), &table_cards);
+        assert_eq!(hand.strength(), HandStrength::HighCard);
+
+        let hand = Card::evaluate_hand_for(Variant::Standard, &cards("Ah 3c"), &table_cards);
+        assert_eq!(hand.strength(), HandStrength::Flush);
+
+        let hand = Card::evaluate_hand_for(Variant::Omaha, &cards("Ah 3h 4d 5s"), &table_cards);
+        assert_eq!(hand.strength(), HandStrength::Flush);
+        assert_eq!(hand.cards()[0], CardValue::Ace);
+    }
+
+    #[test]
+    fn cards_evaluate_hand_short_deck_flush_beats_full_house() {
+        let (flush, full_house, table_cards) = cards_2p("Ah 7h", "Kc Ks", "Kh Qh 9h 9c 6d");
+        let flush = Card::evaluate_hand_for(Variant::ShortDeck, &[flush.0, flush.1], &table_cards);
+        let full_house = Card::evaluate_hand_for(
+            Variant::ShortDeck,
+            &[full_house.0, full_house.1],
+            &table_cards,
+        );
+        assert_eq!(flush.strength(), HandStrength::Flush);
+        assert_eq!(full_house.strength(), HandStrength::FullHouse);
+        assert!(flush > full_house);
+    }
+
     #[test]
     fn cards_evaluate_hand_royal_flush() {
         let (player_cards, table_cards) = cards_1p("Ah Kh", "Qh Jh 10h 9h 8h");
-        let EvaluatedHand(score, _) = Card::evaluate_hand(&player_cards, &table_cards);
+        let EvaluatedHand(score, ..) = Card::evaluate_hand(&player_cards, &table_cards);
         assert_eq!(score, HandStrength::RoyalFlush);
     }
 
     #[test]
     fn cards_evaluate_hand_straight_flush() {
         let (player_cards, table_cards) = cards_1p("8h 7h", "6h 5h 4h 3c 2c");
-        let EvaluatedHand(score, _) = Card::evaluate_hand(&player_cards, &table_cards);
+        let EvaluatedHand(score, ..) = Card::evaluate_hand(&player_cards, &table_cards);
         assert_eq!(score, HandStrength::StraightFlush);
     }
 
+    #[test]
+    fn cards_evaluate_hand_straight_flush_picks_highest() {
+        let (player_cards, table_cards) = cards_1p("9h 8h", "7h 6h 5h 4h 2c");
+        let hand = Card::evaluate_hand(&player_cards, &table_cards);
+        assert_eq!(hand.strength(), HandStrength::StraightFlush);
+        assert_eq!(
+            hand.cards(),
+            &[
+                CardValue::Nine,
+                CardValue::Eight,
+                CardValue::Seven,
+                CardValue::Six,
+                CardValue::Five
+            ]
+        );
+    }
+
+    #[test]
+    fn cards_evaluate_hand_straight_flush_wheel() {
+        let (player_cards, table_cards) = cards_1p("Ah 2h", "3h 4h 5h Kc Jd");
+        let hand = Card::evaluate_hand(&player_cards, &table_cards);
+        assert_eq!(hand.strength(), HandStrength::StraightFlush);
+        assert_eq!(
+            hand.cards(),
+            &[
+                CardValue::Five,
+                CardValue::Four,
+                CardValue::Three,
+                CardValue::Two,
+                CardValue::Ace
+            ]
+        );
+    }
+
     #[test]
     fn cards_evaluate_hand_four_of_a_kind() {
         let (player_cards, table_cards) = cards_1p("Kh Kd", "Kc Ks 2h 3c 4d");
-        let EvaluatedHand(score, _) = Card::evaluate_hand(&player_cards, &table_cards);
+        let EvaluatedHand(score, ..) = Card::evaluate_hand(&player_cards, &table_cards);
         assert_eq!(score, HandStrength::FourOfAKind);
     }
 
     #[test]
     fn cards_evaluate_hand_full_house() {
         let (player_cards, table_cards) = cards_1p("Kh Kd", "Kc 2h 2c 2s 3d");
-        let EvaluatedHand(score, _) = Card::evaluate_hand(&player_cards, &table_cards);
+        let EvaluatedHand(score, ..) = Card::evaluate_hand(&player_cards, &table_cards);
         assert_eq!(score, HandStrength::FullHouse);
     }
 
+    #[test]
+    fn cards_evaluate_hand_full_house_uses_highest_trips() {
+        let (player_cards, table_cards) = cards_1p("Kh Kd", "Kc 2h 2c 2s 3d");
+        let hand = Card::evaluate_hand(&player_cards, &table_cards);
+        assert_eq!(hand.strength(), HandStrength::FullHouse);
+        assert_eq!(
+            hand.cards(),
+            &[
+                CardValue::King,
+                CardValue::King,
+                CardValue::King,
+                CardValue::Two,
+                CardValue::Two
+            ]
+        );
+    }
+
+    #[test]
+    fn cards_evaluate_hand_full_house_uses_highest_pair() {
+        let (player_cards, table_cards) = cards_1p("Kh Kd", "Kc 2h 2c 7s 7d");
+        let hand = Card::evaluate_hand(&player_cards, &table_cards);
+        assert_eq!(hand.strength(), HandStrength::FullHouse);
+        assert_eq!(hand.cards()[3..], [CardValue::Seven, CardValue::Seven]);
+    }
+
+    #[test]
+    fn cards_evaluate_hand_compare_full_house_by_trips() {
+        let (player_1_cards, player_2_cards, table_cards) =
+            cards_2p("Qh Qd", "9d 3d", "Qc 3c 9s 9h 2d");
+        let player_1_score = Card::evaluate_hand(&player_1_cards, &table_cards);
+        let player_2_score = Card::evaluate_hand(&player_2_cards, &table_cards);
+        assert_eq!(player_1_score.strength(), HandStrength::FullHouse);
+        assert_eq!(player_2_score.strength(), HandStrength::FullHouse);
+
+        assert!(player_1_score > player_2_score);
+    }
+
     #[test]
     fn cards_evaluate_hand_flush() {
         let (player_cards, table_cards) = cards_1p("Kh 10h", "8h 7h 2h 3c 4d");
-        let EvaluatedHand(score, _) = Card::evaluate_hand(&player_cards, &table_cards);
+        let EvaluatedHand(score, ..) = Card::evaluate_hand(&player_cards, &table_cards);
         assert_eq!(score, HandStrength::Flush);
     }
 
+    #[test]
+    fn cards_evaluate_hand_compare_flush_by_fifth_card() {
+        let (player_1_cards, player_2_cards, table_cards) =
+            cards_2p("7h 3c", "6h 3d", "Ah Kh Qh 9h 2c");
+        let player_1_score = Card::evaluate_hand(&player_1_cards, &table_cards);
+        let player_2_score = Card::evaluate_hand(&player_2_cards, &table_cards);
+        assert_eq!(player_1_score.strength(), HandStrength::Flush);
+        assert_eq!(player_2_score.strength(), HandStrength::Flush);
+        assert_eq!(player_1_score.cards()[4], CardValue::Seven);
+        assert_eq!(player_2_score.cards()[4], CardValue::Six);
+
+        assert!(player_1_score > player_2_score);
+    }
+
+    #[test]
+    fn cards_evaluate_hand_flush_uses_five_highest_suited_cards() {
+        let (player_1_cards, player_2_cards, table_cards) =
+            cards_2p("8h 2h", "7h 3h", "Ah Kh 9h 4h 2c");
+        let player_1_score = Card::evaluate_hand(&player_1_cards, &table_cards);
+        let player_2_score = Card::evaluate_hand(&player_2_cards, &table_cards);
+        assert_eq!(
+            player_1_score.cards(),
+            &[
+                CardValue::Ace,
+                CardValue::King,
+                CardValue::Nine,
+                CardValue::Eight,
+                CardValue::Four
+            ]
+        );
+
+        assert!(player_1_score > player_2_score);
+    }
+
     #[test]
     fn cards_evaluate_hand_straight() {
         let (player_cards, table_cards) = cards_1p("8h 7d", "6h 5h 4c Kc Jd");
-        let EvaluatedHand(score, _) = Card::evaluate_hand(&player_cards, &table_cards);
+        let EvaluatedHand(score, ..) = Card::evaluate_hand(&player_cards, &table_cards);
         assert_eq!(score, HandStrength::Straight);
     }
 
     #[test]
     fn cards_evaluate_hand_straight_wheel() {
         let (player_cards, table_cards) = cards_1p("5h 4d", "3h 2h Ac Kc Jd");
-        let EvaluatedHand(score, _) = Card::evaluate_hand(&player_cards, &table_cards);
+        let EvaluatedHand(score, ..) = Card::evaluate_hand(&player_cards, &table_cards);
         assert_eq!(score, HandStrength::Straight);
     }
 
+    #[test]
+    fn cards_evaluate_hand_straight_wheel_mixed_suits() {
+        let (player_cards, table_cards) = cards_1p("Ah 2d", "3c 4s 5h 9c Jd");
+        let hand = Card::evaluate_hand(&player_cards, &table_cards);
+        assert_eq!(hand.strength(), HandStrength::Straight);
+        assert_eq!(
+            hand.cards(),
+            &[
+                CardValue::Five,
+                CardValue::Four,
+                CardValue::Three,
+                CardValue::Two,
+                CardValue::Ace
+            ]
+        );
+    }
+
+    #[test]
+    fn cards_evaluate_hand_straight_six_high() {
+        let (player_cards, table_cards) = cards_1p("6h 2d", "3c 4s 5h 5c Jd");
+        let hand = Card::evaluate_hand(&player_cards, &table_cards);
+        assert_eq!(hand.strength(), HandStrength::Straight);
+        assert_eq!(
+            hand.cards(),
+            &[
+                CardValue::Six,
+                CardValue::Five,
+                CardValue::Four,
+                CardValue::Three,
+                CardValue::Two
+            ]
+        );
+    }
+
+    #[test]
+    fn cards_evaluate_hand_straight_prefers_higher_over_wheel() {
+        let (player_1_cards, player_2_cards, table_cards) =
+            cards_2p("Ah 6d", "Ad Kc", "2c 3s 4h 5c Jd");
+        let player_1 = Card::evaluate_hand(&player_1_cards, &table_cards);
+        let player_2 = Card::evaluate_hand(&player_2_cards, &table_cards);
+        assert_eq!(player_1.strength(), HandStrength::Straight);
+        assert_eq!(player_1.cards()[0], CardValue::Six);
+        assert_eq!(player_2.strength(), HandStrength::Straight);
+        assert_eq!(player_2.cards()[0], CardValue::Five);
+        assert!(player_1 > player_2);
+    }
+
     #[test]
     fn cards_evaluate_hand_three_of_a_kind() {
         let (player_cards, table_cards) = cards_1p("Kh Kd", "Kc 7h 2c 3s 4d");
-        let EvaluatedHand(score, _) = Card::evaluate_hand(&player_cards, &table_cards);
+        let EvaluatedHand(score, ..) = Card::evaluate_hand(&player_cards, &table_cards);
         assert_eq!(score, HandStrength::ThreeOfAKind);
     }
 
     #[test]
     fn cards_evaluate_hand_two_pair() {
         let (player_cards, table_cards) = cards_1p("Kh Kd", "7c 7h 2c 2s 3d");
-        let EvaluatedHand(score, _) = Card::evaluate_hand(&player_cards, &table_cards);
+        let EvaluatedHand(score, ..) = Card::evaluate_hand(&player_cards, &table_cards);
         assert_eq!(score, HandStrength::TwoPair);
     }
 
     #[test]
     fn cards_evaluate_hand_one_pair() {
         let (player_cards, table_cards) = cards_1p("Kh 10c", "Kd 7h 2c 3s 4d");
-        let EvaluatedHand(score, _) = Card::evaluate_hand(&player_cards, &table_cards);
+        let EvaluatedHand(score, ..) = Card::evaluate_hand(&player_cards, &table_cards);
         assert_eq!(score, HandStrength::OnePair);
     }
 
@@ -445,6 +862,37 @@ mod tests {
         assert!(player_1_score < player_2_score);
     }
 
+    #[test]
+    fn cards_evaluate_hand_two_pair_orders_pairs_high_first() {
+        let (player_cards, table_cards) = cards_1p("Kh 7d", "Kc 7h 2c 2s 3d");
+        let hand = Card::evaluate_hand(&player_cards, &table_cards);
+        assert_eq!(hand.strength(), HandStrength::TwoPair);
+        assert_eq!(
+            hand.cards(),
+            &[
+                CardValue::King,
+                CardValue::King,
+                CardValue::Seven,
+                CardValue::Seven,
+                CardValue::Three
+            ]
+        );
+    }
+
+    #[test]
+    fn cards_evaluate_hand_compare_two_pair_by_kicker() {
+        let (player_1_cards, player_2_cards, table_cards) =
+            cards_2p("Kh 10c", "Kd 9c", "Ks 7h 7c 2d 3s");
+        let player_1_score = Card::evaluate_hand(&player_1_cards, &table_cards);
+        let player_2_score = Card::evaluate_hand(&player_2_cards, &table_cards);
+        assert_eq!(player_1_score.strength(), HandStrength::TwoPair);
+        assert_eq!(player_2_score.strength(), HandStrength::TwoPair);
+        assert_eq!(player_1_score.cards()[4], CardValue::Ten);
+        assert_eq!(player_2_score.cards()[4], CardValue::Nine);
+
+        assert!(player_1_score > player_2_score);
+    }
+
     mod helpers {
         use super::*;
 
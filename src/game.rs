@@ -4,22 +4,57 @@ use std::{
 };
 
 use crate::{
+    app_metrics::{metrics_labels, Metrics},
     cards, models,
     state::{self, TickerEvent},
 };
 
 use tracing::info;
 
-pub fn spawn_game_worker(shared_state: state::SharedState) -> tokio::task::JoinHandle<()> {
-    async fn run_tasks(room_state: &state::RoomState, shared_state: &state::SharedState) {
+/// Signals the game worker and the server to stop.
+#[derive(Debug, Clone)]
+pub struct ShutdownHandle(tokio::sync::broadcast::Sender<()>);
+
+impl ShutdownHandle {
+    fn new() -> Self {
+        let (sender, _) = tokio::sync::broadcast::channel(1);
+        Self(sender)
+    }
+
+    pub fn shutdown(&self) {
+        _ = self.0.send(());
+    }
+
+    /// Resolves once `shutdown` is called; subscribes immediately so a signal
+    /// sent before the future is polled is not missed.
+    pub fn signal(&self) -> impl std::future::Future<Output = ()> {
+        let mut receiver = self.0.subscribe();
+        async move {
+            _ = receiver.recv().await;
+        }
+    }
+}
+
+pub fn spawn_game_worker(
+    shared_state: state::SharedState,
+) -> (tokio::task::JoinHandle<()>, ShutdownHandle) {
+    async fn run_tasks(
+        room_state: &state::RoomState,
+        shared_state: &state::SharedState,
+        shutdown: &ShutdownHandle,
+    ) {
         let now = state::dt::Instant::default();
 
         let state = room_state.read().await;
-        let status = state.status.clone();
-        let last_update = state.last_update.as_u64();
+        let idle_expired = room_idle_expired(&state, now);
         let players_turn = state.round.players_turn.clone();
         let current_player = players_turn.and_then(|id| state.players.get(&id)).cloned();
+        let turn_warning_due = turn_warning_due(&state, now);
+        let next_hand_due = next_hand_at(&state).is_some_and(|at| at <= now.as_u64());
         let ticker_expired = state.ticker.has_expired_items(now);
+        let vote_expired = state.vote.as_ref().is_some_and(|vote| vote.end_time < now);
+        let reservations_expired = state.reservations.values().any(|expiry| *expiry < now);
+        let spectators_expired = state.spectators.has_expired(now);
         let players = state.players.iter();
         let expired_emoji_players = players
             .filter(|(_, p)| {
@@ -31,13 +66,6 @@ pub fn spawn_game_worker(shared_state: state::SharedState) -> tokio::task::JoinH
             .collect::<Vec<_>>();
         drop(state);
 
-        let now_ms: u64 = now.into();
-        let idle_ms = match status {
-            state::GameStatus::Joining => Some(state::GAME_IDLE_TIMEOUT_SECONDS * 1000),
-            state::GameStatus::Complete => Some(state::GAME_IDLE_TIMEOUT_SECONDS * 1000 * 4),
-            state::GameStatus::Playing | state::GameStatus::Idle => None,
-        };
-
         if !expired_emoji_players.is_empty() {
             let mut state = room_state.write().await;
             for player_id in expired_emoji_players {
@@ -48,21 +76,33 @@ pub fn spawn_game_worker(shared_state: state::SharedState) -> tokio::task::JoinH
             state.last_update.set_now();
         }
 
-        if idle_ms.map_or(false, |idle_ms| now_ms - last_update > idle_ms) {
+        if idle_expired {
             if let Ok("true") = std::env::var("KILL_ON_IDLE").as_deref() {
-                info!("KILL_ON_IDLE is set, exiting...");
-                // TODO: graceful shutdown
-                std::process::exit(0);
+                info!("KILL_ON_IDLE is set, shutting down...");
+                shutdown.shutdown();
+                return;
             }
 
             let mut state = room_state.write().await;
-            if !state.round.deck.is_fresh() || state.status == state::GameStatus::Complete {
-                info!("Game idle timeout, resetting game");
-                *state = state::State::default();
+            if reset_idle_room(&mut state) {
                 state.last_update.set_now();
             }
         };
 
+        if next_hand_due {
+            let mut state = room_state.write().await;
+            if auto_start_next_hand(&mut state, now) {
+                state.last_update.set_now();
+            }
+        }
+
+        if turn_warning_due {
+            let mut state = room_state.write().await;
+            if warn_player_turn(&mut state, now) {
+                state.last_update.set_now();
+            }
+        }
+
         if let Some(player) = current_player {
             let expired = player.ttl.map(|ttl| ttl < now).unwrap_or(false);
             if expired {
@@ -73,44 +113,109 @@ pub fn spawn_game_worker(shared_state: state::SharedState) -> tokio::task::JoinH
             }
         }
 
+        if vote_expired {
+            let mut state = room_state.write().await;
+            let motion = state.vote.as_ref().map(|vote| vote.motion.clone());
+            if resolve_vote(&mut state, now).is_some() {
+                unregister_kicked_player(&state, shared_state, motion).await;
+                state.last_update.set_now();
+            }
+        }
+
         if ticker_expired {
             let mut state = room_state.write().await;
             state.ticker.clear_expired_items(now);
         }
+
+        if reservations_expired {
+            let mut state = room_state.write().await;
+            expire_seat_reservations(&mut state, now);
+            state.last_update.set_now();
+        }
+
+        if spectators_expired {
+            let state = room_state.read().await;
+            expire_spectators(&state, now);
+        }
     }
 
-    tokio::spawn(async move {
+    let shutdown = ShutdownHandle::new();
+    let worker_shutdown = shutdown.clone();
+    let handle = tokio::spawn(async move {
+        let mut shutdown_signal = std::pin::pin!(worker_shutdown.signal());
         loop {
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            shared_state.record_worker_tick();
+            tokio::select! {
+                _ = tokio::time::sleep(tokio::time::Duration::from_secs(1)) => {}
+                _ = &mut shutdown_signal => {
+                    info!("Game worker shutting down");
+                    break;
+                }
+            }
 
             shared_state.cleanup().await;
 
             for state in shared_state.iter().await {
-                run_tasks(&state, &shared_state).await;
+                run_tasks(&state, &shared_state, &worker_shutdown).await;
             }
         }
-    })
+    });
+
+    (handle, shutdown)
 }
 
 pub(crate) fn start_game(state: &mut state::State) -> Result<(), String> {
+    begin_game(state, true)
+}
+
+/// Starts the game without waiting for every seated player to be ready.
+pub(crate) fn force_start_game(state: &mut state::State) -> Result<(), String> {
+    begin_game(state, false)
+}
+
+fn begin_game(state: &mut state::State, require_ready: bool) -> Result<(), String> {
     if state.status == state::GameStatus::Playing {
         return Err("Game already started".to_string());
     }
-    if state.players.len() < 2 {
+    if state.players.values().filter(|p| !p.sitting_out).count() < 2 {
         return Err("Not enough players".to_string());
     }
+    if require_ready && state.players.values().any(|p| !p.sitting_out && !p.ready) {
+        return Err("Not all players are ready".to_string());
+    }
 
     state.round.cards_on_table.clear();
     state.round.pot = 0;
-    state.round.completed = None;
+    state.last_completed = state.round.completed.take();
+    state.round.history.clear();
+    state.round.big_blind = None;
+    state.round.straddle = None;
+    state.round.deck_commitment = None;
     reset_players(state);
+    for player in state.players.values_mut().filter(|p| !p.sitting_out) {
+        player.stats.hands_played += 1;
+    }
+    if let Some(small_blind) = state.next_small_blind.take() {
+        state.small_blind = Some(small_blind);
+    }
+    raise_scheduled_blinds(state);
+    state.hands_started += 1;
     next_turn(state, None);
     if !state.config.card_deal_disabled() {
-        state.round.deck = cards::Deck::default();
+        if !std::mem::take(&mut state.round.reshuffled) {
+            state.round.deck = match state.config.deck_seed() {
+                Some(seed) => cards::Deck::seeded(seed.wrapping_add(state.hands_started - 1)),
+                None => cards::Deck::default(),
+            }
+            .for_variant(state.config.variant());
+        }
+        if let Some(at) = state.config.deck_cut() {
+            state.round.deck.cut(at);
+        }
+        state.round.deck_commitment = Some(commit_deck(&state.round.deck));
+        let hole_cards = state.config.variant().hole_cards();
         for player in state.players.values_mut() {
-            let card_1 = state.round.deck.pop();
-            let card_2 = state.round.deck.pop();
-            player.cards = (card_1, card_2);
+            player.cards = (0..hole_cards).map(|_| state.round.deck.pop()).collect();
         }
     }
 
@@ -120,6 +225,83 @@ pub(crate) fn start_game(state: &mut state::State) -> Result<(), String> {
     Ok(())
 }
 
+fn commit_deck(deck: &cards::Deck) -> state::DeckCommitment {
+    let seed: [u8; 16] = rand::random();
+    let seed: String = seed.iter().map(|b| format!("{:02x}", b)).collect();
+    let deck: Vec<_> = deck.remaining().copied().collect();
+    let hash = deck_commitment_hash(&seed, &deck);
+    state::DeckCommitment { seed, deck, hash }
+}
+
+fn deck_commitment_hash(seed: &str, deck: &[cards::Card]) -> String {
+    use sha2::{Digest, Sha256};
+
+    let deck: Vec<_> = deck.iter().map(|c| (c.suite, c.value)).collect();
+    let deck = serde_json::to_string(&deck).expect("deck serializes");
+    let mut hasher = Sha256::new();
+    hasher.update(seed.as_bytes());
+    hasher.update(b":");
+    hasher.update(deck.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+pub(crate) fn deck_commitment(state: &state::State) -> Option<String> {
+    state.round.deck_commitment.as_ref().map(|c| c.hash.clone())
+}
+
+/// Starts a new game with the same players, resetting every balance to the
+/// configured starting balance.
+pub(crate) fn rematch(state: &mut state::State) -> Result<(), String> {
+    if state.status != state::GameStatus::Complete {
+        return Err("Game not complete".to_string());
+    }
+
+    let starting_balance = state.config.starting_balance();
+    for player in state.players.values_mut() {
+        player.balance = starting_balance;
+        player.buy_in_total = starting_balance;
+        player.transferred = 0;
+    }
+    state.round.raises.clear();
+    state.round.calls.clear();
+    // the blind schedule starts over with the new game
+    state.hands_started = 0;
+    state.blind_level = 0;
+    state.small_blind = None;
+    state.next_small_blind = None;
+
+    start_game(state)
+}
+
+/// Swaps in a freshly shuffled deck between hands.
+pub(crate) fn reshuffle(state: &mut state::State) -> Result<(), String> {
+    if !matches!(
+        state.status,
+        state::GameStatus::Joining | state::GameStatus::Complete
+    ) {
+        return Err("Cannot reshuffle during a hand".to_string());
+    }
+
+    state.round.deck = cards::Deck::default().for_variant(state.config.variant());
+    state.round.reshuffled = true;
+    state.ticker.emit(TickerEvent::DeckReshuffled);
+
+    Ok(())
+}
+
+fn room_full(state: &state::State) -> bool {
+    state.players.len() + state.reservations.len() >= state.config.max_players()
+}
+
+/// Whether a new player could take a seat right now.
+pub(crate) fn is_joinable(state: &state::State) -> bool {
+    state.status == state::GameStatus::Joining && !state.disposed && !room_full(state)
+}
+
 pub(crate) fn add_new_player(
     state: &mut state::State,
     player_name: &str,
@@ -128,7 +310,7 @@ pub(crate) fn add_new_player(
     if state.status == state::GameStatus::Playing {
         return Err("Game already started".to_string());
     }
-    if state.players.len() >= state.config.max_players() {
+    if room_full(state) {
         return Err("Room is full".to_string());
     }
 
@@ -137,22 +319,50 @@ pub(crate) fn add_new_player(
     if player_name.is_empty() {
         return Err("Name cannot be empty".to_string());
     }
+    let lowercase_name = player_name.to_lowercase();
+    if state
+        .players
+        .values()
+        .any(|p| p.name.to_lowercase() == lowercase_name)
+    {
+        return Err("Name is already taken".to_string());
+    }
 
     let funds_token = state::token::Token::default();
-    let card_1 = state.round.deck.pop();
-    let card_2 = state.round.deck.pop();
+    let seat = state
+        .players
+        .iter()
+        .map(|(_, p)| p.seat + 1)
+        .max()
+        .unwrap_or(0);
+    let cards = (0..state.config.variant().hole_cards())
+        .map(|_| state.round.deck.pop())
+        .collect();
     let player = state::Player {
         name: player_name,
         id: player_id.clone(),
         emoji: None,
         funds_token,
+        session_secret: state::token::Token::secret(),
         balance: state.config.starting_balance(),
+        buy_in_total: state.config.starting_balance(),
         stake: 0,
         folded: false,
+        all_in: false,
+        sitting_out: false,
+        ready: false,
+        muck: false,
+        time_bank_used: false,
+        transferred: 0,
+        queued_action: None,
+        last_action_id: None,
+        stats: state::PlayerStats::default(),
+        seat,
         photo: None,
         ttl: None,
+        last_seen: None,
         apid: uuid::Uuid::new_v4().to_string(),
-        cards: (card_1, card_2),
+        cards,
     };
     state.players.insert(player_id.clone(), player);
     state
@@ -167,6 +377,119 @@ pub(crate) fn set_player_apid(state: &mut state::State, player_id: &state::Playe
     }
 }
 
+/// Whether a room waiting on players has gone without updates for longer than
+/// its idle timeout. Completed games get four times the grace period.
+fn room_idle_expired(state: &state::State, now: state::dt::Instant) -> bool {
+    let idle_ms = state.config.idle_timeout_seconds() * 1000;
+    let idle_ms = match state.status {
+        state::GameStatus::Joining | state::GameStatus::Paused => idle_ms,
+        state::GameStatus::Complete => idle_ms * 4,
+        state::GameStatus::Playing | state::GameStatus::Idle => return false,
+    };
+
+    now.as_u64().saturating_sub(state.last_update.as_u64()) > idle_ms
+}
+
+/// Resets a room that went idle once a hand has been dealt, keeping its config.
+fn reset_idle_room(state: &mut state::State) -> bool {
+    let variant = state.config.variant();
+    if state.round.deck.is_fresh(variant) && state.status != state::GameStatus::Complete {
+        return false;
+    }
+
+    info!("Game idle timeout, resetting game");
+    state.reset();
+    true
+}
+
+/// When the next hand starts on its own, for completed rooms configured to
+/// auto-start.
+pub(crate) fn next_hand_at(state: &state::State) -> Option<u64> {
+    if state.status != state::GameStatus::Complete {
+        return None;
+    }
+    let delay_seconds = state.config.auto_start_seconds()?;
+    Some(state.last_update.as_u64() + delay_seconds * 1000)
+}
+
+fn auto_start_next_hand(state: &mut state::State, now: state::dt::Instant) -> bool {
+    if next_hand_at(state).is_none_or(|at| at > now.as_u64()) {
+        return false;
+    }
+
+    match start_game(state) {
+        Ok(()) => {
+            info!("Next hand started automatically");
+            true
+        }
+        Err(err) => {
+            info!("Could not start next hand automatically: {}", err);
+            false
+        }
+    }
+}
+
+fn turn_warning_due(state: &state::State, now: state::dt::Instant) -> bool {
+    if state.round.players_turn_warned {
+        return false;
+    }
+    let ttl = state
+        .round
+        .players_turn
+        .as_ref()
+        .and_then(|id| state.players.get(id))
+        .and_then(|player| player.ttl);
+
+    ttl.is_some_and(|ttl| ttl.as_u64() <= now.as_u64() + state::PLAYER_TURN_WARNING_SECONDS * 1000)
+}
+
+/// Emits a warning once per turn when the current player is close to timing out.
+fn warn_player_turn(state: &mut state::State, now: state::dt::Instant) -> bool {
+    if !turn_warning_due(state, now) {
+        return false;
+    }
+    let Some(player_id) = state.round.players_turn.clone() else {
+        return false;
+    };
+
+    info!("Player {} turn is about to expire", player_id);
+    state.round.players_turn_warned = true;
+    state.ticker.emit(TickerEvent::PlayerTurnWarning(player_id));
+    true
+}
+
+/// Recovers a stuck turn: folds the current player once their turn has
+/// expired, or moves the turn on when it points at a player who has left.
+pub(crate) fn force_advance(
+    state: &mut state::State,
+    now: state::dt::Instant,
+) -> Result<(), String> {
+    if state.status != state::GameStatus::Playing {
+        return Err("Game not started".to_string());
+    }
+    let player_id = state
+        .round
+        .players_turn
+        .clone()
+        .ok_or("No turn to advance".to_string())?;
+
+    match state.players.get(&player_id) {
+        None => {
+            info!("Turn pointed at missing player {}, moving on", player_id);
+            let next_player_id = action_order(state)
+                .into_iter()
+                .find(|id| is_playable(state, id));
+            set_players_turn(state, next_player_id);
+            Ok(())
+        }
+        Some(player) if player.ttl.is_some_and(|ttl| ttl < now) => {
+            info!("Turn of player {} expired, folding", player_id);
+            fold_player(state, &player_id)
+        }
+        Some(_) => Err("Current turn is not stuck".to_string()),
+    }
+}
+
 async fn timeout_player(
     state: &mut state::State,
     shared_state: &state::SharedState,
@@ -188,12 +511,23 @@ async fn timeout_player(
             .emit(TickerEvent::PlayerTurnTimeout(player.name));
     }
     if state.players.len() < 2 {
-        info!("Not enough players, pausing game until more players join");
-        state.status = state::GameStatus::Joining;
-        state.round = state::Round::default();
-        for player in state.players.values_mut() {
-            player.ttl = None;
-        }
+        pause_game(state);
+    }
+}
+
+/// Stops the current hand until more players join. A room that never started
+/// stays in `Joining`, while one that was already running becomes `Paused`.
+fn pause_game(state: &mut state::State) {
+    info!("Not enough players, pausing game until more players join");
+    if matches!(
+        state.status,
+        state::GameStatus::Playing | state::GameStatus::Complete
+    ) {
+        state.status = state::GameStatus::Paused;
+    }
+    state.round = state::Round::default();
+    for player in state.players.values_mut() {
+        player.ttl = None;
     }
 }
 
@@ -201,6 +535,31 @@ pub(crate) fn remove_player(
     state: &mut state::State,
     player_id: &state::PlayerId,
 ) -> Result<(), String> {
+    if let Some(min_hands) = state.config.min_hands_before_leaving() {
+        let player = state
+            .players
+            .get(player_id)
+            .ok_or("Player not found".to_string())?;
+        let hands_played = player.stats.hands_played;
+        if player.balance > player.buy_in_total && hands_played < min_hands {
+            return Err(format!(
+                "Cannot leave with winnings before playing {} hands, {} played so far",
+                min_hands, hands_played
+            ));
+        }
+    }
+
+    let player = take_player(state, player_id)?;
+    state.ticker.emit(TickerEvent::PlayerLeft(player.name));
+
+    Ok(())
+}
+
+/// Folds and unseats a player, pausing the game if too few players remain.
+fn take_player(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+) -> Result<state::Player, String> {
     let player = state
         .players
         .get(player_id)
@@ -214,126 +573,368 @@ pub(crate) fn remove_player(
         fold_player(state, player_id)?;
     }
 
-    match state.players.remove(player_id) {
-        Some(player) => {
-            info!("Player {} has been removed", player.id);
-            state
-                .ticker
-                .emit(TickerEvent::PlayerLeft(player.name.clone()));
-        }
-        None => Err("Player not found".to_string())?,
-    }
+    let player = state
+        .players
+        .remove(player_id)
+        .ok_or("Player not found".to_string())?;
+    info!("Player {} has been removed", player.id);
 
     if state.players.len() < 2 {
-        info!("Not enough players, pausing game until more players join");
-        state.status = state::GameStatus::Joining;
-        state.round = state::Round::default();
-        for player in state.players.values_mut() {
-            player.ttl = None;
-        }
+        pause_game(state);
     }
 
-    Ok(())
+    Ok(player)
 }
 
-pub(crate) fn accept_player_bet(
+/// Marks a player as sitting out (or back in) from the next game onwards.
+/// Sitting out players keep their seat and balance but are not dealt in.
+pub(crate) fn set_player_sitting_out(
     state: &mut state::State,
     player_id: &state::PlayerId,
-    action: state::BetAction,
+    sitting_out: bool,
 ) -> Result<(), String> {
-    if state.status != state::GameStatus::Playing {
-        return Err("Game not started".to_string());
-    }
-    if state.round.players_turn.as_ref() != Some(player_id) {
-        return Err("Not your turn".to_string());
+    let player = state
+        .players
+        .get_mut(player_id)
+        .ok_or("Player not found".to_string())?;
+
+    if player.sitting_out == sitting_out {
+        return Err(match sitting_out {
+            true => "Player is already sitting out".to_string(),
+            false => "Player is not sitting out".to_string(),
+        });
     }
+    player.sitting_out = sitting_out;
 
-    let action = validate_bet_action(state, player_id, &action)?;
-    let player_stake_in_round = player_stake_in_round(state, player_id);
-    let min_raise_to = min_raise_to(state);
-    let call = call_amount(state).unwrap_or(0);
+    let event = match sitting_out {
+        true => TickerEvent::PlayerSatOut(player_id.clone()),
+        false => TickerEvent::PlayerSatIn(player_id.clone()),
+    };
+    state.ticker.emit(event);
 
+    Ok(())
+}
+
+pub(crate) fn set_player_ready(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+) -> Result<(), String> {
     let player = state
         .players
-        .get_mut(&player_id)
+        .get_mut(player_id)
         .ok_or("Player not found".to_string())?;
 
-    let (new_balance, pot_addition) = match action {
-        state::BetAction::Check => {
-            let call = call - player_stake_in_round;
-            if call > 0 {
-                return Err("Cannot check, must call".to_string());
-            }
-            (player.balance, 0)
-        }
-        state::BetAction::Call => {
-            let call = call - player_stake_in_round;
-            let call = call.min(player.balance);
-            state.round.calls.push((player_id.clone(), call));
-            let new_balance = player
-                .balance
-                .checked_sub(call)
-                .expect("Not enough balance to cover call amount");
-            (new_balance, call)
-        }
-        state::BetAction::RaiseTo(raise_to) => {
-            if raise_to < min_raise_to {
-                return Err(format!("Raise must be at least {}", min_raise_to));
-            }
-            state.round.raises.push((player_id.clone(), raise_to));
-            let pot_addition = raise_to - player_stake_in_round;
-            let new_balance = player
-                .balance
-                .checked_sub(pot_addition)
-                .ok_or("Not enough balance".to_string())?;
-            (new_balance, pot_addition)
-        }
-    };
-
-    player.balance = new_balance;
-    player.stake += pot_addition;
-    state.round.pot += pot_addition;
+    if player.ready {
+        return Err("Player is already ready".to_string());
+    }
+    player.ready = true;
 
     state
         .ticker
-        .emit(TickerEvent::PlayerBet(player_id.clone(), action));
-
-    next_turn(state, Some(player_id));
-
-    if state.round.players_turn.is_none() {
-        complete_round(state);
-    }
+        .emit(TickerEvent::PlayerReady(player_id.clone()));
 
     Ok(())
 }
 
-pub fn player_stake_in_round(state: &state::State, player_id: &state::PlayerId) -> u64 {
-    // check if player was last to raise, if so, return raise amount
-    if let Some((id, stake)) = state.round.raises.last() {
-        if id == player_id {
-            return *stake;
-        }
+/// Tops a player back up to the starting balance between hands, if the room
+/// allows rebuys.
+pub(crate) fn rebuy_player(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+) -> Result<(), String> {
+    if !state.config.rebuys_enabled() {
+        return Err("Rebuys are not enabled".to_string());
+    }
+    if state.status == state::GameStatus::Playing {
+        return Err("Cannot rebuy during a hand".to_string());
     }
 
-    let max_raise = state
-        .round
-        .raises
-        .iter()
-        .filter(|(id, _)| id == player_id)
-        .map(|(_, stake)| *stake)
-        .max()
-        .unwrap_or(0);
+    let starting_balance = state.config.starting_balance();
+    let player = state
+        .players
+        .get_mut(player_id)
+        .ok_or("Player not found".to_string())?;
 
-    let sum_of_calls = state
-        .round
-        .calls
-        .iter()
-        .filter(|(id, _)| id == player_id)
-        .map(|(_, stake)| *stake)
-        .sum::<u64>();
+    if player.balance >= starting_balance {
+        return Err("Balance is already at the starting balance".to_string());
+    }
+    player.buy_in_total += starting_balance - player.balance;
+    player.balance = starting_balance;
 
-    let player_stake_in_current_round = max_raise + sum_of_calls;
-    player_stake_in_current_round
+    state
+        .ticker
+        .emit(TickerEvent::PlayerRebought(player_id.clone()));
+
+    Ok(())
+}
+
+/// Extends the current turn by the room's time bank, once per hand.
+pub(crate) fn use_time_bank(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+) -> Result<(), String> {
+    check_players_turn(state, player_id)?;
+    let time_bank_seconds = state.config.time_bank_seconds();
+    if time_bank_seconds == 0 {
+        return Err("Time bank is not enabled".to_string());
+    }
+
+    let player = state
+        .players
+        .get_mut(player_id)
+        .ok_or("Player not found".to_string())?;
+
+    if player.time_bank_used {
+        return Err("Time bank already used this hand".to_string());
+    }
+    let ttl = player
+        .ttl
+        .as_mut()
+        .ok_or("Turn has no time limit".to_string())?;
+    ttl.add_seconds(time_bank_seconds);
+    player.time_bank_used = true;
+    state.round.players_turn_warned = false;
+
+    state
+        .ticker
+        .emit(TickerEvent::PlayerUsedTimeBank(player_id.clone()));
+
+    Ok(())
+}
+
+/// Hides a losing player's cards at showdown. Winners always show their cards.
+pub(crate) fn set_player_muck(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+) -> Result<(), String> {
+    if state.status != state::GameStatus::Complete {
+        return Err("Can only muck after the game is complete".to_string());
+    }
+    let is_winner = state
+        .round
+        .completed
+        .as_ref()
+        .is_some_and(|c| c.winners.iter().any(|w| w.player_id == *player_id));
+    if is_winner {
+        return Err("Winners must show their cards".to_string());
+    }
+
+    let player = state
+        .players
+        .get_mut(player_id)
+        .ok_or("Player not found".to_string())?;
+
+    if player.muck {
+        return Err("Player has already mucked".to_string());
+    }
+    player.muck = true;
+    if let Some(completed) = state.round.completed.as_mut() {
+        completed.showdown.retain(|(id, _)| id != player_id);
+    }
+
+    state
+        .ticker
+        .emit(TickerEvent::PlayerMucked(player_id.clone()));
+
+    Ok(())
+}
+
+/// Reveals a losing hand that was mucked automatically.
+pub(crate) fn set_player_show(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+) -> Result<(), String> {
+    if state.status != state::GameStatus::Complete {
+        return Err("Can only show after the game is complete".to_string());
+    }
+    let player = state
+        .players
+        .get_mut(player_id)
+        .ok_or("Player not found".to_string())?;
+
+    if player.folded {
+        return Err("Folded players cannot show".to_string());
+    }
+    if !player.muck {
+        return Err("Player is already showing".to_string());
+    }
+    player.muck = false;
+    let cards = player.cards.clone();
+    if let Some(completed) = state.round.completed.as_mut() {
+        if completed.best_hand.is_some() {
+            completed.showdown.push((player_id.clone(), cards));
+        }
+    }
+
+    Ok(())
+}
+
+/// Number of seated players who are ready for the game to start.
+pub(crate) fn ready_count(state: &state::State) -> usize {
+    state
+        .players
+        .values()
+        .filter(|p| !p.sitting_out && p.ready)
+        .count()
+}
+
+/// Checks that the game is in play and it is the player's turn to act.
+pub(crate) fn check_players_turn(
+    state: &state::State,
+    player_id: &state::PlayerId,
+) -> Result<(), String> {
+    if state.status != state::GameStatus::Playing {
+        return Err("Game not started".to_string());
+    }
+    if state.round.players_turn.as_ref() != Some(player_id) {
+        return Err("Not your turn".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) fn accept_player_bet(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+    action: state::BetAction,
+) -> Result<(), String> {
+    check_players_turn(state, player_id)?;
+
+    let action = validate_bet_action(state, player_id, &action)?;
+    let player_stake_in_round = player_stake_in_round(state, player_id);
+    let min_raise_to = min_raise_to(state);
+    let call = call_amount(state).unwrap_or(0);
+
+    let player = state
+        .players
+        .get_mut(&player_id)
+        .ok_or("Player not found".to_string())?;
+
+    let (new_balance, pot_addition) = match action {
+        state::BetAction::Check => {
+            let call = call - player_stake_in_round;
+            if call > 0 {
+                return Err("Cannot check, must call".to_string());
+            }
+            (player.balance, 0)
+        }
+        state::BetAction::Call => {
+            let call = call - player_stake_in_round;
+            let call = call.min(player.balance);
+            state.round.calls.push((player_id.clone(), call));
+            let new_balance = player
+                .balance
+                .checked_sub(call)
+                .expect("Not enough balance to cover call amount");
+            (new_balance, call)
+        }
+        state::BetAction::BetTo(raise_to) | state::BetAction::RaiseTo(raise_to) => {
+            let all_in = raise_to == player_stake_in_round + player.balance;
+            if raise_to < min_raise_to && !all_in {
+                return Err(format!("Raise must be at least {}", min_raise_to));
+            }
+            let pot_addition = raise_to
+                .checked_sub(player_stake_in_round)
+                .ok_or("Raise must be above the player's current stake".to_string())?;
+            let new_balance = player
+                .balance
+                .checked_sub(pot_addition)
+                .ok_or("Not enough balance".to_string())?;
+            state.round.raises.push((player_id.clone(), raise_to));
+            (new_balance, pot_addition)
+        }
+        state::BetAction::AllIn => {
+            let all_in = player.balance;
+            let raise_to = player_stake_in_round + all_in;
+            if raise_to > call {
+                state.round.raises.push((player_id.clone(), raise_to));
+            } else {
+                state.round.calls.push((player_id.clone(), all_in));
+            }
+            (0, all_in)
+        }
+    };
+
+    player.balance = new_balance;
+    player.stake += pot_addition;
+    player.all_in = new_balance == 0;
+    state.round.pot += pot_addition;
+
+    // only a full raise reopens the betting for players who already acted
+    let raised_to = player_stake_in_round + pot_addition;
+    if raised_to > call && raised_to >= min_raise_to {
+        state.round.acted_since_full_raise.clear();
+    }
+    state.round.acted_since_full_raise.push(player_id.clone());
+
+    record_hand_action(
+        state,
+        player_id,
+        state::HandAction::Bet(action),
+        pot_addition,
+    );
+    state
+        .ticker
+        .emit(TickerEvent::PlayerBet(player_id.clone(), action));
+
+    next_turn(state, Some(player_id));
+
+    if state.round.players_turn.is_none() {
+        complete_round(state);
+    }
+
+    Ok(())
+}
+
+pub fn player_stake_in_round(state: &state::State, player_id: &state::PlayerId) -> u64 {
+    // check if player was last to raise, if so, return raise amount
+    if let Some((id, stake)) = state.round.raises.last() {
+        if id == player_id {
+            return *stake;
+        }
+    }
+
+    let max_raise = state
+        .round
+        .raises
+        .iter()
+        .filter(|(id, _)| id == player_id)
+        .map(|(_, stake)| *stake)
+        .max()
+        .unwrap_or(0);
+
+    let sum_of_calls = state
+        .round
+        .calls
+        .iter()
+        .filter(|(id, _)| id == player_id)
+        .map(|(_, stake)| *stake)
+        .sum::<u64>();
+
+    let player_stake_in_current_round = max_raise + sum_of_calls;
+    player_stake_in_current_round
+}
+
+fn accept_antes(state: &mut state::State) {
+    let ante = state.config.ante();
+    if ante == 0 {
+        return;
+    }
+
+    for player in state.players.values_mut().filter(|p| !p.folded) {
+        let ante_stake = player.balance.min(ante);
+        player.balance -= ante_stake;
+        player.stake += ante_stake;
+        state.round.pot += ante_stake;
+        state.round.history.push(state::HandHistoryItem {
+            player_id: player.id.clone(),
+            cards_on_table: 0,
+            action: state::HandAction::Ante,
+            amount: ante_stake,
+        });
+    }
+
+    info!("Accepted antes of {} from all players", ante);
+    state.ticker.emit(TickerEvent::AntePosted(ante));
 }
 
 fn accept_blinds(
@@ -341,11 +942,12 @@ fn accept_blinds(
     small_blind_player: state::PlayerId,
     big_blind_player: state::PlayerId,
 ) {
+    let (small_blind, big_blind) = (small_blind(state), big_blind(state));
     let small_blind_player = state
         .players
         .get_mut(&small_blind_player)
         .expect("Small blind player not found");
-    let small_blind_stake = small_blind_player.balance.min(state.config.small_blind());
+    let small_blind_stake = small_blind_player.balance.min(small_blind);
     small_blind_player.balance = small_blind_player.balance - small_blind_stake;
     small_blind_player.stake += small_blind_stake;
     state.round.pot += small_blind_stake;
@@ -358,13 +960,20 @@ fn accept_blinds(
         .round
         .raises
         .push((small_blind_player.id.clone(), small_blind_stake));
+    state.round.history.push(state::HandHistoryItem {
+        player_id: small_blind_player.id.clone(),
+        cards_on_table: 0,
+        action: state::HandAction::SmallBlind,
+        amount: small_blind_stake,
+    });
 
     let big_blind_player = state
         .players
         .get_mut(&big_blind_player)
         .expect("Big blind player not found");
 
-    let big_blind_stake = big_blind_player.balance.min(state.config.big_blind());
+    let big_blind_stake = big_blind_player.balance.min(big_blind);
+    state.round.big_blind = Some(big_blind_player.id.clone());
 
     big_blind_player.balance = big_blind_player.balance - big_blind_stake;
     big_blind_player.stake += big_blind_stake;
@@ -374,16 +983,124 @@ fn accept_blinds(
         .round
         .raises
         .push((big_blind_player.id.clone(), big_blind_stake));
+    state.round.history.push(state::HandHistoryItem {
+        player_id: big_blind_player.id.clone(),
+        cards_on_table: 0,
+        action: state::HandAction::BigBlind,
+        amount: big_blind_stake,
+    });
 
     state
         .ticker
         .emit(TickerEvent::BigBlindPosted(big_blind_player.id.clone()));
 }
 
+fn accept_bring_in(state: &mut state::State, bring_in_player: state::PlayerId, bring_in: u64) {
+    let bring_in_player = state
+        .players
+        .get_mut(&bring_in_player)
+        .expect("Bring-in player not found");
+    let bring_in_stake = bring_in_player.balance.min(bring_in);
+    bring_in_player.balance -= bring_in_stake;
+    bring_in_player.stake += bring_in_stake;
+    state.round.pot += bring_in_stake;
+
+    state
+        .round
+        .raises
+        .push((bring_in_player.id.clone(), bring_in_stake));
+    state.round.history.push(state::HandHistoryItem {
+        player_id: bring_in_player.id.clone(),
+        cards_on_table: 0,
+        action: state::HandAction::BringIn,
+        amount: bring_in_stake,
+    });
+
+    state
+        .ticker
+        .emit(TickerEvent::BringInPosted(bring_in_player.id.clone()));
+}
+
+/// Posts an under-the-gun straddle of twice the big blind. Only the player
+/// left of the big blind may straddle, and only before any other action.
+pub(crate) fn straddle(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+) -> Result<(), String> {
+    if state.status != state::GameStatus::Playing {
+        return Err("Game not started".to_string());
+    }
+    if state.config.forced_bet() != state::config::ForcedBet::Blinds {
+        return Err("Can only straddle when playing with blinds".to_string());
+    }
+    let no_action_yet = state.round.cards_on_table.is_empty()
+        && state.round.raises.len() == 2
+        && state.round.calls.is_empty()
+        && state.round.straddle.is_none();
+    if !no_action_yet {
+        return Err("Can only straddle before any action".to_string());
+    }
+    let posted_blind = state.round.raises.iter().any(|(id, _)| id == player_id);
+    if !is_player_turn(state, player_id) || posted_blind {
+        return Err("Only the player left of the big blind can straddle".to_string());
+    }
+
+    let straddle = big_blind(state) * 2;
+    let player = state
+        .players
+        .get_mut(player_id)
+        .ok_or("Player not found".to_string())?;
+    if player.balance <= straddle {
+        return Err("Not enough balance to straddle".to_string());
+    }
+    player.balance -= straddle;
+    player.stake += straddle;
+    state.round.pot += straddle;
+    state.round.raises.push((player_id.clone(), straddle));
+    state.round.straddle = Some(player_id.clone());
+    record_hand_action(state, player_id, state::HandAction::Straddle, straddle);
+    state
+        .ticker
+        .emit(TickerEvent::StraddlePosted(player_id.clone()));
+
+    // the straddler acts last preflop, so the turn passes on without
+    // checking for the end of the betting round
+    let next_player_id = action_order(state)
+        .iter()
+        .cycle()
+        .skip_while(|id| *id != player_id)
+        .skip(1)
+        .take(state.players.len())
+        .find(|id| is_playable(state, id))
+        .cloned();
+    set_players_turn(state, next_player_id);
+    Ok(())
+}
+
+fn record_hand_action(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+    action: state::HandAction,
+    amount: u64,
+) {
+    state.round.history.push(state::HandHistoryItem {
+        player_id: player_id.clone(),
+        cards_on_table: state.round.cards_on_table.len(),
+        action,
+        amount,
+    });
+}
+
 fn reset_players(state: &mut state::State) {
+    let auto_muck = state.config.auto_muck_losers();
     for player in state.players.values_mut() {
         player.stake = 0;
-        player.folded = false;
+        // sitting out players sit the whole game out as if they had folded
+        player.folded = player.sitting_out;
+        player.all_in = false;
+        player.muck = auto_muck;
+        player.time_bank_used = false;
+        player.queued_action = None;
     }
     state.round.players_turn = None;
 }
@@ -395,6 +1112,10 @@ fn next_turn(state: &mut state::State, current_player_id: Option<&state::PlayerI
         return;
     }
 
+    if current_player_id.is_none() {
+        state.round.acted_since_full_raise.clear();
+    }
+
     let next_player_id = match current_player_id {
         Some(player_id) => get_next_players_turn(&state, player_id),
         None if state.round.cards_on_table.is_empty() => {
@@ -404,29 +1125,57 @@ fn next_turn(state: &mut state::State, current_player_id: Option<&state::PlayerI
                 .filter(|(_, p)| !p.folded && p.balance > 0)
                 .map(|(id, _)| id.clone())
                 .cycle();
-            let small_blind_player = player_ids.next().expect("No players left");
-            let big_blind_player = player_ids.next().expect("No players left");
-            let next_player_id = player_ids.next();
+            match state.config.forced_bet() {
+                state::config::ForcedBet::Blinds => {
+                    let small_blind_player = player_ids.next().expect("No players left");
+                    let big_blind_player = player_ids.next().expect("No players left");
+                    let next_player_id = player_ids.next();
 
-            info!(
-                "Accepting blinds from players {} (sm) and {} (lg)",
-                small_blind_player, big_blind_player
-            );
-            accept_blinds(state, small_blind_player, big_blind_player);
+                    accept_antes(state);
+
+                    info!(
+                        "Accepting blinds from players {} (sm) and {} (lg)",
+                        small_blind_player, big_blind_player
+                    );
+                    accept_blinds(state, small_blind_player, big_blind_player);
+
+                    next_player_id
+                }
+                state::config::ForcedBet::BringIn(bring_in) => {
+                    let bring_in_player = player_ids.next().expect("No players left");
+                    let next_player_id = player_ids.next();
 
-            next_player_id
+                    accept_antes(state);
+
+                    info!("Accepting bring-in from player {}", bring_in_player);
+                    accept_bring_in(state, bring_in_player, bring_in);
+
+                    next_player_id
+                }
+            }
         }
         None => get_rounds_starting_player(state),
     };
 
+    set_players_turn(state, next_player_id);
+}
+
+fn set_players_turn(state: &mut state::State, next_player_id: Option<state::PlayerId>) {
     match next_player_id
         .as_ref()
         .and_then(|id| state.players.get_mut(id))
     {
         Some(next_player) => {
             let mut expires = state::dt::Instant::default();
-            expires.add_seconds(state::PLAYER_TURN_TIMEOUT_SECONDS);
+            expires.add_seconds(state.config.turn_timeout_seconds());
             next_player.ttl = Some(expires);
+            state.round.players_turn_warned = false;
+            // the turn passes through None between betting rounds, so this only
+            // skips re-announcing a turn that hasn't moved
+            if state.round.players_turn != next_player_id {
+                let player_id = next_player.id.clone();
+                state.ticker.emit(TickerEvent::ActionOn(player_id));
+            }
         }
         None => {
             info!("Round complete, awaiting next round");
@@ -434,35 +1183,116 @@ fn next_turn(state: &mut state::State, current_player_id: Option<&state::PlayerI
     }
 
     state.round.players_turn = next_player_id;
+    play_queued_action(state);
 }
 
-fn get_rounds_starting_player(state: &mut state::State) -> Option<state::PlayerId> {
-    let players_in_round = &mut state
+/// Queues a fold or check-fold, played as soon as it is the player's turn.
+pub(crate) fn queue_player_action(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+    action: state::QueuedAction,
+) -> Result<(), String> {
+    if state.status != state::GameStatus::Playing {
+        return Err("Game not started".to_string());
+    }
+    let player = state
         .players
-        .iter()
-        .filter(|(_, p)| !p.folded && p.balance > 0);
-
-    let starting_player = players_in_round.next();
-
-    // if no other players left, the game is complete
-    let next_playable_player = players_in_round.next();
-    if let None = next_playable_player {
-        return None;
+        .get_mut(player_id)
+        .ok_or("Player not found".to_string())?;
+    if player.folded {
+        return Err("Player has already folded".to_string());
     }
+    player.queued_action = Some(action);
 
-    starting_player.map(|(id, _)| id.clone())
+    if is_player_turn(state, player_id) {
+        play_queued_action(state);
+    }
+    Ok(())
 }
 
-fn get_next_players_turn(
-    state: &state::State,
+/// Plays the queued action of the player whose turn it is. A queued fold is
+/// dropped rather than played when the player can check for free.
+fn play_queued_action(state: &mut state::State) {
+    let Some(player_id) = state.round.players_turn.clone() else {
+        return;
+    };
+    let Some(action) = state
+        .players
+        .get_mut(&player_id)
+        .and_then(|player| player.queued_action.take())
+    else {
+        return;
+    };
+
+    let can_check = call_amount(state).unwrap_or(0) <= player_stake_in_round(state, &player_id);
+    let result = match (action, can_check) {
+        (state::QueuedAction::CheckFold, true) => {
+            accept_player_bet(state, &player_id, state::BetAction::Check)
+        }
+        (state::QueuedAction::Fold, true) => {
+            info!("Player {} can check, dropping queued fold", player_id);
+            return;
+        }
+        (_, false) => fold_player(state, &player_id),
+    };
+    if let Err(err) = result {
+        info!("Player {} queued action failed: {}", player_id, err);
+    }
+}
+
+fn get_rounds_starting_player(state: &mut state::State) -> Option<state::PlayerId> {
+    let order = action_order(state);
+    let players_in_round = &mut order.iter().filter(|id| is_playable(state, id));
+
+    let starting_player = players_in_round.next();
+
+    // if no other players left, the game is complete
+    let next_playable_player = players_in_round.next();
+    if let None = next_playable_player {
+        return None;
+    }
+
+    starting_player.cloned()
+}
+
+/// Players in the order they act in the current betting round. Heads-up, the
+/// dealer posts the small blind and acts first preflop, but acts last after the flop.
+fn action_order(state: &state::State) -> Vec<state::PlayerId> {
+    let mut order: Vec<_> = state
+        .players
+        .values()
+        .filter(|p| !p.sitting_out)
+        .map(|p| p.id.clone())
+        .collect();
+
+    let preflop = state.round.cards_on_table.len() < 3;
+    if order.len() == 2 && !preflop {
+        order.rotate_left(1);
+    }
+    order
+}
+
+fn is_playable(state: &state::State, player_id: &state::PlayerId) -> bool {
+    state
+        .players
+        .get(player_id)
+        .is_some_and(|player| !player.folded && player.balance > 0)
+}
+
+fn get_next_players_turn(
+    state: &state::State,
     current_player_id: &state::PlayerId,
 ) -> Option<state::PlayerId> {
     let call_amount = call_amount(state).unwrap_or(0);
     let first_round = state.round.cards_on_table.len() < 3;
+    // unlike the big blind, the bring-in gets no option to raise once called
+    let blinds = state.config.forced_bet() == state::config::ForcedBet::Blinds;
+
+    let forced_raises = 2 + state.round.straddle.is_some() as usize;
 
     // if call amount > 0, check if all players have reached equal
     // stakes in the current round. If so, end round.
-    if call_amount > 0 && (!first_round || state.round.raises.len() > 2) {
+    if call_amount > 0 && (!first_round || !blinds || state.round.raises.len() > forced_raises) {
         let all_players_have_called = state
             .players
             .iter()
@@ -475,34 +1305,36 @@ fn get_next_players_turn(
     }
 
     // if first round, check if player with big blind has checked on the big blind stake.
-    if first_round {
-        let is_big_blind_first_round =
-            current_player_id == state.players.keys().nth(1).expect("No players left");
-        let current_player_stake_is_call_amount =
-            player_stake_in_round(state, current_player_id) == state.config.big_blind();
-        if is_big_blind_first_round && current_player_stake_is_call_amount {
-            return None;
+    // A straddle takes over the big blind's option at twice the stake.
+    if first_round && blinds {
+        let option = match &state.round.straddle {
+            Some(straddle_player) => Some((straddle_player, big_blind(state) * 2)),
+            None => (state.round.big_blind.as_ref()).map(|id| (id, big_blind(state))),
+        };
+        if let Some((option_player, option_stake)) = option {
+            let is_big_blind_first_round = current_player_id == option_player;
+            let current_player_stake_is_call_amount =
+                player_stake_in_round(state, current_player_id) == option_stake;
+            if is_big_blind_first_round && current_player_stake_is_call_amount {
+                return None;
+            }
         }
     }
 
-    let next_player = state
-        .players
+    let order = action_order(state);
+    let next_player = order
         .iter()
-        .enumerate()
-        .skip_while(|(_, (id, _))| id != current_player_id)
+        .skip_while(|id| *id != current_player_id)
         .skip(1)
-        .filter(|(_, (_, player))| !player.folded && player.balance > 0)
-        .next()
-        .map(|(_, (id, _))| id.clone());
+        .find(|id| is_playable(state, id))
+        .cloned();
 
     next_player.or_else(|| {
-        state
-            .players
+        order
             .iter()
-            .filter(|(_, player)| !player.folded && player.balance > 0)
-            .next()
-            .filter(|(_, player)| player_stake_in_round(state, &player.id) != call_amount)
-            .map(|(id, _)| id.clone())
+            .find(|id| is_playable(state, id))
+            .filter(|id| player_stake_in_round(state, id) != call_amount)
+            .cloned()
     })
 }
 
@@ -513,6 +1345,17 @@ fn validate_bet_action(
 ) -> Result<state::BetAction, String> {
     let last_raise = state.round.raises.last().map(|(_, s)| *s).unwrap_or(0);
     let player_stake_in_round = player_stake_in_round(state, player_id);
+    let balance = state.players.get(player_id).map(|p| p.balance).unwrap_or(0);
+    let all_in_to = player_stake_in_round + balance;
+    let raises = match action {
+        state::BetAction::RaiseTo(_) => true,
+        state::BetAction::AllIn => all_in_to > call_amount(state).unwrap_or(0),
+        _ => false,
+    };
+    if raises && state.round.acted_since_full_raise.contains(player_id) {
+        return Err("Cannot re-raise a short all-in, call or fold instead".to_string());
+    }
+
     let stake = match action {
         state::BetAction::Check
             if !state.round.raises.is_empty() && player_stake_in_round != last_raise =>
@@ -523,13 +1366,48 @@ fn validate_bet_action(
             return Err("Stake cannot be 0 for raise".to_string())
         }
         state::BetAction::Check => state::BetAction::Check,
+        state::BetAction::BetTo(_) if call_amount(state).unwrap_or(0) > 0 => {
+            return Err("Cannot bet after a bet, raise instead".to_string())
+        }
+        state::BetAction::BetTo(bet_to) => {
+            let big_blind = big_blind(state);
+            if *bet_to < big_blind {
+                return Err(format!("Bet must be at least {}", big_blind));
+            }
+            if state.config.betting_limit() == state::config::BettingLimit::FixedLimit
+                && *bet_to != big_blind
+            {
+                return Err(format!("Bet must be exactly {}", big_blind));
+            }
+            if let Some(max_bet) = max_raise_to(state, player_id).filter(|max| bet_to > max) {
+                return Err(format!("Bet cannot exceed the pot limit of {}", max_bet));
+            }
+            state::BetAction::BetTo(*bet_to)
+        }
+        state::BetAction::RaiseTo(raise_to)
+            if state.config.betting_limit() == state::config::BettingLimit::FixedLimit =>
+        {
+            let fixed_raise = call_amount(state).unwrap_or(0) + big_blind(state);
+            if *raise_to != fixed_raise {
+                return Err(format!("Raise must be exactly {}", fixed_raise));
+            }
+            state::BetAction::RaiseTo(*raise_to)
+        }
         state::BetAction::RaiseTo(raise_to) => {
             let call_amount = call_amount(state).unwrap_or(0);
             let min_raise_to = min_raise_to(state);
             let min_raise = call_amount.max(min_raise_to);
-            if *raise_to < min_raise {
+            // a player may always go all-in, even for less than a full raise
+            let short_all_in = *raise_to == all_in_to && *raise_to > call_amount;
+            if *raise_to < min_raise && !short_all_in {
                 return Err(format!("Raise must be at least {}", min_raise));
             }
+            if let Some(max_raise) = max_raise_to(state, player_id).filter(|max| raise_to > max) {
+                return Err(format!(
+                    "Raise cannot exceed the pot limit of {}",
+                    max_raise
+                ));
+            }
             state::BetAction::RaiseTo(*raise_to)
         }
         state::BetAction::Call => {
@@ -539,43 +1417,154 @@ fn validate_bet_action(
             }
             state::BetAction::Call
         }
+        state::BetAction::AllIn => {
+            if balance == 0 {
+                return Err("No balance left to go all-in".to_string());
+            }
+            if max_raise_to(state, player_id)
+                .is_some_and(|max| player_stake_in_round + balance > max)
+            {
+                return Err("Cannot go all-in over the betting limit".to_string());
+            }
+            state::BetAction::AllIn
+        }
     };
     Ok(stake)
 }
 
+/// The actions the player could take right now, checked with the same rules
+/// as `accept_player_bet`.
+pub(crate) fn legal_actions(
+    state: &state::State,
+    player_id: &state::PlayerId,
+) -> Option<models::PlayerActions> {
+    let player = state.players.get(player_id)?;
+    if check_players_turn(state, player_id).is_err() {
+        return Some(models::PlayerActions {
+            check: false,
+            call: false,
+            raise: false,
+            fold: false,
+            min_raise_to: 0,
+            max_raise_to: 0,
+        });
+    }
+
+    let is_legal = |action| validate_bet_action(state, player_id, &action).is_ok();
+    let call_amount = call_amount(state).unwrap_or(0);
+    let stake = player_stake_in_round(state, player_id);
+    let affordable = stake + player.balance;
+    let min_raise_to = match state.config.betting_limit() {
+        state::config::BettingLimit::FixedLimit => call_amount + big_blind(state),
+        _ => call_amount.max(min_raise_to(state)),
+    };
+    let max_raise_to = max_raise_to(state, player_id)
+        .unwrap_or(affordable)
+        .min(affordable);
+
+    Some(models::PlayerActions {
+        check: is_legal(state::BetAction::Check) && call_amount <= stake,
+        call: is_legal(state::BetAction::Call),
+        raise: min_raise_to <= max_raise_to && is_legal(state::BetAction::RaiseTo(min_raise_to)),
+        fold: true,
+        min_raise_to,
+        max_raise_to,
+    })
+}
+
 fn complete_round(state: &mut state::State) {
+    // a queued action may have already completed the game
+    if state.status != state::GameStatus::Playing {
+        return;
+    }
+
+    if state.config.run_it_twice() && betting_closed(state) {
+        let remaining = 5 - state.round.cards_on_table.len();
+        if remaining > 0 {
+            let mut second_board = state.round.cards_on_table.clone();
+            for _ in 0..remaining {
+                let next_card = state.round.deck.pop();
+                state.round.cards_on_table.push(next_card);
+            }
+            state.ticker.emit(TickerEvent::RunoutDealt(1, remaining));
+            for _ in 0..remaining {
+                second_board.push(state.round.deck.pop());
+            }
+            state.ticker.emit(TickerEvent::RunoutDealt(2, remaining));
+            let first_board = state.round.cards_on_table.clone();
+            complete_game(state, &[first_board, second_board]);
+            return;
+        }
+    }
+
     match state.round.cards_on_table.len() {
         0 => {
             place_cards_on_table(state, 3);
-            next_turn(state, None);
             state.round.raises.clear();
             state.round.calls.clear();
+            next_turn(state, None);
             if state.round.players_turn.is_none() {
                 complete_round(state);
             }
         }
         3 | 4 => {
             place_cards_on_table(state, 1);
-            next_turn(state, None);
             state.round.raises.clear();
             state.round.calls.clear();
+            next_turn(state, None);
             if state.round.players_turn.is_none() {
                 complete_round(state);
             }
         }
         5 => {
-            payout_game_winners(state);
-            state.round.raises.clear();
-            state.round.calls.clear();
-            state.status = state::GameStatus::Complete;
-            state.ticker.emit(TickerEvent::RoundComplete);
-
-            rotate_dealer(state);
+            let board = state.round.cards_on_table.clone();
+            complete_game(state, &[board]);
         }
         _ => unreachable!(),
     }
 }
 
+fn report_hand_result(state: &state::State, pot: u64) {
+    if let Some(completed) = &state.round.completed {
+        state.hand_results.record(state::results::HandResult {
+            winners: completed.winners.clone(),
+            pot,
+            board: completed.board.clone(),
+            second_board: completed.second_board.clone(),
+            hands: completed.showdown.clone(),
+        });
+    }
+}
+
+/// True when at least two players are left in the hand and no more than one
+/// of them has chips behind, so no further betting can happen.
+fn betting_closed(state: &state::State) -> bool {
+    let live_players = state.players.values().filter(|p| !p.folded);
+    let (live, with_chips) = live_players.fold((0, 0), |(live, with_chips), p| {
+        (live + 1, with_chips + usize::from(p.balance > 0))
+    });
+    live >= 2 && with_chips <= 1
+}
+
+fn record_hand_metrics(state: &state::State, pot: u64) {
+    let labels = metrics_labels::room_gameplay(state.room_code.as_ref().map(ToString::to_string));
+    Metrics::c_hands_played_total_incr(labels.clone());
+    Metrics::h_pot_size_chips(labels, pot);
+}
+
+fn complete_game(state: &mut state::State, boards: &[Vec<cards::Card>]) {
+    let pot = state.round.pot;
+    record_hand_metrics(state, pot);
+    payout_game_winners(state, boards);
+    report_hand_result(state, pot);
+    state.round.raises.clear();
+    state.round.calls.clear();
+    state.status = state::GameStatus::Complete;
+    state.ticker.emit(TickerEvent::RoundComplete);
+
+    rotate_dealer(state);
+}
+
 fn place_cards_on_table(state: &mut state::State, count: usize) {
     for _ in 0..count {
         let next_card = state.round.deck.pop();
@@ -585,6 +1574,9 @@ fn place_cards_on_table(state: &mut state::State, count: usize) {
 }
 
 fn rotate_dealer(state: &mut state::State) {
+    if state.config.dealer_rotation_disabled() {
+        return;
+    }
     if let Some(old_dealer) = state.players.pop_first() {
         state.players.insert(old_dealer.0, old_dealer.1);
 
@@ -594,7 +1586,12 @@ fn rotate_dealer(state: &mut state::State) {
     }
 }
 
-fn payout_game_winners(state: &mut state::State) {
+/// Pays out the pot, split evenly across each board when it was run more
+/// than once.
+fn payout_game_winners(state: &mut state::State, boards: &[Vec<cards::Card>]) {
+    let mut pots = side_pots(state);
+    let seats = seats_from_dealer(state);
+    let variant = state.config.variant();
     let round = &mut state.round;
 
     #[derive(Clone, PartialEq, PartialOrd)]
@@ -614,15 +1611,13 @@ fn payout_game_winners(state: &mut state::State) {
         .collect();
     stakes.sort_by_key(|s| s.stake);
 
-    let mut deduped_stakes = stakes.iter().map(|s| s.stake).collect::<Vec<_>>();
-    deduped_stakes.dedup();
-
     match stakes.len() {
         1 => {
             let winner_stake = stakes.first().unwrap();
             match state.players.get_mut(&winner_stake.id) {
                 Some(player) => {
                     player.balance += round.pot;
+                    player.stats.record_win(round.pot);
                     let winner = state::RoundWinner {
                         player_id: winner_stake.id.clone(),
                         hand: None,
@@ -633,6 +1628,11 @@ fn payout_game_winners(state: &mut state::State) {
                         winners: vec![winner],
                         best_hand: None,
                         hide_cards: false,
+                        history: round.history.clone(),
+                        board: round.cards_on_table.clone(),
+                        second_board: None,
+                        showdown: vec![],
+                        rake: 0,
                     });
                     state
                         .ticker
@@ -648,6 +1648,11 @@ fn payout_game_winners(state: &mut state::State) {
                         winners: vec![],
                         best_hand: None,
                         hide_cards: true,
+                        history: round.history.clone(),
+                        board: round.cards_on_table.clone(),
+                        second_board: None,
+                        showdown: vec![],
+                        rake: 0,
                     });
                     return;
                 }
@@ -660,121 +1665,109 @@ fn payout_game_winners(state: &mut state::State) {
                 winners: vec![],
                 best_hand: None,
                 hide_cards: true,
+                history: round.history.clone(),
+                board: round.cards_on_table.clone(),
+                second_board: None,
+                showdown: vec![],
+                rake: 0,
             });
             return;
         }
         _ => {}
     }
 
-    let mut pots = vec![];
-
-    deduped_stakes.insert(0, 0);
-    for stake in deduped_stakes.windows(2) {
-        let (rel_stake, abs_stake) = (stake[1] - stake[0], stake[1]);
-
-        let winnable_players: Vec<_> = stakes
-            .iter()
-            .filter(|s| s.stake >= abs_stake)
-            .map(|s| s.id.clone())
-            .collect();
-
-        let pot = winnable_players.len() as u64 * rel_stake;
-        pots.push((pot, winnable_players));
+    // the rake comes out of the main pot first, then each side pot in turn
+    let rake = state.config.rake().map_or(0, |rake| rake.amount(round.pot));
+    let mut rake_left = rake;
+    for (pot, _) in pots.iter_mut() {
+        let taken = rake_left.min(*pot);
+        *pot -= taken;
+        rake_left -= taken;
     }
+    state.rake_total += rake;
 
-    // TODO: TEST! the stake values players that folded should still be included in the winnable pot
-    for (_, player) in state.players.iter().filter(|(_, p)| p.folded) {
-        let mut pot = pots
-            .iter_mut()
-            .skip_while(|(pot, players)| (*pot / players.len() as u64) < player.stake);
+    let mut winners = vec![];
+    let mut winner_hands = vec![];
 
-        if let Some((pot, _)) = pot.next() {
+    for (run, board) in boards.iter().enumerate() {
+        let scores = showdown_scores(state.players.values(), variant, board);
+
+        for (player_id, score) in &scores {
             info!(
-                "Player {} folded, adding {} stake to pot of {}",
-                player.id, player.stake, pot
+                "Player {} has score {} (cards {:?})",
+                player_id,
+                score.strength(),
+                score.cards()
             );
-            *pot += player.stake;
         }
-    }
-
-    let mut scores: Vec<_> = state
-        .players
-        .values_mut()
-        .map(|p| {
-            let score = cards::Card::evaluate_hand(&p.cards, &round.cards_on_table);
-            (p, score)
-        })
-        .collect();
-
-    for (player, score) in &scores {
-        info!(
-            "Player {} has score {} (cards {:?})",
-            player.id,
-            score.strength(),
-            score.cards()
-        );
-    }
-    let mut winners = vec![];
-    let mut winner_hands = vec![];
 
-    for (pot, pot_players) in &pots {
-        let winning_hand = scores
-            .iter()
-            .filter(|(player, _)| pot_players.contains(&player.id))
-            .map(|(_, score)| score.clone())
-            .max()
-            .expect("No winning hand found for pot");
-
-        let mut winning_players: Vec<_> = scores
-            .iter_mut()
-            .filter(|(player, score)| !(score < &winning_hand) && pot_players.contains(&player.id))
-            .map(|(player, _)| &mut **player)
-            .collect();
+        for (pot, pot_players) in &pots {
+            // odd chips from splitting the pot between runs go to the first run
+            let runs = boards.len() as u64;
+            let pot = match run {
+                0 => pot / runs + pot % runs,
+                _ => pot / runs,
+            };
+            let winning_hand = scores
+                .iter()
+                .filter(|(player_id, _)| pot_players.contains(player_id))
+                .map(|(_, score)| score.clone())
+                .max()
+                .expect("No winning hand found for pot");
 
-        let winners_count = winning_players.len() as u64;
-        let payout = if winners_count > 0 {
-            pot / winners_count
-        } else {
-            continue;
-        }; // TODO: handle odd pot sizes
-        match &winning_players[..] {
-            [] => unreachable!(),
-            [winner] => {
-                state.ticker.emit(TickerEvent::Winner(
-                    winner.id.clone(),
-                    winning_hand.strength(),
-                ));
+            let winning_ids: Vec<_> = scores
+                .iter()
+                .filter(|(player_id, score)| {
+                    !(score < &winning_hand) && pot_players.contains(player_id)
+                })
+                .map(|(player_id, _)| player_id.clone())
+                .collect();
+            let payouts = split_pot(pot, &winning_ids, &seats);
+            match &winning_ids[..] {
+                [] => unreachable!(),
+                [winner] => {
+                    state
+                        .ticker
+                        .emit(TickerEvent::Winner(winner.clone(), winning_hand.strength()));
+                }
+                winners => {
+                    state.ticker.emit(TickerEvent::SplitPotWinners(
+                        winners.to_vec(),
+                        winning_hand.strength(),
+                    ));
+                }
             }
-            winners => {
-                state.ticker.emit(TickerEvent::SplitPotWinners(
-                    winners.iter().map(|p| p.id.clone()).collect(),
-                    winning_hand.strength(),
-                ));
+
+            for winner_id in &winning_ids {
+                let payout = payouts
+                    .iter()
+                    .find_map(|(id, payout)| (id == winner_id).then_some(*payout))
+                    .unwrap_or(0);
+                winners.push(state::RoundWinner {
+                    player_id: winner_id.clone(),
+                    hand: Some(winning_hand.strength()),
+                    winnings: payout,
+                    total_pot_winnings: pot,
+                });
+                winner_hands.push((winner_id.clone(), winning_hand));
+                if let Some(winner) = state.players.get_mut(winner_id) {
+                    winner.balance += payout;
+                }
+                state
+                    .ticker
+                    .emit(TickerEvent::PaidPot(winner_id.clone(), payout));
             }
-        }
 
-        for winner in winning_players.iter_mut() {
-            winners.push(state::RoundWinner {
-                player_id: winner.id.clone(),
-                hand: Some(winning_hand.strength()),
-                winnings: payout,
-                total_pot_winnings: *pot,
-            });
-            let hand = cards::Card::evaluate_hand(&winner.cards, &round.cards_on_table);
-            winner_hands.push((winner.id.clone(), hand));
-            winner.balance += payout;
-            state
-                .ticker
-                .emit(TickerEvent::PaidPot(winner.id.clone(), payout));
+            info!(
+                "Paid out pot to winners. Pot: {}, Winner(s): {}",
+                pot,
+                winning_ids
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
         }
-
-        let winner_ids: Vec<_> = winning_players.iter().map(|p| p.id.to_string()).collect();
-
-        info!(
-            "Paid out pot to winners. Pot: {}, Winner(s): {}",
-            pot,
-            winner_ids.join(", "),
-        );
     }
 
     let pot_splits = pots.len().saturating_sub(1);
@@ -795,14 +1788,135 @@ fn payout_game_winners(state: &mut state::State) {
         best_hand.cards()
     );
 
+    for player in state.players.values_mut() {
+        let winnings: Vec<_> = winners
+            .iter()
+            .filter(|winner| winner.player_id == player.id)
+            .map(|winner| winner.winnings)
+            .collect();
+        if !winnings.is_empty() {
+            player.stats.record_win(winnings.iter().sum());
+        }
+    }
+
+    // losers who muck keep their cards out of the history as well as the table
+    let showdown = state
+        .players
+        .values()
+        .filter(|p| !p.folded)
+        .filter(|p| !p.muck || winners.iter().any(|w| w.player_id == p.id))
+        .map(|p| (p.id.clone(), p.cards.clone()))
+        .collect();
+
     round.completed = Some(state::CompletedRound {
         winners,
-        best_hand: Some((best_hand_players, best_hand.strength())),
+        best_hand: Some((best_hand_players, *best_hand)),
         hide_cards: false,
+        history: round.history.clone(),
+        board: round.cards_on_table.clone(),
+        second_board: boards.get(1).cloned(),
+        showdown,
+        rake,
     });
     round.pot = 0;
 }
 
+/// Scores each live player's hand once against a board, to be shared by every
+/// pot contested on it.
+fn showdown_scores<'a>(
+    players: impl Iterator<Item = &'a state::Player>,
+    variant: cards::Variant,
+    board: &[cards::Card],
+) -> Vec<(state::PlayerId, cards::EvaluatedHand)> {
+    players
+        .filter(|p| !p.folded)
+        .map(|p| {
+            let score = cards::Card::evaluate_hand_for(variant, &p.cards, board);
+            (p.id.clone(), score)
+        })
+        .collect()
+}
+
+/// Splits the staked chips into a main pot and side pots, one for each level
+/// a live player went all-in at. Every player's chips, folded or not, are
+/// added to each level as far as they matched it, but only live players that
+/// reached a level can win it.
+fn side_pots(state: &state::State) -> Vec<(u64, Vec<state::PlayerId>)> {
+    let mut stakes: Vec<_> = state
+        .players
+        .values()
+        .filter(|p| !p.folded)
+        .map(|p| (p.id.clone(), p.stake))
+        .collect();
+    stakes.sort_by_key(|(_, stake)| *stake);
+
+    let mut deduped_stakes = stakes.iter().map(|(_, stake)| *stake).collect::<Vec<_>>();
+    deduped_stakes.dedup();
+    deduped_stakes.insert(0, 0);
+    let top_stake = deduped_stakes.last().copied().unwrap_or(0);
+
+    let mut pots = vec![];
+    for stake in deduped_stakes.windows(2) {
+        let (low_stake, abs_stake) = (stake[0], stake[1]);
+
+        let winnable_players: Vec<_> = stakes
+            .iter()
+            .filter(|(_, stake)| *stake >= abs_stake)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        // folded chips above the top live stake can't be matched, so they go to the last pot
+        let pot = state
+            .players
+            .values()
+            .map(|p| match abs_stake == top_stake {
+                true => p.stake.saturating_sub(low_stake),
+                false => p.stake.min(abs_stake).saturating_sub(low_stake),
+            })
+            .sum();
+        pots.push((pot, winnable_players));
+    }
+
+    pots
+}
+
+/// Players in seat order, starting from the first player left of the dealer.
+fn seats_from_dealer(state: &state::State) -> Vec<state::PlayerId> {
+    let seats = state.players.keys().cloned().collect::<Vec<_>>();
+    seats
+        .iter()
+        .cycle()
+        .skip(1)
+        .take(seats.len())
+        .cloned()
+        .collect()
+}
+
+/// Splits a pot evenly between winners, with any odd chips going to the
+/// winners closest to the left of the dealer.
+fn split_pot(
+    pot: u64,
+    winners: &[state::PlayerId],
+    seats: &[state::PlayerId],
+) -> Vec<(state::PlayerId, u64)> {
+    if winners.is_empty() {
+        return vec![];
+    }
+
+    let share = pot / winners.len() as u64;
+    let mut remainder = pot % winners.len() as u64;
+
+    seats
+        .iter()
+        .filter(|id| winners.contains(id))
+        .map(|id| {
+            let odd_chip = remainder.min(1);
+            remainder -= odd_chip;
+            (id.clone(), share + odd_chip)
+        })
+        .collect()
+}
+
 pub(crate) fn cards_on_table(state: &state::State) -> Vec<(cards::CardSuite, cards::CardValue)> {
     let cards = state
         .round
@@ -813,30 +1927,97 @@ pub(crate) fn cards_on_table(state: &state::State) -> Vec<(cards::CardSuite, car
     cards
 }
 
+/// Checks the secret sent with a request for a player's cards, if the room
+/// requires one.
+pub(crate) fn check_session_secret(
+    state: &state::State,
+    player_id: &state::PlayerId,
+    session_secret: Option<&str>,
+) -> Result<(), String> {
+    let player = state
+        .players
+        .get(player_id)
+        .ok_or("Player not found".to_string())?;
+    if !state.config.session_secret_required() {
+        return Ok(());
+    }
+    match session_secret {
+        Some(secret) if constant_time_eq(secret, player.session_secret.as_ref()) => Ok(()),
+        Some(_) => Err("Session secret does not match".to_string()),
+        None => Err("Session secret is required".to_string()),
+    }
+}
+
+/// Compares two strings without returning early on the first difference, so
+/// the time taken doesn't reveal how much of a secret was guessed.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
 pub(crate) fn cards_in_hand(
     state: &state::State,
     player_id: &state::PlayerId,
-) -> Option<(
-    (cards::CardSuite, cards::CardValue),
-    (cards::CardSuite, cards::CardValue),
-)> {
+) -> Option<Vec<(cards::CardSuite, cards::CardValue)>> {
     let player = state.players.get(player_id)?;
-    let cards = player.cards.clone();
-    let cards = (
-        (cards.0.suite.clone(), cards.0.value.clone()),
-        (cards.1.suite.clone(), cards.1.value.clone()),
-    );
+    let cards = player.cards.iter().map(|c| (c.suite, c.value)).collect();
     Some(cards)
 }
 
+pub(crate) fn game_player_state(
+    state: &state::State,
+    player_id: &state::PlayerId,
+) -> Option<models::GamePlayerState> {
+    let player = state.players.get(player_id)?;
+    let call_amount = call_amount(state).unwrap_or(0);
+    let current_round_stake = player_stake_in_round(state, player_id);
+    let pot = state.round.pot;
+    let pot_odds = match call_amount.saturating_sub(current_round_stake) {
+        0 => None,
+        to_call => Some(to_call as f64 / (pot + to_call) as f64),
+    };
+
+    Some(models::GamePlayerState {
+        state: game_phase(state),
+        balance: player.balance,
+        cards: cards_in_hand(state, player_id)?,
+        your_turn: is_player_turn(state, player_id),
+        call_amount,
+        min_raise_to: min_raise_to(state),
+        players_count: state.players.len(),
+        turn_expires_dt: turn_expires_dt(state, player_id),
+        last_update: state.last_update.as_u64(),
+        current_round_stake,
+        session_net: player.balance as i64 - player.buy_in_total as i64,
+        pot,
+        pot_odds,
+    })
+}
+
 pub(crate) fn is_player_turn(state: &state::State, player_id: &state::PlayerId) -> bool {
     state.status == state::GameStatus::Playing
         && state.round.players_turn.as_ref() == Some(&player_id)
 }
 
+pub(crate) fn room_summary(
+    room_code: &state::room::RoomCode,
+    state: &state::State,
+) -> models::RoomSummary {
+    models::RoomSummary {
+        room_code: room_code.to_string(),
+        state: game_phase(state),
+        player_count: state.players.len(),
+        last_update: state.last_update.as_u64(),
+    }
+}
+
 pub(crate) fn game_phase(state: &state::State) -> models::GamePhase {
     match state.status {
         state::GameStatus::Joining => models::GamePhase::Waiting,
+        state::GameStatus::Paused => models::GamePhase::Paused,
         state::GameStatus::Playing => models::GamePhase::Playing,
         state::GameStatus::Complete => models::GamePhase::Complete,
         state::GameStatus::Idle => models::GamePhase::Idle,
@@ -854,19 +2035,10 @@ pub(crate) fn ticker(state: &state::State) -> Option<String> {
             )),
         }
     }
-    fn ticker_item(
-        state: &state::State,
-        item: &state::ticker::TickerItem,
-        now: state::dt::Instant,
-    ) -> String {
-        let start_offset_ms = (item.start.as_u64() as i64) - (now.as_u64() as i64);
-        let duration = item.end.as_u64().saturating_sub(item.start.as_u64());
+    fn ticker_item(entry: models::TickerEntry) -> String {
         format!(
             "{}|{}|{}\x00{}",
-            item.seq_index,
-            start_offset_ms,
-            duration,
-            item.payload.format(state)
+            entry.seq, entry.start_offset_ms, entry.duration_ms, entry.text
         )
     }
 
@@ -876,14 +2048,36 @@ pub(crate) fn ticker(state: &state::State) -> Option<String> {
 
     let now = state::dt::Instant::default();
     let header = ticker_header(state, now)?;
-    let items: Vec<_> = state
-        .ticker
-        .iter()
-        .map(|item| ticker_item(state, item, now))
+    let items: Vec<_> = ticker_entries(state, now)
+        .into_iter()
+        .map(ticker_item)
         .collect();
     Some(format!("{}\n{}", header, items.join("\n")))
 }
 
+pub(crate) fn ticker_items(state: &state::State) -> Vec<models::TickerEntry> {
+    if state.config.ticker_disabled() {
+        return vec![];
+    }
+    ticker_entries(state, state::dt::Instant::default())
+}
+
+/// The most recent ticker items, up to `TICKER_MAX_ITEMS`.
+fn ticker_entries(state: &state::State, now: state::dt::Instant) -> Vec<models::TickerEntry> {
+    let skip = state.ticker.len().saturating_sub(state::TICKER_MAX_ITEMS);
+    state
+        .ticker
+        .iter()
+        .skip(skip)
+        .map(|item| models::TickerEntry {
+            seq: item.seq_index,
+            start_offset_ms: (item.start.as_u64() as i64) - (now.as_u64() as i64),
+            duration_ms: item.end.as_u64().saturating_sub(item.start.as_u64()),
+            text: item.payload.format(state),
+        })
+        .collect()
+}
+
 pub(crate) fn completed_game(state: &state::State) -> Option<models::CompletedGame> {
     if state.status != state::GameStatus::Complete {
         return None;
@@ -916,46 +2110,226 @@ pub(crate) fn completed_game(state: &state::State) -> Option<models::CompletedGa
     let winning_hand = completed_round
         .best_hand
         .as_ref()
-        .map(|(_, hand)| hand.to_string());
+        .map(|(_, hand)| hand.strength().to_string());
+    let tiebreak_cards = completed_round
+        .best_hand
+        .as_ref()
+        .map(|(_, hand)| hand.cards().to_vec());
 
-    Some(models::CompletedGame {
+    let players = players_by_seat(state);
+    let shown = |p: &state::Player| {
+        let is_winner = winners.contains_key(&p.id);
+        let mucked = p.muck && !is_winner;
+        !p.folded && !mucked && !completed_round.hide_cards
+    };
+    // the deck gives away every hole card, so it is only revealed when no hand was hidden
+    let all_shown = players.iter().all(|p| p.cards.is_empty() || shown(p));
+
+    Some(models::CompletedGame {
         winner_name,
         winning_hand,
-        player_cards: state
-            .players
+        tiebreak_cards,
+        second_board: (completed_round.second_board.as_ref())
+            .map(|board| board.iter().map(|c| (c.suite, c.value)).collect()),
+        player_cards: players
             .iter()
-            .map(|(_, p)| {
-                (!p.folded && !completed_round.hide_cards).then(|| {
-                    (
-                        (p.cards.0.suite.clone(), p.cards.0.value.clone()),
-                        (p.cards.1.suite.clone(), p.cards.1.value.clone()),
-                    )
-                })
-            })
+            .map(|p| shown(p).then(|| p.cards.iter().map(|c| (c.suite, c.value)).collect()))
             .collect(),
+        rake_taken: completed_round.rake,
+        deck_reveal: (state.round.deck_commitment.as_ref())
+            .filter(|_| all_shown)
+            .map(|commitment| models::DeckReveal {
+                seed: commitment.seed.clone(),
+                deck: commitment.deck.iter().map(|c| (c.suite, c.value)).collect(),
+            }),
+    })
+}
+
+/// Formats the room's event log as newline-delimited JSON, oldest first.
+pub(crate) fn event_log(state: &state::State) -> String {
+    state
+        .ticker
+        .log()
+        .filter_map(|(timestamp, event)| {
+            let event = models::LoggedEvent {
+                timestamp: timestamp.as_u64(),
+                event: event.kind(),
+                message: event.format(state),
+            };
+            serde_json::to_string(&event).ok()
+        })
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// History of the most recently completed game, kept until the following game completes.
+pub(crate) fn hand_history(state: &state::State) -> Option<models::HandHistory> {
+    let completed = match state.status {
+        state::GameStatus::Complete => state.round.completed.as_ref(),
+        _ => state.last_completed.as_ref(),
+    }?;
+    let player_name = |id: &state::PlayerId| state.players.get(id).map(|p| p.name.clone());
+    let card = |c: &cards::Card| (c.suite, c.value);
+
+    let actions = completed
+        .history
+        .iter()
+        .map(|item| models::HandHistoryAction {
+            name: player_name(&item.player_id),
+            street: match item.cards_on_table {
+                0..=2 => models::Street::Preflop,
+                3 => models::Street::Flop,
+                4 => models::Street::Turn,
+                _ => models::Street::River,
+            },
+            action: match item.action {
+                state::HandAction::Ante => models::HandActionKind::Ante,
+                state::HandAction::SmallBlind => models::HandActionKind::SmallBlind,
+                state::HandAction::BigBlind => models::HandActionKind::BigBlind,
+                state::HandAction::BringIn => models::HandActionKind::BringIn,
+                state::HandAction::Straddle => models::HandActionKind::Straddle,
+                state::HandAction::Bet(state::BetAction::Check) => models::HandActionKind::Check,
+                state::HandAction::Bet(state::BetAction::Call) => models::HandActionKind::Call,
+                state::HandAction::Bet(state::BetAction::BetTo(_)) => models::HandActionKind::BetTo,
+                state::HandAction::Bet(state::BetAction::RaiseTo(_)) => {
+                    models::HandActionKind::RaiseTo
+                }
+                state::HandAction::Bet(state::BetAction::AllIn) => models::HandActionKind::AllIn,
+                state::HandAction::Fold => models::HandActionKind::Fold,
+            },
+            amount: item.amount,
+        })
+        .collect();
+
+    let showdown = completed
+        .showdown
+        .iter()
+        .map(|(id, cards)| models::ShowdownHand {
+            name: player_name(id),
+            cards: cards.iter().map(card).collect(),
+        })
+        .collect();
+
+    let winners = completed
+        .winners
+        .iter()
+        .map(|winner| models::HandHistoryWinner {
+            name: player_name(&winner.player_id),
+            winnings: winner.winnings,
+            hand: winner.hand.as_ref().map(|hand| hand.to_string()),
+        })
+        .collect();
+
+    Some(models::HandHistory {
+        actions,
+        board: completed.board.iter().map(card).collect(),
+        second_board: (completed.second_board.as_ref()).map(|b| b.iter().map(card).collect()),
+        showdown,
+        winners,
     })
 }
 
+pub(crate) fn room_config(state: &state::State) -> models::RoomConfig {
+    let config = &state.config;
+    models::RoomConfig {
+        small_blind: config.small_blind(),
+        big_blind: config.big_blind(),
+        ante: config.ante(),
+        starting_balance: config.starting_balance(),
+        max_players: config.max_players(),
+        turn_timeout_seconds: config.turn_timeout_seconds(),
+        variant: match config.variant() {
+            cards::Variant::Standard => models::GameVariant::Standard,
+            cards::Variant::ShortDeck => models::GameVariant::ShortDeck,
+            cards::Variant::Omaha => models::GameVariant::Omaha,
+        },
+    }
+}
+
+pub(crate) fn room_debug(state: &state::State) -> models::RoomDebug {
+    let card = |c: &cards::Card| (c.suite, c.value);
+    models::RoomDebug {
+        deck: state.round.deck.remaining().map(card).collect(),
+        players: players_by_seat(state)
+            .into_iter()
+            .map(|p| models::DebugPlayer {
+                name: p.name.clone(),
+                cards: p.cards.iter().map(card).collect(),
+            })
+            .collect(),
+        board: state.round.cards_on_table.iter().map(card).collect(),
+    }
+}
+
+/// The room as shown on the big screen, shared by every route that serves it.
+pub(crate) fn room_view(
+    state: &state::State,
+    room_code: Option<String>,
+    pair_screen_code: Option<String>,
+    now: state::dt::Instant,
+) -> models::GameClientRoom {
+    models::GameClientRoom {
+        state: game_phase(state),
+        players: room_players(state),
+        pot: state.round.pot,
+        cards: cards_on_table(state),
+        completed: completed_game(state),
+        ticker: ticker(state),
+        ticker_items: ticker_items(state),
+        room_code,
+        pair_screen_code,
+        dealer_index: dealer_index(state),
+        ready_count: ready_count(state),
+        blind_level: state.blind_level,
+        small_blind: small_blind(state),
+        spectator_count: spectator_count(state, now),
+        next_hand_at: next_hand_at(state),
+        deck_commitment: deck_commitment(state),
+        last_update: state.last_update.as_u64(),
+    }
+}
+
 pub(crate) fn room_players(state: &state::State) -> Vec<models::GameClientPlayer> {
     let current_player_id = state.round.players_turn.as_ref();
-    let players = state
-        .players
-        .iter()
-        .map(|(_, p)| models::GameClientPlayer {
+    let now = state::dt::Instant::default();
+    let players = players_by_seat(state)
+        .into_iter()
+        .map(|p| models::GameClientPlayer {
             name: p.name.clone(),
             balance: p.balance,
             folded: p.folded,
+            all_in: p.all_in,
+            sitting_out: p.sitting_out,
+            ready: p.ready,
             emoji: p.emoji.as_ref().map(|(e, _)| e.to_string()),
             photo: player_photo_url(p),
             color_hue: player_color_hue(p),
             turn_expires_dt: p.ttl.map(|dt| dt.into()).filter(|_| {
                 current_player_id == Some(&p.id) && state.status == state::GameStatus::Playing
             }),
+            online: player_online(p, now),
         })
         .collect();
     players
 }
 
+/// Players in the order they sat down at the table, which unlike the
+/// dealer-first player order does not change as the dealer rotates.
+fn players_by_seat(state: &state::State) -> Vec<&state::Player> {
+    let mut players: Vec<_> = state.players.iter().map(|(_, p)| p).collect();
+    players.sort_by_key(|p| p.seat);
+    players
+}
+
+/// Index of the dealer in the seated player order returned by `room_players`.
+pub(crate) fn dealer_index(state: &state::State) -> usize {
+    let dealer = state.players.keys().next();
+    players_by_seat(state)
+        .iter()
+        .position(|p| Some(&p.id) == dealer)
+        .unwrap_or(0)
+}
+
 fn player_photo_url(p: &state::Player) -> Option<String> {
     let state::PlayerPhoto(_, token) = p.photo.as_ref()?;
     Some(format!("player/photo/{}", token))
@@ -972,15 +2346,14 @@ pub(crate) fn fold_player(
     state: &mut state::State,
     player_id: &state::PlayerId,
 ) -> Result<(), String> {
-    if state.round.players_turn.as_ref() != Some(player_id) {
-        return Err("Not your turn".to_string());
-    }
+    check_players_turn(state, player_id)?;
     let player = state
         .players
         .get_mut(&player_id)
         .ok_or("Player not found".to_string())?;
 
     player.folded = true;
+    record_hand_action(state, player_id, state::HandAction::Fold, 0);
 
     state
         .ticker
@@ -995,7 +2368,14 @@ pub(crate) fn fold_player(
             );
             let pot = state.round.pot;
             only_player_left.balance += pot;
+            only_player_left.stats.record_win(pot);
             state.round.pot = 0;
+            let winner = state::RoundWinner {
+                player_id: only_player_left.id.clone(),
+                hand: None,
+                winnings: pot,
+                total_pot_winnings: pot,
+            };
 
             state
                 .ticker
@@ -1003,13 +2383,21 @@ pub(crate) fn fold_player(
 
             rotate_dealer(state);
             state.status = state::GameStatus::Complete;
+            state.round.players_turn = None;
             state.round.raises.clear();
             state.round.calls.clear();
             state.round.completed = Some(state::CompletedRound {
-                winners: vec![],
+                winners: vec![winner],
                 best_hand: None,
                 hide_cards: true,
+                history: state.round.history.clone(),
+                board: state.round.cards_on_table.clone(),
+                second_board: None,
+                showdown: vec![],
+                rake: 0,
             });
+            report_hand_result(state, pot);
+            record_hand_metrics(state, pot);
             return Ok(());
         }
         _ => {}
@@ -1038,18 +2426,66 @@ pub(crate) fn reset_ttl(state: &mut state::State, id: &state::PlayerId) -> Resul
     }
 }
 
+/// Corrects a player's balance between hands, recording the change in the
+/// event log.
+pub(crate) fn adjust_balance(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+    delta: i64,
+) -> Result<(), String> {
+    if state.status == state::GameStatus::Playing {
+        return Err("Cannot adjust balances during a game".to_string());
+    }
+    let player = state
+        .players
+        .get_mut(player_id)
+        .ok_or("Player not found".to_string())?;
+    player.balance = player
+        .balance
+        .checked_add_signed(delta)
+        .ok_or("Adjustment would leave a negative balance".to_string())?;
+    // a correction counts as chips bought in or taken back, not as winnings,
+    // so it leaves the session net unchanged
+    player.buy_in_total = player.buy_in_total.saturating_add_signed(delta);
+
+    state
+        .ticker
+        .emit(TickerEvent::BalanceAdjusted(player_id.clone(), delta));
+    Ok(())
+}
+
 pub(crate) fn transfer_funds(
     state: &mut state::State,
     player_id: &state::PlayerId,
     payload: &models::TransferRequest,
-) -> Result<(), ()> {
-    let player_balance = state.players.get(&player_id).ok_or(())?.balance;
+) -> Result<(), String> {
+    if state.status == state::GameStatus::Playing {
+        info!("Player {} failed to transfer: game in progress", player_id);
+        return Err("Cannot transfer funds during a game".to_string());
+    }
+    let player = state
+        .players
+        .get(player_id)
+        .ok_or("Player not found".to_string())?;
+    let (player_balance, transferred) = (player.balance, player.transferred);
+    if let Some(limit) = state.config.transfer_limit() {
+        if transferred.saturating_add(payload.amount) > limit {
+            info!(
+                "Player {} failed to transfer: over the limit of {}",
+                player_id, limit
+            );
+            return Err(format!(
+                "Transfer exceeds the limit of {} per game ({} already transferred)",
+                limit, transferred
+            ));
+        }
+    }
     let remaining = player_balance.checked_sub(payload.amount).ok_or_else(|| {
         info!(
             "Player {} failed to transfer: insufficient funds",
             player_id
         );
-        ()
+        "Insufficient funds".to_string()
     })?;
     let destination_id = {
         let destination_id = state
@@ -1067,15 +2503,22 @@ pub(crate) fn transfer_funds(
                     "Player {} failed to transfer: destination not found",
                     player_id
                 );
-                ()
+                "Destination not found".to_string()
             })?;
+        if &destination_id == player_id {
+            info!(
+                "Player {} failed to transfer: destination is self",
+                player_id
+            );
+            return Err("Cannot transfer funds to yourself".to_string());
+        }
 
         let destination = state.players.get_mut(&destination_id).ok_or_else(|| {
             info!(
                 "Player {} failed to transfer: destination not found (destination_id: {})",
                 player_id, destination_id
             );
-            ()
+            "Destination not found".to_string()
         })?;
         destination.balance += payload.amount;
         destination.id.clone()
@@ -1086,6 +2529,7 @@ pub(crate) fn transfer_funds(
             .get_mut(&player_id)
             .expect("Player must exist");
         player.balance = remaining;
+        player.transferred += payload.amount;
     }
     state
         .ticker
@@ -1098,133 +2542,589 @@ pub(crate) fn transfer_funds(
     Ok(())
 }
 
-pub(crate) fn call_amount(state: &state::State) -> Option<u64> {
-    state.round.raises.last().map(|(_, last_stake)| *last_stake)
-}
+pub(crate) fn send_chat_message(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+    message: &str,
+) -> Result<(), String> {
+    let player = state
+        .players
+        .get(player_id)
+        .ok_or("Player not found".to_string())?;
 
-pub(crate) fn min_raise_to(state: &state::State) -> u64 {
-    let raises: Vec<_> = [0_u64]
-        .into_iter()
-        .chain(state.round.raises.iter().map(|(_, s)| *s))
-        .collect();
+    let chat_message = state::ChatMessage {
+        player_id: player_id.clone(),
+        name: player.name.clone(),
+        message: message.trim().to_owned(),
+        sent: state::dt::Instant::default(),
+    };
 
-    let max_raise = raises.iter().max().unwrap_or(&0);
+    if state.chat.len() >= state::CHAT_HISTORY_LENGTH {
+        state.chat.pop_front();
+    }
+    state.chat.push_back(chat_message);
+    Ok(())
+}
 
-    let largest_raise_diff = raises
-        .windows(2)
-        .map(|w| w[1].saturating_sub(w[0])) // TODO: fix 'attempt to subtract with overflow' error after approx 300 games
-        .max()
-        .unwrap_or(0)
-        .max(state.config.big_blind());
+/// Holds a seat for a visitor who is still choosing a name, returning the
+/// reservation token to pass when joining.
+pub(crate) fn reserve_seat(
+    state: &mut state::State,
+    now: state::dt::Instant,
+) -> Result<(String, state::dt::Instant), String> {
+    if state.status == state::GameStatus::Playing {
+        return Err("Game already started".to_string());
+    }
+    if room_full(state) {
+        return Err("Room is full".to_string());
+    }
 
-    let min_raise_to = max_raise + largest_raise_diff;
-    min_raise_to
+    let token = state::token::Token::default().to_string();
+    let mut expiry = now;
+    expiry.add_seconds(state::SEAT_RESERVATION_SECONDS);
+    state.reservations.insert(token.clone(), expiry);
+
+    Ok((token, expiry))
 }
 
-pub(crate) fn turn_expires_dt(state: &state::State, player_id: &state::PlayerId) -> Option<u64> {
-    state
-        .players
-        .get(player_id)
-        .and_then(|p| p.ttl.map(|dt| dt.into()))
+/// Adds a player into the seat held by their reservation. The reservation is
+/// kept if the player cannot be added, so they can retry until it expires.
+pub(crate) fn add_reserved_player(
+    state: &mut state::State,
+    player_name: &str,
+    player_id: state::PlayerId,
+    reservation: &str,
+) -> Result<state::PlayerId, String> {
+    let expiry = state
+        .reservations
+        .remove(reservation)
+        .ok_or("Reservation not found".to_string())?;
+
+    add_new_player(state, player_name, player_id).inspect_err(|_| {
+        state.reservations.insert(reservation.to_string(), expiry);
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{
-        game::tests::fixtures::GameFixture,
-        state::{BIG_BLIND, SMALL_BLIND, STARTING_BALANCE},
-    };
-    use state::BetAction as P;
+pub(crate) fn expire_seat_reservations(state: &mut state::State, now: state::dt::Instant) {
+    state.reservations.retain(|_, expiry| *expiry >= now);
+}
 
-    #[test]
-    fn two_player_game_deals_correct_cards_to_table() {
-        let (state, _) = fixtures::start_two_player_game(GameFixture::Round1);
-        assert_eq!(cards_on_table(&state).len(), 0);
+/// Counts a visitor polling the room as a spectator until the TTL passes.
+/// The room's own big screens and seated players aren't counted.
+pub(crate) fn record_spectator(state: &state::State, apid: &str, now: state::dt::Instant) {
+    if state.screen_apids.contains(apid) || state.players.values().any(|p| p.apid == apid) {
+        return;
+    }
+    let mut expiry = now;
+    expiry.add_seconds(state::SPECTATOR_TTL_SECONDS);
+    state.spectators.record(apid, expiry);
+}
 
-        let (state, _) = fixtures::start_two_player_game(GameFixture::Round2);
-        assert_eq!(cards_on_table(&state).len(), 3);
+pub(crate) fn spectator_count(state: &state::State, now: state::dt::Instant) -> usize {
+    state.spectators.count(now)
+}
 
-        let (state, _) = fixtures::start_two_player_game(GameFixture::Round3);
-        assert_eq!(cards_on_table(&state).len(), 4);
+/// Whether the player's last seen time is old enough to be worth recording
+/// again, so that polls don't need the write lock every time.
+pub(crate) fn player_seen_stale(
+    state: &state::State,
+    player_id: &state::PlayerId,
+    now: state::dt::Instant,
+) -> bool {
+    state.players.get(player_id).is_some_and(|player| {
+        !player.last_seen.is_some_and(|seen| {
+            seen.as_u64() + state::PLAYER_SEEN_REFRESH_SECONDS * 1000 >= now.as_u64()
+        })
+    })
+}
 
-        let (state, _) = fixtures::start_two_player_game(GameFixture::Round4);
-        assert_eq!(cards_on_table(&state).len(), 5);
+pub(crate) fn record_player_seen(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+    now: state::dt::Instant,
+) {
+    if let Some(player) = state.players.get_mut(player_id) {
+        player.last_seen = Some(now);
     }
+}
 
-    #[test]
-    fn two_player_game_redeals_players_cards_after_round() {
-        let (mut state, (player_1, player_2)) =
-            fixtures::start_two_player_game(GameFixture::Complete);
-        let player_1_cards = cards_in_hand(&state, &player_1).unwrap();
-        let player_2_cards = cards_in_hand(&state, &player_2).unwrap();
+fn player_online(player: &state::Player, now: state::dt::Instant) -> bool {
+    player
+        .last_seen
+        .is_some_and(|seen| seen.as_u64() + state::PLAYER_ONLINE_SECONDS * 1000 >= now.as_u64())
+}
 
-        state.config = Default::default();
-        start_game(&mut state).unwrap();
-        let new_player_1_cards = cards_in_hand(&state, &player_1).unwrap();
-        let new_player_2_cards = cards_in_hand(&state, &player_2).unwrap();
+pub(crate) fn expire_spectators(state: &state::State, now: state::dt::Instant) {
+    state.spectators.expire(now);
+}
 
-        assert_ne!(
-            (player_1_cards, player_2_cards),
-            (new_player_1_cards, new_player_2_cards)
-        );
+/// Shows an emoji next to the player, at most once per cooldown so that the
+/// ticker isn't flooded.
+pub(crate) fn send_emoji(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+    emoji: state::ticker::emoji::TickerEmoji,
+    now: state::dt::Instant,
+) -> Result<(), String> {
+    let player = state
+        .players
+        .get_mut(player_id)
+        .ok_or("Player not found".to_string())?;
+    let cooldown_ms = state::PLAYER_EMOJI_COOLDOWN_SECONDS * 1000;
+    if player
+        .emoji
+        .as_ref()
+        .is_some_and(|(_, sent)| sent.as_u64() + cooldown_ms > now.as_u64())
+    {
+        return Err("Already sent an emoji recently".to_string());
     }
 
-    #[test]
-    fn game_pays_outright_winner_from_pot() {
-        let mut state = state::State::default();
-        state.config = state.config.with_card_deal_disabled();
-        state.round.deck = cards::Deck::ordered();
+    player.emoji = Some((emoji, now));
+    state
+        .ticker
+        .emit(TickerEvent::PlayerSentEmoji(player_id.clone(), emoji));
 
-        let state = &mut state;
+    Ok(())
+}
 
-        let player_1 = fixtures::add_player(state, "player_1").unwrap();
-        let player_2 = fixtures::add_player(state, "player_2").unwrap();
-        let player_3 = fixtures::add_player(state, "player_3").unwrap();
-        let player_4 = fixtures::add_player(state, "player_4").unwrap();
-        let player_5 = fixtures::add_player(state, "player_5").unwrap();
+/// Lets a visitor without a seat nudge the room, at most once per cooldown.
+pub(crate) fn knock(
+    state: &mut state::State,
+    apid: &str,
+    now: state::dt::Instant,
+) -> Result<(), String> {
+    let cooldown_ms = state::KNOCK_COOLDOWN_SECONDS * 1000;
+    state
+        .knocks
+        .retain(|_, knocked| knocked.as_u64() + cooldown_ms > now.as_u64());
+    if state.knocks.contains_key(apid) {
+        return Err("Already knocked recently".to_string());
+    }
 
-        assert_eq!(state.players.len(), 5);
-        assert_eq!(state.status, state::GameStatus::Joining);
-        let starting_balance = state.players.iter().map(|(_, p)| p.balance).next().unwrap();
-        assert_eq!(starting_balance, state::STARTING_BALANCE);
+    state.knocks.insert(apid.to_string(), now);
+    state.ticker.emit(TickerEvent::Knock);
 
-        start_game(state).unwrap();
+    Ok(())
+}
 
-        assert_eq!(cards_on_table(state).len(), 0);
+pub(crate) fn start_vote(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+    motion: state::vote::Motion,
+) -> Result<(), String> {
+    if state.vote.is_some() {
+        return Err("A vote is already in progress".to_string());
+    }
+    if state.players.get(player_id).is_none() {
+        return Err("Player not found".to_string());
+    }
+    if let state::vote::Motion::KickPlayer(target) = &motion {
+        if state.players.get(target).is_none() {
+            return Err("Player to kick not found".to_string());
+        }
+    }
 
-        accept_player_bet(state, &player_3, P::Call).expect("R1-P3");
-        accept_player_bet(state, &player_4, P::Call).expect("R1-P4");
-        accept_player_bet(state, &player_5, P::Call).expect("R1-P5");
-        accept_player_bet(state, &player_1, P::Call).expect("R1-P1");
-        accept_player_bet(state, &player_2, P::Check).expect("R1-P2");
-        assert_eq!(state.round.pot, 100);
-        assert_eq!(cards_on_table(state).len(), 3);
+    let mut end_time = state::dt::Instant::default();
+    end_time.add_seconds(state::VOTE_TIMEOUT_SECONDS);
+    let votes = HashMap::from([(player_id.clone(), true)]);
 
-        accept_player_bet(state, &player_1, P::RaiseTo(500)).expect("R2-P1");
-        assert_eq!(state.round.pot, 600);
-        accept_player_bet(state, &player_2, P::Call).expect("R2-P2");
-        assert_eq!(state.round.pot, 1100);
-        accept_player_bet(state, &player_3, P::Call).expect("R2-P3");
-        assert_eq!(state.round.pot, 1600);
-        fold_player(state, &player_4).expect("R2-P4");
-        fold_player(state, &player_5).expect("R2-P4");
+    state.vote = Some(state::vote::Vote {
+        motion: motion.clone(),
+        started_by: player_id.clone(),
+        end_time,
+        votes,
+    });
+    state
+        .ticker
+        .emit(TickerEvent::VoteStarted(player_id.clone(), motion));
 
-        assert_eq!(cards_on_table(state).len(), 4);
+    resolve_vote(state, state::dt::Instant::default());
+    Ok(())
+}
 
-        accept_player_bet(state, &player_1, P::Check).unwrap();
-        accept_player_bet(state, &player_2, P::Check).unwrap();
-        accept_player_bet(state, &player_3, P::Check).unwrap();
+pub(crate) fn cast_vote(
+    state: &mut state::State,
+    player_id: &state::PlayerId,
+    approve: bool,
+) -> Result<(), String> {
+    if state.players.get(player_id).is_none() {
+        return Err("Player not found".to_string());
+    }
+    let vote = state
+        .vote
+        .as_mut()
+        .ok_or("No vote in progress".to_string())?;
+    if vote.votes.contains_key(player_id) {
+        return Err("Player has already voted".to_string());
+    }
+    vote.votes.insert(player_id.clone(), approve);
 
-        let pot_before_payout = state.round.pot;
-        let winner_balance_before_payout = state.players.get(&player_1).unwrap().balance;
+    resolve_vote(state, state::dt::Instant::default());
+    Ok(())
+}
 
-        assert_eq!(pot_before_payout, (BIG_BLIND * 5) + (500 * 3));
-        assert_eq!(cards_on_table(state).len(), 5);
+/// Resolves the current vote once every seated player has voted or the vote
+/// has expired. A motion passes with a strict majority of seated players.
+pub(crate) fn resolve_vote(state: &mut state::State, now: state::dt::Instant) -> Option<bool> {
+    let vote = state.vote.as_ref()?;
+    let all_voted = state.players.keys().all(|id| vote.votes.contains_key(id));
+    if !all_voted && vote.end_time > now {
+        return None;
+    }
 
-        accept_player_bet(state, &player_1, P::Check).unwrap();
-        accept_player_bet(state, &player_2, P::Check).unwrap();
-        accept_player_bet(state, &player_3, P::Check).unwrap();
+    let vote = state.vote.take()?;
+    let approvals = vote
+        .votes
+        .iter()
+        .filter(|(id, approve)| **approve && state.players.get(id).is_some())
+        .count();
+    let passed = approvals * 2 > state.players.len();
+
+    info!(
+        "Vote to {} completed: {} of {} players approved",
+        vote.motion,
+        approvals,
+        state.players.len()
+    );
+    if passed {
+        apply_motion(state, &vote.motion);
+    }
+    state
+        .ticker
+        .emit(TickerEvent::VoteCompleted(vote.motion, passed));
+
+    Some(passed)
+}
+
+/// Drops a player kicked by a passed vote from the room registry, so their
+/// player id no longer resolves to this room.
+pub(crate) async fn unregister_kicked_player(
+    state: &state::State,
+    shared_state: &state::SharedState,
+    motion: Option<state::vote::Motion>,
+) {
+    let Some(state::vote::Motion::KickPlayer(player_id)) = motion else {
+        return;
+    };
+    if state.vote.is_none() && state.players.get(&player_id).is_none() {
+        shared_state.remove(&player_id).await;
+    }
+}
+
+/// The small blind for this hand, which starts at the room's configured blind.
+fn small_blind(state: &state::State) -> u64 {
+    state.small_blind.unwrap_or(state.config.small_blind())
+}
+
+fn big_blind(state: &state::State) -> u64 {
+    small_blind(state) * 2
+}
+
+fn raise_scheduled_blinds(state: &mut state::State) {
+    let level = state.config.blind_schedule().level(state.hands_started);
+    let Some((level, small_blind)) = level.filter(|(level, _)| *level > state.blind_level) else {
+        return;
+    };
+
+    state.blind_level = level;
+    if small_blind < state.config.starting_balance() {
+        state.small_blind = Some(small_blind);
+        state.ticker.emit(TickerEvent::BlindsRaised(small_blind));
+    }
+}
+
+fn apply_motion(state: &mut state::State, motion: &state::vote::Motion) {
+    match motion {
+        state::vote::Motion::DoubleBlinds => {
+            let small_blind = state.next_small_blind.unwrap_or(small_blind(state)) * 2;
+            if small_blind < state.config.starting_balance() {
+                state.next_small_blind = Some(small_blind);
+            }
+        }
+        state::vote::Motion::KickPlayer(player_id) => {
+            if let Ok(player) = take_player(state, player_id) {
+                info!("Player {} was kicked by vote", player.id);
+                state.ticker.emit(TickerEvent::PlayerKicked(player.name));
+            }
+        }
+    }
+}
+
+/// What's needed to estimate a player's equity, copied out of the room so the
+/// simulation can run without holding its lock.
+pub(crate) struct EquityHand {
+    variant: cards::Variant,
+    board: Vec<cards::Card>,
+    cards: Vec<cards::Card>,
+    opponents: usize,
+}
+
+pub(crate) fn equity_hand(
+    state: &state::State,
+    player_id: &state::PlayerId,
+) -> Result<EquityHand, String> {
+    if state.status != state::GameStatus::Playing {
+        return Err("Game not in progress".to_string());
+    }
+    let player = state
+        .players
+        .get(player_id)
+        .ok_or("Player not found".to_string())?;
+    if player.folded {
+        return Err("Player has folded".to_string());
+    }
+
+    let opponents = state
+        .players
+        .values()
+        .filter(|p| !p.folded && p.id != player.id)
+        .count();
+
+    Ok(EquityHand {
+        variant: state.config.variant(),
+        board: state.round.cards_on_table.clone(),
+        cards: player.cards.clone(),
+        opponents,
+    })
+}
+
+/// Estimates a player's chances by dealing out the rest of the board, and a
+/// random hand to every opponent still in, many times over. Opponents' real
+/// cards are treated as unknown so nothing about them leaks.
+pub(crate) fn simulate_equity(hand: &EquityHand) -> models::PlayerEquity {
+    use rand::seq::SliceRandom;
+
+    let EquityHand {
+        variant,
+        board,
+        cards,
+        opponents,
+    } = hand;
+    let known: Vec<_> = board
+        .iter()
+        .chain(cards)
+        .map(|c| (c.suite, c.value))
+        .collect();
+    let mut unknown: Vec<_> = cards::Deck::ordered()
+        .for_variant(*variant)
+        .remaining()
+        .filter(|c| !known.contains(&(c.suite, c.value)))
+        .copied()
+        .collect();
+
+    let (mut wins, mut ties) = (0, 0);
+    let mut rng = rand::thread_rng();
+    for _ in 0..state::EQUITY_SIMULATIONS {
+        unknown.shuffle(&mut rng);
+        let mut draw = unknown.iter().copied();
+
+        let mut full_board = board.clone();
+        full_board.extend(draw.by_ref().take(5 - board.len()));
+        let score = cards::Card::evaluate_hand_for(*variant, cards, &full_board);
+        let best_opponent = (0..*opponents)
+            .map(|_| {
+                let cards: Vec<_> = draw.by_ref().take(cards.len()).collect();
+                cards::Card::evaluate_hand_for(*variant, &cards, &full_board)
+            })
+            .max();
+
+        match best_opponent.map(|opponent| score.cmp(&opponent)) {
+            None | Some(std::cmp::Ordering::Greater) => wins += 1,
+            Some(std::cmp::Ordering::Equal) => ties += 1,
+            Some(std::cmp::Ordering::Less) => {}
+        }
+    }
+
+    let percent = |count: usize| count as f64 * 100.0 / state::EQUITY_SIMULATIONS as f64;
+    models::PlayerEquity {
+        win: percent(wins),
+        tie: percent(ties),
+        lose: percent(state::EQUITY_SIMULATIONS - wins - ties),
+    }
+}
+
+pub(crate) fn call_amount(state: &state::State) -> Option<u64> {
+    state.round.raises.last().map(|(_, last_stake)| *last_stake)
+}
+
+/// The smallest stake a player may raise to: the current bet plus the size of
+/// the last full raise, and never less than a big blind over. Preflop, the
+/// blinds count as a bet of one big blind even when posted short, and a
+/// straddle as a raise to two.
+pub(crate) fn min_raise_to(state: &state::State) -> u64 {
+    let big_blind = big_blind(state);
+    let preflop = state.round.cards_on_table.len() < 3;
+    let blinds = state.config.forced_bet() == state::config::ForcedBet::Blinds;
+    let opening_bet = if preflop && blinds { big_blind } else { 0 };
+    let opening_raise = match state.round.straddle {
+        Some(_) if preflop => big_blind * 2,
+        _ => big_blind,
+    };
+
+    let (current_bet, raise_size) = state.round.raises.iter().map(|(_, s)| *s).fold(
+        (opening_bet, opening_raise),
+        |(current_bet, raise_size), stake| match stake.checked_sub(current_bet) {
+            Some(raise) if raise > 0 => (stake, raise_size.max(raise)),
+            _ => (current_bet, raise_size),
+        },
+    );
+
+    current_bet + raise_size
+}
+
+/// The stake a raise by a fraction of the pot comes to: the call amount plus
+/// that share of the pot, bumped up to the minimum raise and capped at the
+/// player's all-in.
+pub(crate) fn pot_fraction_raise_to(
+    state: &state::State,
+    player_id: &state::PlayerId,
+    numerator: u64,
+    denominator: u64,
+) -> Result<u64, String> {
+    if denominator == 0 {
+        return Err("Pot fraction denominator must be positive".to_string());
+    }
+    let balance = state
+        .players
+        .get(player_id)
+        .map(|p| p.balance)
+        .ok_or("Player not found".to_string())?;
+    let all_in_to = player_stake_in_round(state, player_id) + balance;
+
+    let raise_by = pot_after_call(state, player_id)
+        .checked_mul(numerator)
+        .ok_or("Pot fraction is too large".to_string())?
+        / denominator;
+    let raise_to = call_amount(state).unwrap_or(0).saturating_add(raise_by);
+    Ok(raise_to.max(min_raise_to(state)).min(all_in_to))
+}
+
+/// The pot once the player has called, which a pot-sized raise is raised by.
+fn pot_after_call(state: &state::State, player_id: &state::PlayerId) -> u64 {
+    let to_call = call_amount(state)
+        .unwrap_or(0)
+        .saturating_sub(player_stake_in_round(state, player_id));
+    state.round.pot + to_call
+}
+
+/// The largest stake a player may raise to in the current round, or `None`
+/// when there is no limit. Under pot limit, a player may raise by the size of
+/// the pot after calling.
+pub(crate) fn max_raise_to(state: &state::State, player_id: &state::PlayerId) -> Option<u64> {
+    let call_amount = call_amount(state).unwrap_or(0);
+    match state.config.betting_limit() {
+        state::config::BettingLimit::NoLimit => None,
+        state::config::BettingLimit::PotLimit => {
+            Some(call_amount + pot_after_call(state, player_id))
+        }
+        state::config::BettingLimit::FixedLimit => Some(call_amount + big_blind(state)),
+    }
+}
+
+pub(crate) fn turn_expires_dt(state: &state::State, player_id: &state::PlayerId) -> Option<u64> {
+    state
+        .players
+        .get(player_id)
+        .and_then(|p| p.ttl.map(|dt| dt.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::tests::fixtures::GameFixture,
+        state::{BIG_BLIND, SMALL_BLIND, STARTING_BALANCE},
+    };
+    use state::BetAction as P;
+
+    #[test]
+    fn two_player_game_deals_correct_cards_to_table() {
+        let (state, _) = fixtures::start_two_player_game(GameFixture::Round1);
+        assert_eq!(cards_on_table(&state).len(), 0);
+
+        let (state, _) = fixtures::start_two_player_game(GameFixture::Round2);
+        assert_eq!(cards_on_table(&state).len(), 3);
+
+        let (state, _) = fixtures::start_two_player_game(GameFixture::Round3);
+        assert_eq!(cards_on_table(&state).len(), 4);
+
+        let (state, _) = fixtures::start_two_player_game(GameFixture::Round4);
+        assert_eq!(cards_on_table(&state).len(), 5);
+    }
+
+    #[test]
+    fn two_player_game_redeals_players_cards_after_round() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Complete);
+        let player_1_cards = cards_in_hand(&state, &player_1).unwrap();
+        let player_2_cards = cards_in_hand(&state, &player_2).unwrap();
+
+        state.config = Default::default();
+        start_game(&mut state).unwrap();
+        let new_player_1_cards = cards_in_hand(&state, &player_1).unwrap();
+        let new_player_2_cards = cards_in_hand(&state, &player_2).unwrap();
+
+        assert_ne!(
+            (player_1_cards, player_2_cards),
+            (new_player_1_cards, new_player_2_cards)
+        );
+    }
+
+    #[test]
+    fn game_pays_outright_winner_from_pot() {
+        let mut state = state::State::default();
+        state.config = state.config.with_card_deal_disabled();
+        state.round.deck = cards::Deck::ordered();
+
+        let state = &mut state;
+
+        let player_1 = fixtures::add_player(state, "player_1").unwrap();
+        let player_2 = fixtures::add_player(state, "player_2").unwrap();
+        let player_3 = fixtures::add_player(state, "player_3").unwrap();
+        let player_4 = fixtures::add_player(state, "player_4").unwrap();
+        let player_5 = fixtures::add_player(state, "player_5").unwrap();
+
+        assert_eq!(state.players.len(), 5);
+        assert_eq!(state.status, state::GameStatus::Joining);
+        let starting_balance = state.players.iter().map(|(_, p)| p.balance).next().unwrap();
+        assert_eq!(starting_balance, state::STARTING_BALANCE);
+
+        start_game(state).unwrap();
+
+        assert_eq!(cards_on_table(state).len(), 0);
+
+        accept_player_bet(state, &player_3, P::Call).expect("R1-P3");
+        accept_player_bet(state, &player_4, P::Call).expect("R1-P4");
+        accept_player_bet(state, &player_5, P::Call).expect("R1-P5");
+        accept_player_bet(state, &player_1, P::Call).expect("R1-P1");
+        accept_player_bet(state, &player_2, P::Check).expect("R1-P2");
+        assert_eq!(state.round.pot, 100);
+        assert_eq!(cards_on_table(state).len(), 3);
+
+        accept_player_bet(state, &player_1, P::RaiseTo(500)).expect("R2-P1");
+        assert_eq!(state.round.pot, 600);
+        accept_player_bet(state, &player_2, P::Call).expect("R2-P2");
+        assert_eq!(state.round.pot, 1100);
+        accept_player_bet(state, &player_3, P::Call).expect("R2-P3");
+        assert_eq!(state.round.pot, 1600);
+        fold_player(state, &player_4).expect("R2-P4");
+        fold_player(state, &player_5).expect("R2-P4");
+
+        assert_eq!(cards_on_table(state).len(), 4);
+
+        accept_player_bet(state, &player_1, P::Check).unwrap();
+        accept_player_bet(state, &player_2, P::Check).unwrap();
+        accept_player_bet(state, &player_3, P::Check).unwrap();
+
+        let pot_before_payout = state.round.pot;
+        let winner_balance_before_payout = state.players.get(&player_1).unwrap().balance;
+
+        assert_eq!(pot_before_payout, (BIG_BLIND * 5) + (500 * 3));
+        assert_eq!(cards_on_table(state).len(), 5);
+
+        accept_player_bet(state, &player_1, P::Check).unwrap();
+        accept_player_bet(state, &player_2, P::Check).unwrap();
+        accept_player_bet(state, &player_3, P::Check).unwrap();
 
         assert_eq!(cards_on_table(state).len(), 5);
         assert_eq!(state.status, state::GameStatus::Complete);
@@ -1323,7 +3223,7 @@ mod tests {
     #[test]
     fn two_player_game_can_transfer_funds() {
         let (mut state, (player_1, player_2)) =
-            fixtures::start_two_player_game(GameFixture::Round1);
+            fixtures::start_two_player_game(GameFixture::Complete);
         let player_1_balance = state.players.get(&player_1).unwrap().balance;
         let player_2_balance = state.players.get(&player_2).unwrap().balance;
 
@@ -1334,255 +3234,2302 @@ mod tests {
             amount: 100,
         };
 
-        transfer_funds(&mut state, &player_1, &transfer_request).unwrap();
-
-        let player_1_balance_after_transfer = state.players.get(&player_1).unwrap().balance;
-        let player_2_balance_after_transfer = state.players.get(&player_2).unwrap().balance;
+        transfer_funds(&mut state, &player_1, &transfer_request).unwrap();
+
+        let player_1_balance_after_transfer = state.players.get(&player_1).unwrap().balance;
+        let player_2_balance_after_transfer = state.players.get(&player_2).unwrap().balance;
+
+        assert_eq!(player_1_balance - 100, player_1_balance_after_transfer);
+        assert_eq!(player_2_balance + 100, player_2_balance_after_transfer);
+    }
+
+    #[test]
+    fn two_player_game_rejects_transfer_during_game() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round1);
+        let transfer_request = models::TransferRequest {
+            to: state
+                .players
+                .get(&player_2)
+                .unwrap()
+                .funds_token
+                .to_string(),
+            amount: 100,
+        };
+
+        let err = transfer_funds(&mut state, &player_1, &transfer_request).unwrap_err();
+        assert_eq!(err, "Cannot transfer funds during a game");
+    }
+
+    #[test]
+    fn two_player_game_rejects_transfer_over_limit() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Complete);
+        state.config = state.config.clone().with_transfer_limit(150);
+        let transfer_request = models::TransferRequest {
+            to: state
+                .players
+                .get(&player_2)
+                .unwrap()
+                .funds_token
+                .to_string(),
+            amount: 100,
+        };
+
+        transfer_funds(&mut state, &player_1, &transfer_request).unwrap();
+        let player_1_balance = state.players.get(&player_1).unwrap().balance;
+
+        let err = transfer_funds(&mut state, &player_1, &transfer_request).unwrap_err();
+        assert_eq!(
+            err,
+            "Transfer exceeds the limit of 150 per game (100 already transferred)"
+        );
+        assert_eq!(
+            state.players.get(&player_1).unwrap().balance,
+            player_1_balance
+        );
+
+        let transfer_request = models::TransferRequest {
+            amount: 50,
+            ..transfer_request
+        };
+        transfer_funds(&mut state, &player_1, &transfer_request).unwrap();
+
+        let huge_request = models::TransferRequest {
+            to: transfer_request.to.clone(),
+            amount: u64::MAX,
+        };
+        transfer_funds(&mut state, &player_1, &huge_request).unwrap_err();
+
+        // the cap carries over between hands until a rematch starts a new game
+        start_game(&mut state).unwrap();
+        fixtures::progress_two_player_game(&mut state, GameFixture::Complete);
+        transfer_funds(&mut state, &player_1, &transfer_request).unwrap_err();
+
+        rematch(&mut state).unwrap();
+        fixtures::progress_two_player_game(&mut state, GameFixture::Complete);
+        transfer_funds(&mut state, &player_1, &transfer_request).unwrap();
+    }
+
+    #[test]
+    fn two_player_game_rejects_transfer_to_self() {
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Complete);
+        let player_1_balance = state.players.get(&player_1).unwrap().balance;
+        let transfer_request = models::TransferRequest {
+            to: state
+                .players
+                .get(&player_1)
+                .unwrap()
+                .funds_token
+                .to_string(),
+            amount: 100,
+        };
+
+        let err = transfer_funds(&mut state, &player_1, &transfer_request).unwrap_err();
+        assert_eq!(err, "Cannot transfer funds to yourself");
+        assert_eq!(
+            state.players.get(&player_1).unwrap().balance,
+            player_1_balance
+        );
+    }
+
+    #[test]
+    fn ticker_formats_amounts_with_room_currency() {
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Round1);
+        state.config = state.config.clone().with_currency("$");
+
+        let event = TickerEvent::PlayerBet(player_1, P::RaiseTo(40));
+        assert!(event.format(&state).ends_with("raised to $40"));
+    }
+
+    #[test]
+    fn ticker_items_match_legacy_ticker_string() {
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Round1);
+        state.config = state.config.clone().with_ticker_enabled();
+        state.ticker.emit(TickerEvent::PlayerBet(player_1, P::Call));
+
+        let ticker = ticker(&state).unwrap();
+        let items = ticker_items(&state);
+        let lines: Vec<_> = ticker.lines().skip(1).collect();
+        assert!(!items.is_empty());
+        assert_eq!(lines.len(), items.len());
+        for (line, item) in lines.iter().zip(&items) {
+            let (meta, text) = line.split_once('\x00').unwrap();
+            let meta: Vec<_> = meta.split('|').collect();
+            assert_eq!(meta[0], item.seq.to_string());
+            assert_eq!(meta[2], item.duration_ms.to_string());
+            assert_eq!(text, item.text);
+        }
+    }
+
+    #[test]
+    fn ticker_returns_most_recent_items_with_full_count() {
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Round1);
+        state.config = state.config.clone().with_ticker_enabled();
+        for _ in 0..state::TICKER_MAX_ITEMS * 2 {
+            state
+                .ticker
+                .emit(TickerEvent::PlayerBet(player_1.clone(), P::Call));
+        }
+        let total = state.ticker.len();
+        assert!(total > state::TICKER_MAX_ITEMS);
+
+        let ticker = ticker(&state).unwrap();
+        let mut lines = ticker.lines();
+        let header = lines.next().unwrap();
+        assert!(header.ends_with(&format!("\x00{}\x00", total)));
+        let items: Vec<_> = lines.collect();
+        assert_eq!(items.len(), state::TICKER_MAX_ITEMS);
+
+        let last_seq = state.ticker.iter().last().unwrap().seq_index;
+        let (meta, _) = items.last().unwrap().split_once('\x00').unwrap();
+        assert!(meta.starts_with(&format!("{}|", last_seq)));
+    }
+
+    #[test]
+    fn min_raise_to_follows_last_full_raise() {
+        let (mut state, _) = fixtures::start_two_player_game(GameFixture::Round1);
+        assert_eq!(min_raise_to(&state), BIG_BLIND * 2);
+
+        let player = state.round.players_turn.clone().unwrap();
+        accept_player_bet(&mut state, &player, P::RaiseTo(BIG_BLIND * 3)).unwrap();
+        assert_eq!(min_raise_to(&state), BIG_BLIND * 5);
+
+        let player = state.round.players_turn.clone().unwrap();
+        accept_player_bet(&mut state, &player, P::RaiseTo(BIG_BLIND * 6)).unwrap();
+        assert_eq!(min_raise_to(&state), BIG_BLIND * 9);
+
+        let player = state.round.players_turn.clone().unwrap();
+        accept_player_bet(&mut state, &player, P::Call).unwrap();
+        assert_eq!(cards_on_table(&state).len(), 3);
+        assert_eq!(min_raise_to(&state), BIG_BLIND);
+    }
+
+    #[test]
+    fn min_raise_to_ignores_short_big_blind() {
+        let mut state = state::State::default();
+        let player_1 = fixtures::add_player(&mut state, "player_1").unwrap();
+        let player_2 = fixtures::add_player(&mut state, "player_2").unwrap();
+        state.players.get_mut(&player_1).unwrap().balance = SMALL_BLIND + 5;
+        state.players.get_mut(&player_2).unwrap().balance = SMALL_BLIND + 5;
+
+        start_game(&mut state).unwrap();
+        assert_eq!(call_amount(&state), Some(SMALL_BLIND + 5));
+        assert_eq!(min_raise_to(&state), BIG_BLIND * 2);
+    }
+
+    #[test]
+    fn pot_fraction_raise_to_bumps_up_to_minimum() {
+        let (mut state, _) = fixtures::start_two_player_game(GameFixture::Round1);
+        let player_id = state.round.players_turn.clone().unwrap();
+        assert_eq!(state.round.pot, SMALL_BLIND + BIG_BLIND);
+
+        // the small blind calls for another small blind, then raises by the pot
+        let pot_raise = pot_fraction_raise_to(&state, &player_id, 1, 1).unwrap();
+        assert_eq!(pot_raise, BIG_BLIND + SMALL_BLIND + BIG_BLIND + SMALL_BLIND);
+        state.config = state
+            .config
+            .clone()
+            .with_betting_limit(state::config::BettingLimit::PotLimit);
+        assert_eq!(max_raise_to(&state, &player_id), Some(pot_raise));
+
+        let quarter_pot_raise = pot_fraction_raise_to(&state, &player_id, 1, 4).unwrap();
+        assert!(BIG_BLIND + (SMALL_BLIND + BIG_BLIND + SMALL_BLIND) / 4 < min_raise_to(&state));
+        assert_eq!(quarter_pot_raise, min_raise_to(&state));
+
+        assert!(pot_fraction_raise_to(&state, &player_id, 1, 0).is_err());
+        assert!(pot_fraction_raise_to(&state, &player_id, u64::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn three_player_game_keeps_dealer_when_rotation_disabled() {
+        let mut state = state::State::default();
+        state.config = state.config.clone().with_dealer_rotation_disabled();
+        fixtures::add_player(&mut state, "player_1").unwrap();
+        fixtures::add_player(&mut state, "player_2").unwrap();
+        fixtures::add_player(&mut state, "player_3").unwrap();
+
+        start_game(&mut state).unwrap();
+        let dealer = state.players.keys().next().cloned();
+        while state.status == state::GameStatus::Playing {
+            let player_id = state.round.players_turn.clone().unwrap();
+            fold_player(&mut state, &player_id).unwrap();
+        }
+
+        start_game(&mut state).unwrap();
+        assert_eq!(state.players.keys().next().cloned(), dealer);
+    }
+
+    #[test]
+    fn two_player_game_reraising_minimum_works() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round4);
+        assert_eq!(state.round.pot, 40);
+        accept_player_bet(&mut state, &player_2, P::RaiseTo(BIG_BLIND)).unwrap();
+        assert_eq!(state.status, state::GameStatus::Playing);
+        assert_eq!(state.round.pot, 60);
+        assert_eq!(state.players.get(&player_2).unwrap().stake, BIG_BLIND * 2);
+        assert_eq!(state.players.get(&player_1).unwrap().stake, BIG_BLIND);
+
+        accept_player_bet(&mut state, &player_1, P::RaiseTo(BIG_BLIND * 2)).unwrap();
+        assert_eq!(state.round.pot, 100);
+        assert_eq!(state.players.get(&player_2).unwrap().stake, BIG_BLIND * 2);
+        assert_eq!(state.players.get(&player_1).unwrap().stake, BIG_BLIND * 3);
+
+        accept_player_bet(&mut state, &player_2, P::RaiseTo(BIG_BLIND * 3)).unwrap();
+        assert_eq!(state.players.get(&player_2).unwrap().stake, BIG_BLIND * 4);
+        assert_eq!(state.players.get(&player_1).unwrap().stake, BIG_BLIND * 3);
+
+        assert_eq!(state.status, state::GameStatus::Playing);
+
+        assert_eq!(state.round.pot, 140);
+
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        assert_eq!(state.status, state::GameStatus::Complete);
+    }
+
+    #[test]
+    fn three_player_game_fold_on_small_blind() {
+        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
+        assert_eq!(cards_on_table(&state).len(), 0);
+        assert_eq!(state.round.pot, 30);
+
+        fold_player(&mut state, &player_3).expect("R2-P3");
+        fold_player(&mut state, &player_1).expect("R2-P1");
+        assert_eq!(state.status, state::GameStatus::Complete);
+
+        let winner = state.players.get(&player_2).unwrap();
+        assert_eq!(winner.balance, STARTING_BALANCE + SMALL_BLIND);
+
+        let completed = completed_game(&state).unwrap();
+        assert_eq!(completed.winning_hand, None);
+    }
+
+    #[test]
+    fn three_player_game_walk_pays_big_blind_without_showdown() {
+        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
+
+        fold_player(&mut state, &player_3).unwrap();
+        fold_player(&mut state, &player_1).unwrap();
+        assert_eq!(state.status, state::GameStatus::Complete);
+        assert!(state.round.cards_on_table.is_empty());
+
+        let completed = state.round.completed.as_ref().unwrap();
+        assert!(completed.board.is_empty());
+        assert!(completed.showdown.is_empty());
+        assert_eq!(completed.winners.len(), 1);
+        assert_eq!(completed.winners[0].player_id, player_2);
+        assert_eq!(completed.winners[0].winnings, SMALL_BLIND + BIG_BLIND);
+        assert!(completed.winners[0].hand.is_none());
+
+        let completed = completed_game(&state).unwrap();
+        assert_eq!(completed.winning_hand, None);
+    }
+
+    #[test]
+    fn three_player_game_force_advance_recovers_missing_turn() {
+        let (mut state, _) = fixtures::start_three_player_game();
+        let now = state::dt::Instant::default();
+        force_advance(&mut state, now).unwrap_err();
+
+        state.round.players_turn = Some(state::PlayerId::default());
+        force_advance(&mut state, now).unwrap();
+
+        let player_id = state.round.players_turn.clone().unwrap();
+        assert!(is_playable(&state, &player_id));
+        accept_player_bet(&mut state, &player_id, P::Call).unwrap();
+    }
+
+    #[test]
+    fn three_player_game_check_until_river_then_raise_on_last_player() {
+        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
+        assert_eq!(cards_on_table(&state).len(), 0);
+        assert_eq!(state.round.pot, 30);
+
+        accept_player_bet(&mut state, &player_3, P::Call).unwrap();
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+
+        assert_eq!(cards_on_table(&state).len(), 3);
+        assert_eq!(state.round.pot, 60);
+
+        accept_player_bet(&mut state, &player_1, P::Check).unwrap();
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+        accept_player_bet(&mut state, &player_3, P::Check).unwrap();
+
+        assert_eq!(cards_on_table(&state).len(), 4);
+        assert_eq!(state.round.pot, 60);
+
+        accept_player_bet(&mut state, &player_1, P::Check).unwrap();
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+        accept_player_bet(&mut state, &player_3, P::Check).unwrap();
+
+        assert_eq!(cards_on_table(&state).len(), 5);
+        assert_eq!(state.round.pot, 60);
+
+        accept_player_bet(&mut state, &player_1, P::RaiseTo(100)).unwrap();
+        assert_eq!(player_stake_in_round(&state, &player_1), 100);
+
+        accept_player_bet(&mut state, &player_2, P::Call).unwrap();
+        assert_eq!(player_stake_in_round(&state, &player_2), 100);
+
+        accept_player_bet(&mut state, &player_3, P::RaiseTo(200)).unwrap();
+        assert_eq!(player_stake_in_round(&state, &player_3), 200);
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        assert_eq!(player_stake_in_round(&state, &player_1), 200);
+
+        accept_player_bet(&mut state, &player_2, P::Call).unwrap();
+        assert_eq!(state.status, state::GameStatus::Complete);
+    }
+
+    #[test]
+    fn three_player_game_queued_check_fold_checks_on_the_flop() {
+        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
+
+        accept_player_bet(&mut state, &player_3, P::Call).unwrap();
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        queue_player_action(&mut state, &player_1, state::QueuedAction::CheckFold).unwrap();
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+
+        assert_eq!(cards_on_table(&state).len(), 3);
+        assert!(!state.players.get(&player_1).unwrap().folded);
+        assert_eq!(state.round.players_turn, Some(player_2.clone()));
+
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+        assert_eq!(state.round.players_turn, Some(player_3.clone()));
+        accept_player_bet(&mut state, &player_3, P::Check).unwrap();
+
+        assert_eq!(cards_on_table(&state).len(), 4);
+        assert_eq!(state.round.players_turn, Some(player_1.clone()));
+    }
+
+    #[test]
+    fn two_player_game_ends_in_big_win_next_game_accepts_call_to_all_in() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round4);
+
+        assert_eq!(cards_on_table(&state).len(), 5);
+
+        // game 1, round 4
+        accept_player_bet(&mut state, &player_2, P::RaiseTo(500)).unwrap();
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        assert_eq!(state.status, state::GameStatus::Complete);
+
+        let player_1_balance = {
+            let loser = state.players.get(&player_1).unwrap();
+            let winner = state.players.get(&player_2).unwrap();
+            assert!(winner.balance > loser.balance);
+            loser.balance
+        };
+
+        // game 2, round 1
+        start_game(&mut state).unwrap();
+        accept_player_bet(&mut state, &player_2, P::RaiseTo(player_1_balance)).unwrap();
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+    }
+
+    #[test]
+    fn two_player_game_ends_in_big_win_next_game_accepts_call_to_above_all_in() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round4);
+
+        assert_eq!(cards_on_table(&state).len(), 5);
+
+        // game 1, round 4
+        accept_player_bet(&mut state, &player_2, P::RaiseTo(500)).unwrap();
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        assert_eq!(state.status, state::GameStatus::Complete);
+
+        let player_1_balance = {
+            let loser = state.players.get(&player_1).unwrap();
+            let winner = state.players.get(&player_2).unwrap();
+            assert!(winner.balance > loser.balance);
+            loser.balance
+        };
+
+        // game 2, round 1
+        start_game(&mut state).unwrap();
+        fixtures::deal_biased_deck(&mut state, &player_1, &player_2, true);
+        assert_eq!(state.status, state::GameStatus::Playing);
+
+        accept_player_bet(&mut state, &player_2, P::RaiseTo(player_1_balance + 100)).unwrap();
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+
+        assert_eq!(cards_on_table(&state).len(), 5);
+        assert_eq!(state.status, state::GameStatus::Complete);
+
+        let loser = state.players.get(&player_1).unwrap();
+        let winner = state.players.get(&player_2).unwrap();
+        assert!(winner.balance > loser.balance);
+        assert_eq!(loser.balance, 0);
+    }
+
+    #[test]
+    fn two_player_game_raising_round_one() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round1);
+
+        assert_eq!(cards_on_table(&state).len(), 0);
+
+        accept_player_bet(&mut state, &player_1, P::RaiseTo(BIG_BLIND * 2)).unwrap();
+
+        accept_player_bet(&mut state, &player_2, P::RaiseTo(BIG_BLIND * 3)).unwrap();
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+
+        assert_eq!(cards_on_table(&state).len(), 3);
+    }
+
+    #[test]
+    fn two_player_game_raising_with_intermittent_calls_checking_balances() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round1);
+
+        assert_eq!(cards_on_table(&state).len(), 0);
+        assert_eq!(
+            state.players.get(&player_1).unwrap().balance,
+            STARTING_BALANCE - SMALL_BLIND
+        );
+        assert_eq!(
+            state.players.get(&player_2).unwrap().balance,
+            STARTING_BALANCE - BIG_BLIND
+        );
+
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        assert_eq!(
+            state.players.get(&player_1).unwrap().balance,
+            STARTING_BALANCE - BIG_BLIND
+        );
+        accept_player_bet(&mut state, &player_2, P::RaiseTo(BIG_BLIND * 2)).unwrap();
+        assert_eq!(
+            state.players.get(&player_2).unwrap().balance,
+            STARTING_BALANCE - 40
+        );
+    }
+
+    #[test]
+    fn three_player_game_folded_players_dont_have_turns_in_further_rounds() {
+        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
+        assert_eq!(cards_on_table(&state).len(), 0);
+
+        accept_player_bet(&mut state, &player_3, P::Call).unwrap();
+        fold_player(&mut state, &player_1).expect("R2-P1");
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+
+        assert_eq!(cards_on_table(&state).len(), 3);
+
+        // ensure player 1 does not take a turn given they have folded
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+    }
+
+    #[test]
+    fn three_player_game_raise_someone_over_all_in_completes() {
+        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
+        let player_1 = state.players.get_mut(&player_1).unwrap();
+        player_1.balance = 100;
+
+        let player_1 = player_1.id.clone();
+
+        assert_eq!(cards_on_table(&state).len(), 0);
+
+        accept_player_bet(&mut state, &player_3, P::Call).unwrap();
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+
+        assert_eq!(cards_on_table(&state).len(), 3);
+
+        accept_player_bet(&mut state, &player_1, P::Check).unwrap();
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+        accept_player_bet(&mut state, &player_3, P::Check).unwrap();
+
+        assert_eq!(cards_on_table(&state).len(), 4);
+
+        accept_player_bet(&mut state, &player_1, P::Check).unwrap();
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+        accept_player_bet(&mut state, &player_3, P::Check).unwrap();
+
+        assert_eq!(cards_on_table(&state).len(), 5);
+
+        accept_player_bet(&mut state, &player_1, P::Check).unwrap();
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+        accept_player_bet(&mut state, &player_3, P::RaiseTo(200)).unwrap();
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        accept_player_bet(&mut state, &player_2, P::Call).unwrap();
+
+        assert_eq!(state.status, state::GameStatus::Complete);
+    }
+
+    #[test]
+    fn two_player_game_uses_configured_blinds() {
+        let mut state = state::State::default();
+        state.config = state.config.with_small_blind(25);
+
+        let player_1 = fixtures::add_player(&mut state, "player_1").unwrap();
+        let player_2 = fixtures::add_player(&mut state, "player_2").unwrap();
+
+        start_game(&mut state).unwrap();
+
+        assert_eq!(state.round.pot, 75);
+        assert_eq!(state.players.get(&player_1).unwrap().stake, 25);
+        assert_eq!(state.players.get(&player_2).unwrap().stake, 50);
+        assert_eq!(min_raise_to(&state), 100);
+
+        let active_player = state.round.players_turn.clone().unwrap();
+        accept_player_bet(&mut state, &active_player, P::RaiseTo(99)).unwrap_err();
+        accept_player_bet(&mut state, &active_player, P::RaiseTo(100)).unwrap();
+    }
+
+    #[test]
+    fn two_player_game_uses_configured_turn_timeout() {
+        let mut state = state::State::default();
+        state.config = state.config.with_card_deal_disabled().with_turn_timeout(15);
+        fixtures::add_player(&mut state, "player_1").unwrap();
+        fixtures::add_player(&mut state, "player_2").unwrap();
+        start_game(&mut state).unwrap();
+
+        let now = state::dt::Instant::default().as_u64();
+        let player_id = state.round.players_turn.clone().unwrap();
+        let ttl = state.players.get(&player_id).unwrap().ttl.unwrap().as_u64();
+        assert!(ttl > now + 14_000);
+        assert!(ttl <= now + 15_000);
+
+        assert_eq!(
+            state::config::RoomConfig::default()
+                .with_turn_timeout(1)
+                .turn_timeout_seconds(),
+            state::MIN_PLAYER_TURN_TIMEOUT_SECONDS
+        );
+    }
+
+    #[test]
+    fn two_player_game_pot_limit_rejects_raise_over_the_pot() {
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Round1);
+        state.config = state
+            .config
+            .with_betting_limit(state::config::BettingLimit::PotLimit);
+
+        // pot of 30 plus 10 to call, raising to 20 + 40
+        let max_raise = max_raise_to(&state, &player_1).unwrap();
+        assert_eq!(max_raise, 60);
+
+        accept_player_bet(&mut state, &player_1, P::RaiseTo(max_raise + 1)).unwrap_err();
+        accept_player_bet(&mut state, &player_1, P::AllIn).unwrap_err();
+        accept_player_bet(&mut state, &player_1, P::RaiseTo(max_raise)).unwrap();
+        assert_eq!(state.round.pot, 30 + 50);
+    }
+
+    #[test]
+    fn two_player_game_fixed_limit_raises_by_big_blind() {
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Round1);
+        state.config = state
+            .config
+            .with_betting_limit(state::config::BettingLimit::FixedLimit);
+
+        accept_player_bet(&mut state, &player_1, P::RaiseTo(BIG_BLIND * 3)).unwrap_err();
+        accept_player_bet(&mut state, &player_1, P::RaiseTo(BIG_BLIND * 2)).unwrap();
+    }
+
+    #[test]
+    fn three_player_game_collects_ante_from_every_player() {
+        let mut state = state::State::default();
+        state.config = state.config.with_card_deal_disabled().with_ante(5);
+        let player_1 = fixtures::add_player(&mut state, "player_1").unwrap();
+        let player_2 = fixtures::add_player(&mut state, "player_2").unwrap();
+        let player_3 = fixtures::add_player(&mut state, "player_3").unwrap();
+        start_game(&mut state).unwrap();
+
+        assert_eq!(state.round.pot, 15 + SMALL_BLIND + BIG_BLIND);
+        assert_eq!(
+            state.players.get(&player_3).unwrap().balance,
+            STARTING_BALANCE - 5
+        );
+
+        accept_player_bet(&mut state, &player_3, P::Call).unwrap();
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+        assert_eq!(cards_on_table(&state).len(), 3);
+        assert_eq!(state.round.pot, 15 + BIG_BLIND * 3);
+    }
+
+    #[test]
+    fn game_rejects_players_over_configured_max_players() {
+        let mut state = state::State::default();
+        state.config = state.config.with_max_players(3);
+
+        fixtures::add_player(&mut state, "player_1").unwrap();
+        fixtures::add_player(&mut state, "player_2").unwrap();
+        fixtures::add_player(&mut state, "player_3").unwrap();
+
+        let err = fixtures::add_player(&mut state, "player_4").unwrap_err();
+        assert_eq!(err, "Room is full");
+        assert_eq!(state.players.len(), 3);
+    }
+
+    #[test]
+    fn three_player_game_all_in_with_unequal_stacks_builds_side_pots() {
+        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
+        state.players.get_mut(&player_1).unwrap().balance = 91;
+        state.players.get_mut(&player_2).unwrap().balance = 281;
+
+        accept_player_bet(&mut state, &player_3, P::AllIn).unwrap();
+        accept_player_bet(&mut state, &player_1, P::AllIn).unwrap();
+        assert!(state.players.get(&player_1).unwrap().all_in);
+        accept_player_bet(&mut state, &player_2, P::AllIn).unwrap();
+
+        assert_eq!(cards_on_table(&state).len(), 5);
+        assert_eq!(state.status, state::GameStatus::Complete);
+
+        let pots = side_pots(&state);
+        assert_eq!(
+            pots,
+            vec![
+                (
+                    303,
+                    vec![player_1.clone(), player_2.clone(), player_3.clone()]
+                ),
+                (400, vec![player_2.clone(), player_3.clone()]),
+                (699, vec![player_3.clone()]),
+            ]
+        );
+
+        let total_balance: u64 = state.players.values().map(|p| p.balance).sum();
+        assert_eq!(total_balance, 101 + 301 + 1000);
+        assert_eq!(state.round.pot, 0);
+    }
+
+    #[test]
+    fn six_player_showdown_scores_each_hand_once() {
+        let mut state = state::State::default();
+        state.config = state.config.with_card_deal_disabled();
+        state.round.deck = cards::Deck::ordered();
+        for i in 1..=6 {
+            let player_id = fixtures::add_player(&mut state, &format!("player_{}", i)).unwrap();
+            state.players.get_mut(&player_id).unwrap().balance = 100 * i;
+        }
+        start_game(&mut state).unwrap();
+
+        cards::EVALUATIONS.with(|evaluations| evaluations.set(0));
+        while let Some(player_id) = state.round.players_turn.clone() {
+            accept_player_bet(&mut state, &player_id, P::AllIn).unwrap();
+        }
+
+        assert_eq!(state.status, state::GameStatus::Complete);
+        assert!(state.round.completed.as_ref().unwrap().winners.len() >= 1);
+        assert_eq!(cards::EVALUATIONS.with(|evaluations| evaluations.get()), 6);
+    }
+
+    #[test]
+    fn two_player_game_reports_each_hand_result() {
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct CapturingSink(Arc<Mutex<Vec<state::results::HandResult>>>);
+
+        impl state::results::HandResultSink for CapturingSink {
+            fn record(&self, result: state::results::HandResult) {
+                self.0.lock().unwrap().push(result);
+            }
+        }
+
+        let (mut state, _) = fixtures::start_two_player_game(GameFixture::Round1);
+        let sink = CapturingSink::default();
+        state.hand_results = state::results::HandResults::new(sink.clone());
+
+        fixtures::progress_two_player_game(&mut state, GameFixture::Complete);
+        start_game(&mut state).unwrap();
+        let folding_player = state.round.players_turn.clone().unwrap();
+        fold_player(&mut state, &folding_player).unwrap();
+
+        let results = sink.0.lock().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].pot, 40);
+        assert_eq!(results[0].board.len(), 5);
+        assert_eq!(results[0].hands.len(), 2);
+        assert_eq!(results[1].pot, 30);
+        assert_eq!(results[1].winners.len(), 1);
+        assert_ne!(results[1].winners[0].player_id, folding_player);
+    }
+
+    #[test]
+    fn omaha_game_deals_four_hole_cards() {
+        let mut state = state::State::default();
+        state.config = state.config.clone().with_variant(cards::Variant::Omaha);
+        fixtures::add_player(&mut state, "player_1").unwrap();
+        fixtures::add_player(&mut state, "player_2").unwrap();
+        start_game(&mut state).unwrap();
+
+        for player in state.players.values() {
+            assert_eq!(player.cards.len(), 4);
+        }
+        assert_eq!(state.round.deck.remaining().count(), 52 - 8);
+    }
+
+    #[test]
+    fn short_deck_game_flush_beats_full_house_at_showdown() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round1);
+        state.config = state.config.clone().with_variant(cards::Variant::ShortDeck);
+        let card = |suite, value| cards::Card { suite, value };
+        state.players.get_mut(&player_1).unwrap().cards = vec![
+            card(cards::CardSuite::Hearts, cards::CardValue::Ace),
+            card(cards::CardSuite::Hearts, cards::CardValue::Seven),
+        ];
+        state.players.get_mut(&player_2).unwrap().cards = vec![
+            card(cards::CardSuite::Clubs, cards::CardValue::King),
+            card(cards::CardSuite::Spades, cards::CardValue::King),
+        ];
+        state.round.cards_on_table = vec![
+            card(cards::CardSuite::Hearts, cards::CardValue::King),
+            card(cards::CardSuite::Hearts, cards::CardValue::Queen),
+            card(cards::CardSuite::Hearts, cards::CardValue::Nine),
+            card(cards::CardSuite::Clubs, cards::CardValue::Nine),
+            card(cards::CardSuite::Diamonds, cards::CardValue::Six),
+        ];
+
+        let first_player = state.round.players_turn.clone().unwrap();
+        let second_player = match first_player == player_1 {
+            true => player_2.clone(),
+            false => player_1.clone(),
+        };
+        accept_player_bet(&mut state, &first_player, P::AllIn).unwrap();
+        accept_player_bet(&mut state, &second_player, P::Call).unwrap();
+        assert_eq!(state.status, state::GameStatus::Complete);
+
+        let completed = state.round.completed.as_ref().unwrap();
+        assert_eq!(completed.winners.len(), 1);
+        assert_eq!(completed.winners[0].player_id, player_1);
+        assert_eq!(completed.winners[0].hand, Some(cards::HandStrength::Flush));
+        assert_eq!(completed.winners[0].winnings, STARTING_BALANCE * 2);
+        assert_eq!(
+            completed
+                .best_hand
+                .as_ref()
+                .map(|(_, hand)| hand.strength()),
+            Some(cards::HandStrength::Flush)
+        );
+    }
+
+    #[test]
+    fn two_player_game_made_flush_has_high_equity() {
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Round1);
+        let card = |suite, value| cards::Card { suite, value };
+        state.players.get_mut(&player_1).unwrap().cards = vec![
+            card(cards::CardSuite::Hearts, cards::CardValue::Ace),
+            card(cards::CardSuite::Hearts, cards::CardValue::Nine),
+        ];
+        state.round.cards_on_table = vec![
+            card(cards::CardSuite::Hearts, cards::CardValue::Two),
+            card(cards::CardSuite::Hearts, cards::CardValue::Seven),
+            card(cards::CardSuite::Hearts, cards::CardValue::King),
+            card(cards::CardSuite::Clubs, cards::CardValue::Three),
+            card(cards::CardSuite::Diamonds, cards::CardValue::Eight),
+        ];
+
+        let equity = simulate_equity(&equity_hand(&state, &player_1).unwrap());
+        assert!(equity.win > 90.0);
+        assert!(equity.lose < 10.0);
+        assert_eq!(equity.win + equity.tie + equity.lose, 100.0);
+    }
+
+    #[test]
+    fn short_deck_game_flush_has_high_equity_against_full_houses() {
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Round1);
+        let card = |suite, value| cards::Card { suite, value };
+        state.players.get_mut(&player_1).unwrap().cards = vec![
+            card(cards::CardSuite::Hearts, cards::CardValue::Ace),
+            card(cards::CardSuite::Hearts, cards::CardValue::Seven),
+        ];
+        // most opponent hands pair up with the trips for a full house
+        state.round.cards_on_table = vec![
+            card(cards::CardSuite::Hearts, cards::CardValue::King),
+            card(cards::CardSuite::Hearts, cards::CardValue::Queen),
+            card(cards::CardSuite::Hearts, cards::CardValue::Nine),
+            card(cards::CardSuite::Clubs, cards::CardValue::Nine),
+            card(cards::CardSuite::Diamonds, cards::CardValue::Nine),
+        ];
+
+        let equity = simulate_equity(&equity_hand(&state, &player_1).unwrap());
+        assert!(equity.win < 75.0);
+
+        state.config = state.config.clone().with_variant(cards::Variant::ShortDeck);
+        let equity = simulate_equity(&equity_hand(&state, &player_1).unwrap());
+        assert!(equity.win > 85.0);
+        assert_eq!(equity.win + equity.tie + equity.lose, 100.0);
+    }
+
+    #[test]
+    fn two_player_game_all_in_runs_it_twice() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round1);
+        state.config = state.config.clone().with_run_it_twice();
+        let card = |suite, value| cards::Card { suite, value };
+        // player_1 makes a straight flush on the first run, player_2 a royal flush on the second
+        state.players.get_mut(&player_1).unwrap().cards = vec![
+            card(cards::CardSuite::Spades, cards::CardValue::Six),
+            card(cards::CardSuite::Hearts, cards::CardValue::Seven),
+        ];
+        state.players.get_mut(&player_2).unwrap().cards = vec![
+            card(cards::CardSuite::Clubs, cards::CardValue::Ace),
+            card(cards::CardSuite::Diamonds, cards::CardValue::Seven),
+        ];
+        // leaves 5♠ 4♠ 3♠ 2♠ A♣ for the first run and K♣ Q♣ J♣ 10♣ 9♣ for the second
+        for _ in 0..9 {
+            state.round.deck.pop();
+        }
+
+        let first_player = state.round.players_turn.clone().unwrap();
+        let second_player = match first_player == player_1 {
+            true => player_2.clone(),
+            false => player_1.clone(),
+        };
+        accept_player_bet(&mut state, &first_player, P::AllIn).unwrap();
+        accept_player_bet(&mut state, &second_player, P::Call).unwrap();
+        assert_eq!(state.status, state::GameStatus::Complete);
+
+        let winners = &state.round.completed.as_ref().unwrap().winners;
+        assert_eq!(winners.len(), 2);
+        assert_eq!(winners[0].player_id, player_1);
+        assert_eq!(winners[0].hand, Some(cards::HandStrength::StraightFlush));
+        assert_eq!(winners[1].player_id, player_2);
+        assert_eq!(winners[1].hand, Some(cards::HandStrength::RoyalFlush));
+        assert_eq!(winners[0].winnings, STARTING_BALANCE);
+        assert_eq!(winners[1].winnings, STARTING_BALANCE);
+
+        assert_eq!(
+            state.players.get(&player_1).unwrap().balance,
+            STARTING_BALANCE
+        );
+        assert_eq!(
+            state.players.get(&player_2).unwrap().balance,
+            STARTING_BALANCE
+        );
+        assert_eq!(state.round.pot, 0);
+
+        let second_board = completed_game(&state).unwrap().second_board.unwrap();
+        assert_eq!(
+            second_board[0],
+            (cards::CardSuite::Clubs, cards::CardValue::King)
+        );
+        let history = hand_history(&state).unwrap();
+        assert_eq!(history.board[0].1, cards::CardValue::Five);
+        assert_eq!(history.second_board, Some(second_board));
+
+        let runouts: Vec<_> = state
+            .ticker
+            .log()
+            .filter_map(|(_, event)| match event {
+                TickerEvent::RunoutDealt(run, count) => Some((*run, *count)),
+                TickerEvent::CardsDealtToTable(_) => panic!("runout dealt as a street"),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(runouts, vec![(1, 5), (2, 5)]);
+    }
+
+    #[test]
+    fn three_player_game_short_all_in_raise_does_not_reopen_betting() {
+        let (mut state, _) = fixtures::start_three_player_game();
+
+        let raiser = state.round.players_turn.clone().unwrap();
+        accept_player_bet(&mut state, &raiser, P::RaiseTo(100)).unwrap();
+        assert_eq!(min_raise_to(&state), 180);
+
+        // the next player can only go all-in for 150, short of a full raise
+        let short_stack = state.round.players_turn.clone().unwrap();
+        let stake = player_stake_in_round(&state, &short_stack);
+        state.players.get_mut(&short_stack).unwrap().balance = 150 - stake;
+        accept_player_bet(&mut state, &short_stack, P::RaiseTo(140)).unwrap_err();
+        accept_player_bet(&mut state, &short_stack, P::RaiseTo(150)).unwrap();
+        assert!(state.players.get(&short_stack).unwrap().all_in);
+        assert_eq!(call_amount(&state), Some(150));
+
+        // a player yet to act may still raise, but calls here
+        let last_player = state.round.players_turn.clone().unwrap();
+        assert!(legal_actions(&state, &last_player).unwrap().raise);
+        accept_player_bet(&mut state, &last_player, P::Call).unwrap();
+
+        // the original raiser already acted, so can only call or fold
+        assert_eq!(state.round.players_turn, Some(raiser.clone()));
+        let actions = legal_actions(&state, &raiser).unwrap();
+        assert!(!actions.raise);
+        assert!(actions.call);
+        let err = accept_player_bet(&mut state, &raiser, P::RaiseTo(400)).unwrap_err();
+        assert_eq!(err, "Cannot re-raise a short all-in, call or fold instead");
+        accept_player_bet(&mut state, &raiser, P::AllIn).unwrap_err();
+        accept_player_bet(&mut state, &raiser, P::Call).unwrap();
+        assert_eq!(cards_on_table(&state).len(), 3);
+    }
+
+    #[test]
+    fn four_player_game_calls_after_short_all_in_do_not_reopen_betting() {
+        let mut state = state::State::default();
+        state.config = state.config.clone().with_card_deal_disabled();
+        for name in ["player_1", "player_2", "player_3", "player_4"] {
+            fixtures::add_player(&mut state, name).unwrap();
+        }
+        let order: Vec<_> = state.players.keys().cloned().collect();
+        state.players.get_mut(&order[0]).unwrap().balance = 150;
+        start_game(&mut state).unwrap();
+
+        accept_player_bet(&mut state, &order[2], P::RaiseTo(100)).unwrap();
+        accept_player_bet(&mut state, &order[3], P::Call).unwrap();
+        // the small blind is all-in for 150, short of the minimum raise to 180
+        accept_player_bet(&mut state, &order[0], P::AllIn).unwrap();
+        assert_eq!(call_amount(&state), Some(150));
+        accept_player_bet(&mut state, &order[1], P::Call).unwrap();
+
+        // both players who acted before the all-in may only call or fold
+        for player_id in [&order[2], &order[3]] {
+            assert_eq!(state.round.players_turn.as_ref(), Some(player_id));
+            assert!(!legal_actions(&state, player_id).unwrap().raise);
+            accept_player_bet(&mut state, player_id, P::RaiseTo(400)).unwrap_err();
+            accept_player_bet(&mut state, player_id, P::Call).unwrap();
+        }
+
+        assert_eq!(cards_on_table(&state).len(), 3);
+        assert_eq!(state.round.pot, 600);
+    }
+
+    #[test]
+    fn three_player_game_folded_stake_is_added_to_side_pots() {
+        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
+        state.players.get_mut(&player_1).unwrap().balance = 91;
+
+        accept_player_bet(&mut state, &player_3, P::RaiseTo(200)).unwrap();
+        accept_player_bet(&mut state, &player_1, P::AllIn).unwrap();
+        accept_player_bet(&mut state, &player_2, P::Call).unwrap();
+        assert_eq!(cards_on_table(&state).len(), 3);
+        let pot = state.round.pot;
+
+        if state.round.players_turn == Some(player_3.clone()) {
+            accept_player_bet(&mut state, &player_3, P::Check).unwrap();
+        }
+        fold_player(&mut state, &player_2).unwrap();
+
+        let pots = side_pots(&state);
+        assert_eq!(
+            pots,
+            vec![
+                (303, vec![player_1.clone(), player_3.clone()]),
+                (198, vec![player_3.clone()]),
+            ]
+        );
+        assert_eq!(pots.iter().map(|(pot, _)| pot).sum::<u64>(), pot);
+    }
+
+    #[test]
+    fn three_player_game_all_in_is_rejected_without_balance() {
+        let (mut state, (_, _, player_3)) = fixtures::start_three_player_game();
+        state.players.get_mut(&player_3).unwrap().balance = 0;
+
+        accept_player_bet(&mut state, &player_3, P::AllIn).unwrap_err();
+    }
+
+    #[test]
+    fn split_pot_gives_odd_chips_to_winners_left_of_dealer() {
+        let (state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
+        let seats = seats_from_dealer(&state);
+        assert_eq!(
+            seats,
+            vec![player_2.clone(), player_3.clone(), player_1.clone()]
+        );
+
+        let payouts = split_pot(101, &[player_1.clone(), player_3.clone()], &seats);
+        assert_eq!(payouts, vec![(player_3, 51), (player_1, 50)]);
+    }
+
+    #[test]
+    fn four_player_game_three_way_tie_gives_odd_chip_left_of_dealer() {
+        let mut state = state::State::default();
+        state.config = state.config.clone().with_card_deal_disabled();
+        for name in ["player_1", "player_2", "player_3", "player_4"] {
+            fixtures::add_player(&mut state, name).unwrap();
+        }
+        let order: Vec<_> = state.players.keys().cloned().collect();
+        start_game(&mut state).unwrap();
+
+        let card = |suite, value| cards::Card { suite, value };
+        // the board plays for everyone, so the three live players tie
+        let board = vec![
+            card(cards::CardSuite::Spades, cards::CardValue::Ace),
+            card(cards::CardSuite::Spades, cards::CardValue::King),
+            card(cards::CardSuite::Spades, cards::CardValue::Queen),
+            card(cards::CardSuite::Spades, cards::CardValue::Jack),
+            card(cards::CardSuite::Spades, cards::CardValue::Ten),
+        ];
+        for player in state.players.values_mut() {
+            player.cards = vec![
+                card(cards::CardSuite::Hearts, cards::CardValue::Two),
+                card(cards::CardSuite::Clubs, cards::CardValue::Three),
+            ];
+            player.stake = 100;
+        }
+        // the small blind folds after putting in one chip, leaving a pot of 301
+        let folded = state.players.get_mut(&order[0]).unwrap();
+        folded.folded = true;
+        folded.stake = 1;
+        state.round.pot = 301;
+        state.round.cards_on_table = board.clone();
+
+        let balances_before: u64 = state.players.values().map(|p| p.balance).sum();
+        payout_game_winners(&mut state, &[board]);
+
+        let winnings: Vec<_> = state
+            .round
+            .completed
+            .as_ref()
+            .unwrap()
+            .winners
+            .iter()
+            .map(|w| (w.player_id.clone(), w.winnings))
+            .collect();
+        assert_eq!(
+            winnings,
+            vec![
+                (order[1].clone(), 101),
+                (order[2].clone(), 100),
+                (order[3].clone(), 100),
+            ]
+        );
+        let balances_after: u64 = state.players.values().map(|p| p.balance).sum();
+        assert_eq!(balances_after - balances_before, 301);
+    }
+
+    #[test]
+    fn two_player_game_announces_each_turn_once() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round1);
+        let action_on = |state: &state::State| -> Vec<state::PlayerId> {
+            state
+                .ticker
+                .log()
+                .filter_map(|(_, event)| match event {
+                    TickerEvent::ActionOn(player_id) => Some(player_id.clone()),
+                    _ => None,
+                })
+                .collect()
+        };
+        let first_player = state.round.players_turn.clone().unwrap();
+        let second_player = match first_player == player_1 {
+            true => player_2.clone(),
+            false => player_1.clone(),
+        };
+        assert_eq!(action_on(&state), vec![first_player.clone()]);
+
+        accept_player_bet(&mut state, &first_player, P::Call).unwrap();
+        assert_eq!(
+            action_on(&state),
+            vec![first_player.clone(), second_player.clone()]
+        );
+
+        // the flop starts a new turn, even if it lands on the player who just acted
+        accept_player_bet(&mut state, &second_player, P::Check).unwrap();
+        assert_eq!(cards_on_table(&state).len(), 3);
+        let flop_player = state.round.players_turn.clone().unwrap();
+        assert_eq!(
+            action_on(&state),
+            vec![first_player, second_player, flop_player.clone()]
+        );
+
+        // re-setting the same turn doesn't announce it again
+        set_players_turn(&mut state, Some(flop_player));
+        assert_eq!(action_on(&state).len(), 3);
+    }
+
+    #[test]
+    fn two_player_game_chat_keeps_most_recent_messages() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round1);
+
+        for i in 0..state::CHAT_HISTORY_LENGTH {
+            send_chat_message(&mut state, &player_1, &format!("message {}", i)).unwrap();
+        }
+        send_chat_message(&mut state, &player_2, "last message").unwrap();
+
+        assert_eq!(state.chat.len(), state::CHAT_HISTORY_LENGTH);
+        assert_eq!(state.chat.front().unwrap().message, "message 1");
+        assert_eq!(state.chat.back().unwrap().name, "player_2");
+    }
+
+    #[test]
+    fn three_player_game_unanimous_vote_doubles_blinds() {
+        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
+        let motion = state::vote::Motion::DoubleBlinds;
+
+        start_vote(&mut state, &player_1, motion).unwrap();
+        cast_vote(&mut state, &player_2, true).unwrap();
+        assert!(state.vote.is_some());
+        cast_vote(&mut state, &player_3, true).unwrap();
+
+        assert!(state.vote.is_none());
+        assert_eq!(state.config.small_blind(), SMALL_BLIND);
+        assert_eq!(small_blind(&state), SMALL_BLIND);
+
+        // the doubled blinds wait for the next hand
+        fold_player(&mut state, &player_3).unwrap();
+        fold_player(&mut state, &player_1).unwrap();
+        start_game(&mut state).unwrap();
+        assert_eq!(small_blind(&state), SMALL_BLIND * 2);
+        assert_eq!(big_blind(&state), BIG_BLIND * 2);
+        assert_eq!(state.round.pot, (SMALL_BLIND + BIG_BLIND) * 2);
+    }
+
+    #[test]
+    fn three_player_game_kick_vote_removes_player() {
+        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
+        let motion = state::vote::Motion::KickPlayer(player_3.clone());
+
+        start_vote(&mut state, &player_1, motion).unwrap();
+        cast_vote(&mut state, &player_2, true).unwrap();
+        cast_vote(&mut state, &player_3, false).unwrap();
+
+        assert!(state.vote.is_none());
+        assert!(state.players.get(&player_3).is_none());
+        assert_eq!(state.players.len(), 2);
+    }
+
+    #[test]
+    fn four_player_game_tied_vote_fails() {
+        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
+        state.status = state::GameStatus::Joining;
+        let player_4 = fixtures::add_player(&mut state, "player_4").unwrap();
+
+        start_vote(&mut state, &player_1, state::vote::Motion::DoubleBlinds).unwrap();
+        cast_vote(&mut state, &player_2, true).unwrap();
+        cast_vote(&mut state, &player_3, false).unwrap();
+        cast_vote(&mut state, &player_4, false).unwrap();
+
+        assert!(state.vote.is_none());
+        assert_eq!(state.next_small_blind, None);
+    }
+
+    #[test]
+    fn three_player_game_expired_vote_fails() {
+        let (mut state, (player_1, _, _)) = fixtures::start_three_player_game();
+
+        start_vote(&mut state, &player_1, state::vote::Motion::DoubleBlinds).unwrap();
+        assert_eq!(
+            resolve_vote(&mut state, state::dt::Instant::default()),
+            None
+        );
+
+        let mut after_vote = state::dt::Instant::default();
+        after_vote.add_seconds(state::VOTE_TIMEOUT_SECONDS + 1);
+        assert_eq!(resolve_vote(&mut state, after_vote), Some(false));
+
+        assert!(state.vote.is_none());
+        assert_eq!(state.next_small_blind, None);
+    }
+
+    #[test]
+    fn three_player_game_collects_bring_in_instead_of_blinds() {
+        const BRING_IN: u64 = 5;
+        let mut state = state::State::default();
+        state.config = state
+            .config
+            .clone()
+            .with_forced_bet(state::config::ForcedBet::BringIn(BRING_IN));
+        fixtures::add_player(&mut state, "player_1").unwrap();
+        fixtures::add_player(&mut state, "player_2").unwrap();
+        fixtures::add_player(&mut state, "player_3").unwrap();
+        start_game(&mut state).unwrap();
+
+        let order: Vec<_> = state.players.keys().cloned().collect();
+        assert_eq!(state.round.pot, BRING_IN);
+        assert_eq!(state.players.get(&order[0]).unwrap().stake, BRING_IN);
+        assert_eq!(state.players.get(&order[1]).unwrap().stake, 0);
+        assert_eq!(state.round.players_turn.as_ref(), Some(&order[1]));
+
+        accept_player_bet(&mut state, &order[1], P::Call).unwrap();
+        assert_eq!(state.round.players_turn.as_ref(), Some(&order[2]));
+        accept_player_bet(&mut state, &order[2], P::Call).unwrap();
+
+        assert_eq!(state.round.pot, BRING_IN * 3);
+        assert_eq!(state.round.cards_on_table.len(), 3);
+    }
+
+    #[test]
+    fn three_player_game_straddle_posts_twice_the_big_blind() {
+        let mut state = state::State::default();
+        fixtures::add_player(&mut state, "player_1").unwrap();
+        fixtures::add_player(&mut state, "player_2").unwrap();
+        fixtures::add_player(&mut state, "player_3").unwrap();
+        start_game(&mut state).unwrap();
+
+        let order: Vec<_> = state.players.keys().cloned().collect();
+        let big_blind = big_blind(&state);
+        assert_eq!(state.round.players_turn.as_ref(), Some(&order[2]));
+        assert!(straddle(&mut state, &order[1]).is_err());
+
+        straddle(&mut state, &order[2]).unwrap();
+        assert_eq!(state.round.pot, small_blind(&state) + big_blind * 3);
+        assert_eq!(
+            state.round.raises.last(),
+            Some(&(order[2].clone(), big_blind * 2))
+        );
+        assert_eq!(call_amount(&state), Some(big_blind * 2));
+        assert_eq!(min_raise_to(&state), big_blind * 4);
+        assert_eq!(state.round.players_turn.as_ref(), Some(&order[0]));
+        assert!(straddle(&mut state, &order[2]).is_err());
+
+        accept_player_bet(&mut state, &order[0], P::Call).unwrap();
+        accept_player_bet(&mut state, &order[1], P::Call).unwrap();
+        assert_eq!(state.round.players_turn.as_ref(), Some(&order[2]));
+        accept_player_bet(&mut state, &order[2], P::Check).unwrap();
+
+        assert_eq!(state.round.pot, big_blind * 6);
+        assert_eq!(state.round.cards_on_table.len(), 3);
+    }
+
+    #[test]
+    fn three_player_game_rejects_double_vote() {
+        let (mut state, (player_1, _, _)) = fixtures::start_three_player_game();
+
+        cast_vote(&mut state, &player_1, true).unwrap_err();
+        start_vote(&mut state, &player_1, state::vote::Motion::DoubleBlinds).unwrap();
+        cast_vote(&mut state, &player_1, true).unwrap_err();
+    }
+
+    #[test]
+    fn two_player_game_time_bank_extends_turn_once() {
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Round1);
+        let player_id = state.round.players_turn.clone().unwrap();
+
+        let mut deadline = state::dt::Instant::default();
+        deadline.add_seconds(2);
+        state.players.get_mut(&player_id).unwrap().ttl = Some(deadline);
+
+        use_time_bank(&mut state, &player_id).unwrap();
+        assert!(use_time_bank(&mut state, &player_id).is_err());
+
+        let mut after_deadline = deadline;
+        after_deadline.add_seconds(5);
+        let ttl = state.players.get(&player_id).unwrap().ttl.unwrap();
+        assert!(ttl > after_deadline);
+
+        fold_player(&mut state, &player_1).unwrap();
+        start_game(&mut state).unwrap();
+        assert!(!state.players.get(&player_id).unwrap().time_bank_used);
+    }
+
+    #[test]
+    fn two_player_game_warns_player_once_before_turn_expires() {
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Round1);
+        let now = state::dt::Instant::default();
+        assert!(!warn_player_turn(&mut state, now));
+
+        let mut ttl = state::dt::Instant::default();
+        ttl.add_seconds(state::PLAYER_TURN_WARNING_SECONDS - 5);
+        let player_id = state.round.players_turn.clone().unwrap();
+        state.players.get_mut(&player_id).unwrap().ttl = Some(ttl);
+
+        assert!(warn_player_turn(&mut state, now));
+        assert!(!warn_player_turn(&mut state, now));
+        assert!(!warn_player_turn(&mut state, now));
+
+        let warnings = state
+            .ticker
+            .iter()
+            .filter(|item| matches!(item.payload, TickerEvent::PlayerTurnWarning(_)))
+            .count();
+        assert_eq!(warnings, 1);
+
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        assert!(!state.round.players_turn_warned);
+    }
+
+    #[test]
+    fn two_player_game_tracks_player_stats_across_games() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round1);
+
+        fold_player(&mut state, &player_1).unwrap();
+        assert_eq!(state.status, state::GameStatus::Complete);
+
+        start_game(&mut state).unwrap();
+        assert_eq!(state.round.players_turn, Some(player_2.clone()));
+        accept_player_bet(&mut state, &player_2, P::Call).unwrap();
+        fold_player(&mut state, &player_1).unwrap();
+        assert_eq!(state.status, state::GameStatus::Complete);
+
+        let winner = state.players.get(&player_2).unwrap().stats;
+        assert_eq!(winner.hands_played, 2);
+        assert_eq!(winner.hands_won, 2);
+        assert_eq!(winner.biggest_pot, BIG_BLIND * 2);
+
+        let loser = state.players.get(&player_1).unwrap().stats;
+        assert_eq!(loser.hands_played, 2);
+        assert_eq!(loser.hands_won, 0);
+        assert_eq!(loser.biggest_pot, 0);
+    }
+
+    #[test]
+    fn two_player_game_dealer_posts_small_blind_and_acts_first_preflop() {
+        let (state, (player_1, player_2)) = fixtures::start_two_player_game(GameFixture::Round1);
+
+        let dealer = state.players.get(&player_1).unwrap();
+        let big_blind = state.players.get(&player_2).unwrap();
+        assert_eq!(dealer.stake, SMALL_BLIND);
+        assert_eq!(big_blind.stake, BIG_BLIND);
+        assert_eq!(state.round.players_turn, Some(player_1.clone()));
+    }
+
+    #[test]
+    fn two_player_game_dealer_acts_last_postflop() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round2);
+        assert_eq!(cards_on_table(&state).len(), 3);
+        assert_eq!(state.round.players_turn, Some(player_2.clone()));
+
+        accept_player_bet(&mut state, &player_1, P::Check).unwrap_err();
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+        assert_eq!(state.round.players_turn, Some(player_1.clone()));
+        accept_player_bet(&mut state, &player_1, P::Check).unwrap();
+
+        assert_eq!(cards_on_table(&state).len(), 4);
+        assert_eq!(state.round.players_turn, Some(player_2.clone()));
+    }
+
+    #[test]
+    fn three_player_game_dealer_index_advances_after_game() {
+        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
+        assert_eq!(dealer_index(&state), 0);
+
+        fold_player(&mut state, &player_3).unwrap();
+        fold_player(&mut state, &player_1).unwrap();
+        assert_eq!(state.status, state::GameStatus::Complete);
+        assert_eq!(dealer_index(&state), 1);
+
+        let players: Vec<_> = room_players(&state).into_iter().map(|p| p.name).collect();
+        assert_eq!(players, vec!["player_1", "player_2", "player_3"]);
+        assert_eq!(state.players.keys().next(), Some(&player_2));
+    }
+
+    #[test]
+    fn two_player_game_records_hand_history() {
+        let (mut state, (_, player_2)) = fixtures::start_two_player_game(GameFixture::Complete);
+
+        let history = hand_history(&state).unwrap();
+        let actions: Vec<_> = history
+            .actions
+            .iter()
+            .map(|a| (a.name.clone().unwrap(), &a.street, &a.action, a.amount))
+            .collect();
+
+        use models::{HandActionKind as A, Street as S};
+        let (p1, p2) = ("player_1".to_string(), "player_2".to_string());
+        assert_eq!(
+            actions,
+            vec![
+                (p1.clone(), &S::Preflop, &A::SmallBlind, SMALL_BLIND),
+                (p2.clone(), &S::Preflop, &A::BigBlind, BIG_BLIND),
+                (p1.clone(), &S::Preflop, &A::Call, SMALL_BLIND),
+                (p2.clone(), &S::Preflop, &A::Check, 0),
+                (p2.clone(), &S::Flop, &A::Check, 0),
+                (p1.clone(), &S::Flop, &A::Check, 0),
+                (p2.clone(), &S::Turn, &A::Check, 0),
+                (p1.clone(), &S::Turn, &A::Check, 0),
+                (p2.clone(), &S::River, &A::Check, 0),
+                (p1.clone(), &S::River, &A::Check, 0),
+            ]
+        );
+        assert_eq!(history.board.len(), 5);
+        assert_eq!(history.showdown.len(), 2);
+        assert_eq!(history.winners.len(), 1);
+        assert_eq!(history.winners[0].name, Some(p2));
+        assert_eq!(history.winners[0].winnings, BIG_BLIND * 2);
+
+        // history of the completed game is kept while the next game is played
+        start_game(&mut state).unwrap();
+        assert_eq!(hand_history(&state).unwrap().actions.len(), actions.len());
+
+        fold_player(&mut state, &player_2).unwrap();
+        let history = hand_history(&state).unwrap();
+        assert_eq!(history.actions.last().unwrap().action, A::Fold);
+        assert_eq!(history.winners[0].name, Some(p1));
+    }
+
+    #[test]
+    fn two_player_game_rematch_resets_balances() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Complete);
+        assert_ne!(
+            state.players.get(&player_1).unwrap().balance,
+            STARTING_BALANCE
+        );
+
+        rematch(&mut state).unwrap();
+
+        assert_eq!(state.status, state::GameStatus::Playing);
+        assert!(state.round.cards_on_table.is_empty());
+        for player_id in [&player_1, &player_2] {
+            let player = state.players.get(player_id).unwrap();
+            let expected = STARTING_BALANCE - player.stake;
+            assert_eq!(player.balance, expected);
+            assert!(!player.folded);
+        }
+        assert_eq!(state.round.pot, SMALL_BLIND + BIG_BLIND);
+    }
+
+    #[test]
+    fn two_player_game_rematch_rejected_while_playing() {
+        let (mut state, _) = fixtures::start_two_player_game(GameFixture::Round1);
+
+        rematch(&mut state).unwrap_err();
+    }
+
+    #[test]
+    fn three_player_game_sitting_out_player_is_skipped() {
+        let mut state = state::State::default();
+        state.config = state.config.with_card_deal_disabled();
+        let player_1 = fixtures::add_player(&mut state, "player_1").unwrap();
+        let player_2 = fixtures::add_player(&mut state, "player_2").unwrap();
+        let player_3 = fixtures::add_player(&mut state, "player_3").unwrap();
+        set_player_sitting_out(&mut state, &player_3, true).unwrap();
+        start_game(&mut state).unwrap();
+
+        assert_eq!(state.round.players_turn, Some(player_1.clone()));
+
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        assert_eq!(state.round.players_turn, Some(player_2.clone()));
+        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+        assert_eq!(state.round.players_turn, Some(player_2.clone()));
+
+        let player_3 = state.players.get(&player_3).unwrap();
+        assert!(player_3.folded);
+        assert_eq!(player_3.stake, 0);
+        assert_eq!(player_3.balance, STARTING_BALANCE);
+    }
+
+    #[test]
+    fn four_player_game_big_blind_gets_option_when_first_seat_sits_out() {
+        let mut state = state::State::default();
+        state.config = state.config.clone().with_card_deal_disabled();
+        for name in ["player_1", "player_2", "player_3", "player_4"] {
+            fixtures::add_player(&mut state, name).unwrap();
+        }
+        let sitting_out = state.players.keys().next().cloned().unwrap();
+        set_player_sitting_out(&mut state, &sitting_out, true).unwrap();
+        start_game(&mut state).unwrap();
+
+        let order: Vec<_> = state
+            .players
+            .values()
+            .filter(|p| !p.folded)
+            .map(|p| p.id.clone())
+            .collect();
+        let (small_blind, big_blind, under_the_gun) = (&order[0], &order[1], &order[2]);
+        assert_eq!(state.round.big_blind.as_ref(), Some(big_blind));
+        assert_eq!(state.round.players_turn.as_ref(), Some(under_the_gun));
+
+        accept_player_bet(&mut state, under_the_gun, P::Call).unwrap();
+        accept_player_bet(&mut state, small_blind, P::Call).unwrap();
+        assert_eq!(state.round.players_turn.as_ref(), Some(big_blind));
+        assert!(cards_on_table(&state).is_empty());
+
+        accept_player_bet(&mut state, big_blind, P::Check).unwrap();
+        assert_eq!(cards_on_table(&state).len(), 3);
+    }
+
+    #[test]
+    fn three_player_game_sitting_out_player_can_sit_back_in() {
+        let (mut state, (_, _, player_3)) = fixtures::start_three_player_game();
+        state.status = state::GameStatus::Complete;
+        set_player_sitting_out(&mut state, &player_3, true).unwrap();
+        set_player_sitting_out(&mut state, &player_3, true).unwrap_err();
+        set_player_sitting_out(&mut state, &player_3, false).unwrap();
+        start_game(&mut state).unwrap();
+
+        let player_3 = state.players.get(&player_3).unwrap();
+        assert!(!player_3.folded);
+        assert!(!player_3.sitting_out);
+    }
+
+    #[test]
+    fn two_player_game_accepts_opening_bet_postflop() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round2);
+
+        accept_player_bet(&mut state, &player_2, P::BetTo(BIG_BLIND - 1)).unwrap_err();
+        accept_player_bet(&mut state, &player_2, P::BetTo(BIG_BLIND * 2)).unwrap();
+        assert_eq!(player_stake_in_round(&state, &player_2), BIG_BLIND * 2);
+        assert_eq!(call_amount(&state), Some(BIG_BLIND * 2));
+
+        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        assert_eq!(cards_on_table(&state).len(), 4);
+    }
+
+    #[test]
+    fn two_player_game_rejects_bet_when_bet_exists() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round2);
+
+        accept_player_bet(&mut state, &player_2, P::BetTo(BIG_BLIND)).unwrap();
+        let err = accept_player_bet(&mut state, &player_1, P::BetTo(BIG_BLIND * 4)).unwrap_err();
+        assert_eq!(err, "Cannot bet after a bet, raise instead");
+
+        accept_player_bet(&mut state, &player_1, P::RaiseTo(BIG_BLIND * 4)).unwrap();
+        assert_eq!(player_stake_in_round(&state, &player_1), BIG_BLIND * 4);
+    }
+
+    #[test]
+    fn three_player_game_active_player_leaving_passes_turn() {
+        let (mut state, _) = fixtures::start_three_player_game();
+
+        let leaving_player = state.round.players_turn.clone().unwrap();
+        let next_player = {
+            let mut players = state
+                .players
+                .keys()
+                .cycle()
+                .skip_while(|p| **p != leaving_player)
+                .skip(1);
+            players.next().cloned().unwrap()
+        };
+
+        remove_player(&mut state, &leaving_player).unwrap();
+        assert!(state.players.get(&leaving_player).is_none());
+        assert_eq!(state.status, state::GameStatus::Playing);
+        assert_eq!(state.round.players_turn, Some(next_player.clone()));
+
+        accept_player_bet(&mut state, &next_player, P::Call).unwrap();
+    }
+
+    #[test]
+    fn three_player_game_last_opponent_leaving_pays_out_remaining_player() {
+        let (mut state, _) = fixtures::start_three_player_game();
+
+        let folding_player = state.round.players_turn.clone().unwrap();
+        fold_player(&mut state, &folding_player).unwrap();
+        let leaving_player = state.round.players_turn.clone().unwrap();
+        let pot = state.round.pot;
+
+        remove_player(&mut state, &leaving_player).unwrap();
+        assert_eq!(state.status, state::GameStatus::Complete);
+        assert!(state.round.players_turn.is_none());
+
+        let winner = &state.round.completed.as_ref().unwrap().winners[0];
+        assert_eq!(winner.winnings, pot);
+        assert_ne!(winner.player_id, folding_player);
+    }
+
+    #[test]
+    fn two_player_game_losing_a_player_pauses_until_another_joins() {
+        let (mut state, (_, player_2)) = fixtures::start_two_player_game(GameFixture::Round2);
+
+        remove_player(&mut state, &player_2).unwrap();
+        assert_eq!(state.status, state::GameStatus::Paused);
+        assert!(matches!(game_phase(&state), models::GamePhase::Paused));
+        assert!(state.round.players_turn.is_none());
+
+        fixtures::add_player(&mut state, "player_3").unwrap();
+        start_game(&mut state).unwrap();
+        assert_eq!(state.status, state::GameStatus::Playing);
+        assert!(state.round.players_turn.is_some());
+    }
+
+    #[test]
+    fn two_player_game_blocks_leaving_with_winnings_before_min_hands() {
+        let mut state = state::State::default();
+        state.config = state.config.clone().with_min_hands_before_leaving(2);
+        let player_1 = fixtures::add_player(&mut state, "player_1").unwrap();
+        let player_2 = fixtures::add_player(&mut state, "player_2").unwrap();
+        start_game(&mut state).unwrap();
+        fixtures::deal_biased_deck(&mut state, &player_1, &player_2, true);
+        fixtures::progress_two_player_game(&mut state, GameFixture::Complete);
+
+        let err = remove_player(&mut state, &player_2).unwrap_err();
+        assert!(err.contains("2 hands"));
+        remove_player(&mut state, &player_1).unwrap();
+
+        let player_3 = fixtures::add_player(&mut state, "player_3").unwrap();
+        start_game(&mut state).unwrap();
+        fixtures::deal_biased_deck(&mut state, &player_3, &player_2, true);
+        fixtures::progress_two_player_game(&mut state, GameFixture::Complete);
+        remove_player(&mut state, &player_2).unwrap();
+    }
+
+    #[test]
+    fn player_leaving_before_start_keeps_room_joining() {
+        let mut state = state::State::default();
+        fixtures::add_player(&mut state, "player_1").unwrap();
+        let player_2 = fixtures::add_player(&mut state, "player_2").unwrap();
+
+        remove_player(&mut state, &player_2).unwrap();
+        assert_eq!(state.status, state::GameStatus::Joining);
+    }
+
+    #[test]
+    fn legal_actions_disallow_check_after_raise() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round1);
+        let first_player = state.round.players_turn.clone().unwrap();
+        let second_player = match first_player == player_1 {
+            true => player_2.clone(),
+            false => player_1.clone(),
+        };
+        let waiting = legal_actions(&state, &second_player).unwrap();
+        assert!(!waiting.fold && !waiting.check);
+
+        accept_player_bet(&mut state, &first_player, P::RaiseTo(BIG_BLIND * 3)).unwrap();
+
+        let actions = legal_actions(&state, &second_player).unwrap();
+        assert!(!actions.check);
+        assert!(actions.call && actions.raise && actions.fold);
+        assert_eq!(actions.min_raise_to, BIG_BLIND * 5);
+        assert_eq!(actions.max_raise_to, STARTING_BALANCE);
+    }
+
+    #[test]
+    fn legal_actions_disallow_call_without_bet() {
+        let (state, _) = fixtures::start_two_player_game(GameFixture::Round2);
+        let player = state.round.players_turn.clone().unwrap();
+        assert_eq!(call_amount(&state), None);
+
+        let actions = legal_actions(&state, &player).unwrap();
+        assert!(!actions.call);
+        assert!(actions.check && actions.raise && actions.fold);
+        assert_eq!(actions.min_raise_to, BIG_BLIND);
+    }
+
+    #[test]
+    fn completed_game_auto_starts_next_hand_after_delay() {
+        let (mut state, _) = fixtures::start_two_player_game(GameFixture::Complete);
+        assert_eq!(next_hand_at(&state), None);
+
+        state.config = state.config.clone().with_auto_start(5);
+        let next_hand = next_hand_at(&state).unwrap();
+        assert_eq!(next_hand, state.last_update.as_u64() + 5_000);
+
+        let mut now = state::dt::Instant::from(state.last_update.as_u64());
+        now.add_seconds(1);
+        assert!(!auto_start_next_hand(&mut state, now));
+        assert_eq!(state.status, state::GameStatus::Complete);
+
+        now.add_seconds(5);
+        assert!(auto_start_next_hand(&mut state, now));
+        assert_eq!(state.status, state::GameStatus::Playing);
+        assert_eq!(next_hand_at(&state), None);
+    }
+
+    #[test]
+    fn reshuffle_is_only_allowed_between_hands() {
+        let (mut state, _) = fixtures::start_two_player_game(GameFixture::Round2);
+        reshuffle(&mut state).unwrap_err();
+
+        let (mut state, _) = fixtures::start_two_player_game(GameFixture::Complete);
+        reshuffle(&mut state).unwrap();
+        assert!(state.round.deck.is_fresh(state.config.variant()));
+
+        let mut state = state::State::default();
+        fixtures::add_player(&mut state, "player_1").unwrap();
+        fixtures::add_player(&mut state, "player_2").unwrap();
+        reshuffle(&mut state).unwrap();
+
+        start_game(&mut state).unwrap();
+        assert_eq!(state.round.deck.remaining().count(), 52 - 4);
+        reshuffle(&mut state).unwrap_err();
+    }
+
+    #[test]
+    fn two_player_game_deals_from_reshuffled_deck() {
+        let mut state = state::State::default();
+        state.config = state.config.clone().with_deck_seed(7);
+        let player_1 = fixtures::add_player(&mut state, "player_1").unwrap();
+        let player_2 = fixtures::add_player(&mut state, "player_2").unwrap();
+
+        reshuffle(&mut state).unwrap();
+        let top: Vec<_> = state.round.deck.remaining().take(4).copied().collect();
+        let seeded: Vec<_> = cards::Deck::seeded(7)
+            .remaining()
+            .take(4)
+            .copied()
+            .collect();
+        let as_tuples = |cards: &[cards::Card]| -> Vec<_> {
+            cards.iter().map(|c| (c.suite, c.value)).collect()
+        };
+        assert_ne!(as_tuples(&top), as_tuples(&seeded));
+
+        start_game(&mut state).unwrap();
+        assert!(!state.round.reshuffled);
+        let dealt: Vec<_> = [&player_1, &player_2]
+            .into_iter()
+            .flat_map(|id| state.players.get(id).unwrap().cards.clone())
+            .collect();
+        assert_eq!(as_tuples(&dealt), as_tuples(&top));
+    }
+
+    #[test]
+    fn room_idle_timeout_is_configurable() {
+        let mut state = state::State::default();
+        fixtures::add_player(&mut state, "player_1").unwrap();
+        let mut now = state::dt::Instant::from(state.last_update.as_u64());
+        now.add_seconds(60);
+
+        state.config = state.config.clone().with_idle_timeout(30);
+        assert!(room_idle_expired(&state, now));
+
+        state.config = state.config.clone().with_idle_timeout(600);
+        assert!(!room_idle_expired(&state, now));
+
+        state.reset();
+        for _ in 0..2 {
+            let player_1 = fixtures::add_player(&mut state, "player_1").unwrap();
+            fixtures::add_player(&mut state, "player_2").unwrap();
+            start_game(&mut state).unwrap();
+            let first_player = state.round.players_turn.clone().unwrap();
+            fold_player(&mut state, &first_player).unwrap();
+            assert_eq!(state.status, state::GameStatus::Complete);
+
+            // completed games get four times the grace period
+            let mut now = state::dt::Instant::from(state.last_update.as_u64());
+            now.add_seconds(2000);
+            assert!(!room_idle_expired(&state, now));
+            now.add_seconds(401);
+            assert!(room_idle_expired(&state, now));
+
+            assert!(reset_idle_room(&mut state));
+            assert!(state.players.get(&player_1).is_none());
+            assert_eq!(state.config.idle_timeout_seconds(), 600);
+        }
+
+        // a reshuffled short deck has not been dealt from, so there is nothing to reset
+        state.config = state.config.clone().with_variant(cards::Variant::ShortDeck);
+        fixtures::add_player(&mut state, "player_1").unwrap();
+        reshuffle(&mut state).unwrap();
+        assert!(!reset_idle_room(&mut state));
+        assert_eq!(state.players.len(), 1);
+    }
+
+    #[test]
+    fn room_keeps_its_code_after_idle_reset() {
+        let (mut state, _) = fixtures::start_two_player_game(GameFixture::Complete);
+        let room_code = state::room::RoomCode::default();
+        state.room_code = Some(room_code.clone());
+
+        assert!(reset_idle_room(&mut state));
+        assert_eq!(state.room_code, Some(room_code));
+    }
+
+    #[test]
+    fn private_room_requires_password_after_idle_reset() {
+        let (mut state, _) = fixtures::start_two_player_game(GameFixture::Complete);
+        state.config = state.config.clone().with_password("hunter2");
+
+        assert!(reset_idle_room(&mut state));
+        assert_eq!(state.players.len(), 0);
+        assert!(!state.config.check_password(None));
+        assert!(state.config.check_password(Some("hunter2")));
+    }
+
+    #[test]
+    fn seat_reservations_block_joins_until_expired() {
+        let mut state = state::State::default();
+        state.config = state.config.clone().with_max_players(2);
+        let now = state::dt::Instant::default();
+
+        let (reservation, _) = reserve_seat(&mut state, now).unwrap();
+        reserve_seat(&mut state, now).unwrap();
+        reserve_seat(&mut state, now).unwrap_err();
+        fixtures::add_player(&mut state, "player_1").unwrap_err();
+
+        let player_id = state::PlayerId::default();
+        add_reserved_player(&mut state, "player_1", player_id, &reservation).unwrap();
+        assert_eq!(state.players.len(), 1);
+        fixtures::add_player(&mut state, "player_2").unwrap_err();
+
+        let mut later = now;
+        later.add_seconds(state::SEAT_RESERVATION_SECONDS + 1);
+        expire_seat_reservations(&mut state, later);
+        fixtures::add_player(&mut state, "player_2").unwrap();
+    }
+
+    #[test]
+    fn spectator_count_decays_after_ttl() {
+        let mut state = state::State::default();
+        let now = state::dt::Instant::default();
+        let player_id = fixtures::add_player(&mut state, "player_1").unwrap();
+        set_player_apid(&mut state, &player_id, "player");
+        state.screen_apids.insert("screen".to_string());
+
+        record_spectator(&state, "visitor_1", now);
+        record_spectator(&state, "visitor_2", now);
+        record_spectator(&state, "visitor_2", now);
+        record_spectator(&state, "player", now);
+        record_spectator(&state, "screen", now);
+        assert_eq!(spectator_count(&state, now), 2);
+
+        let mut later = now;
+        later.add_seconds(state::SPECTATOR_TTL_SECONDS + 1);
+        assert_eq!(spectator_count(&state, later), 0);
+
+        expire_spectators(&state, later);
+        assert!(state.spectators.is_empty());
+    }
+
+    #[test]
+    fn two_player_game_shows_only_polling_player_online() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round1);
+        let online = |state: &state::State, name: &str| {
+            room_players(state)
+                .into_iter()
+                .find(|p| p.name == name)
+                .unwrap()
+                .online
+        };
+        let name_1 = state.players.get(&player_1).unwrap().name.clone();
+        let name_2 = state.players.get(&player_2).unwrap().name.clone();
+
+        let now = state::dt::Instant::default();
+        assert!(player_seen_stale(&state, &player_1, now));
+        record_player_seen(&mut state, &player_1, now);
+        assert!(!player_seen_stale(&state, &player_1, now));
+        assert!(online(&state, &name_1));
+        assert!(!online(&state, &name_2));
+
+        let earlier = now.as_u64() - (state::PLAYER_ONLINE_SECONDS + 1) * 1000;
+        record_player_seen(&mut state, &player_1, earlier.into());
+        assert!(player_seen_stale(&state, &player_1, now));
+        assert!(!online(&state, &name_1));
+    }
 
-        assert_eq!(player_1_balance - 100, player_1_balance_after_transfer);
-        assert_eq!(player_2_balance + 100, player_2_balance_after_transfer);
+    #[test]
+    fn two_player_game_checks_session_secret_when_required() {
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Round1);
+        let secret = state
+            .players
+            .get(&player_1)
+            .unwrap()
+            .session_secret
+            .to_string();
+        check_session_secret(&state, &player_1, None).unwrap();
+
+        state.config = state.config.clone().with_session_secret_required();
+        check_session_secret(&state, &player_1, None).unwrap_err();
+        check_session_secret(&state, &player_1, Some(&secret[1..])).unwrap_err();
+        check_session_secret(&state, &player_1, Some(&secret.to_uppercase())).unwrap_err();
+        check_session_secret(&state, &player_1, Some(&secret)).unwrap();
     }
 
     #[test]
-    fn two_player_game_reraising_minimum_works() {
+    fn two_player_game_emoji_is_rate_limited_per_player() {
         let (mut state, (player_1, player_2)) =
-            fixtures::start_two_player_game(GameFixture::Round4);
-        assert_eq!(state.round.pot, 40);
-        accept_player_bet(&mut state, &player_1, P::RaiseTo(BIG_BLIND)).unwrap();
-        assert_eq!(state.status, state::GameStatus::Playing);
-        assert_eq!(state.round.pot, 60);
-        assert_eq!(state.players.get(&player_1).unwrap().stake, BIG_BLIND * 2);
-        assert_eq!(state.players.get(&player_2).unwrap().stake, BIG_BLIND);
-
-        accept_player_bet(&mut state, &player_2, P::RaiseTo(BIG_BLIND * 2)).unwrap();
-        assert_eq!(state.round.pot, 100);
-        assert_eq!(state.players.get(&player_1).unwrap().stake, BIG_BLIND * 2);
-        assert_eq!(state.players.get(&player_2).unwrap().stake, BIG_BLIND * 3);
-
-        accept_player_bet(&mut state, &player_1, P::RaiseTo(BIG_BLIND * 3)).unwrap();
-        assert_eq!(state.players.get(&player_1).unwrap().stake, BIG_BLIND * 4);
-        assert_eq!(state.players.get(&player_2).unwrap().stake, BIG_BLIND * 3);
+            fixtures::start_two_player_game(GameFixture::Round1);
+        let now = state::dt::Instant::default();
+        let emoji = state::ticker::emoji::TickerEmoji::thumbs_up;
 
-        assert_eq!(state.status, state::GameStatus::Playing);
+        send_emoji(&mut state, &player_1, emoji(), now).unwrap();
+        send_emoji(&mut state, &player_1, emoji(), now).unwrap_err();
+        send_emoji(&mut state, &player_2, emoji(), now).unwrap();
 
-        assert_eq!(state.round.pot, 140);
+        let mut later = now;
+        later.add_seconds(state::PLAYER_EMOJI_COOLDOWN_SECONDS);
+        send_emoji(&mut state, &player_1, emoji(), later).unwrap();
 
-        accept_player_bet(&mut state, &player_2, P::Call).unwrap();
-        assert_eq!(state.status, state::GameStatus::Complete);
+        let sent = state
+            .ticker
+            .iter()
+            .filter(|item| matches!(item.payload, TickerEvent::PlayerSentEmoji(..)))
+            .count();
+        assert_eq!(sent, 3);
     }
 
     #[test]
-    fn three_player_game_fold_on_small_blind() {
-        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
-        assert_eq!(cards_on_table(&state).len(), 0);
-        assert_eq!(state.round.pot, 30);
-
-        fold_player(&mut state, &player_3).expect("R2-P3");
-        fold_player(&mut state, &player_1).expect("R2-P1");
-        assert_eq!(state.status, state::GameStatus::Complete);
+    fn knock_is_rate_limited_per_visitor() {
+        let mut state = state::State::default();
+        let now = state::dt::Instant::default();
+        let knocks = |state: &state::State| {
+            state
+                .ticker
+                .iter()
+                .filter(|item| matches!(item.payload, TickerEvent::Knock))
+                .count()
+        };
 
-        let winner = state.players.get(&player_2).unwrap();
-        assert_eq!(winner.balance, STARTING_BALANCE + SMALL_BLIND);
+        knock(&mut state, "visitor", now).unwrap();
+        knock(&mut state, "visitor", now).unwrap_err();
+        assert_eq!(knocks(&state), 1);
 
-        let completed = completed_game(&state).unwrap();
-        assert_eq!(completed.winning_hand, None);
+        let mut later = now;
+        later.add_seconds(state::KNOCK_COOLDOWN_SECONDS);
+        knock(&mut state, "visitor", later).unwrap();
+        assert_eq!(knocks(&state), 2);
+        assert_eq!(state.status, state::GameStatus::Joining);
     }
 
     #[test]
-    fn three_player_game_check_until_river_then_raise_on_last_player() {
-        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
-        assert_eq!(cards_on_table(&state).len(), 0);
-        assert_eq!(state.round.pot, 30);
+    fn two_player_game_queued_check_fold_checks_then_folds() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round2);
+        let first_player = state.round.players_turn.clone().unwrap();
+        let second_player = match first_player == player_1 {
+            true => player_2.clone(),
+            false => player_1.clone(),
+        };
 
-        accept_player_bet(&mut state, &player_3, P::Call).unwrap();
-        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
-        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+        queue_player_action(&mut state, &second_player, state::QueuedAction::CheckFold).unwrap();
+        accept_player_bet(&mut state, &first_player, P::Check).unwrap();
+        assert_eq!(cards_on_table(&state).len(), 4);
+        assert_eq!(state.round.players_turn, Some(first_player.clone()));
 
-        assert_eq!(cards_on_table(&state).len(), 3);
-        assert_eq!(state.round.pot, 60);
+        queue_player_action(&mut state, &second_player, state::QueuedAction::CheckFold).unwrap();
+        accept_player_bet(&mut state, &first_player, P::BetTo(BIG_BLIND)).unwrap();
+        assert!(state.players.get(&second_player).unwrap().folded);
+        assert_eq!(state.status, state::GameStatus::Complete);
+    }
 
-        accept_player_bet(&mut state, &player_1, P::Check).unwrap();
-        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
-        accept_player_bet(&mut state, &player_3, P::Check).unwrap();
+    #[test]
+    fn queued_fold_is_dropped_when_player_can_check() {
+        let (mut state, (player_1, player_2)) =
+            fixtures::start_two_player_game(GameFixture::Round2);
+        let first_player = state.round.players_turn.clone().unwrap();
+        let second_player = match first_player == player_1 {
+            true => player_2.clone(),
+            false => player_1.clone(),
+        };
 
-        assert_eq!(cards_on_table(&state).len(), 4);
-        assert_eq!(state.round.pot, 60);
+        queue_player_action(&mut state, &second_player, state::QueuedAction::Fold).unwrap();
+        accept_player_bet(&mut state, &first_player, P::Check).unwrap();
+        assert_eq!(state.round.players_turn, Some(second_player.clone()));
+        let player = state.players.get(&second_player).unwrap();
+        assert!(!player.folded);
+        assert!(player.queued_action.is_none());
+    }
 
-        accept_player_bet(&mut state, &player_1, P::Check).unwrap();
-        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
-        accept_player_bet(&mut state, &player_3, P::Check).unwrap();
+    #[test]
+    fn two_player_game_reports_pot_odds() {
+        let (mut state, _) = fixtures::start_two_player_game(GameFixture::Round1);
+        let first_player = state.round.players_turn.clone().unwrap();
+
+        // the small blind owes 10 to call into a pot of 30
+        let player = game_player_state(&state, &first_player).unwrap();
+        assert_eq!(player.pot, 30);
+        assert_eq!(player.pot_odds, Some(0.25));
+
+        accept_player_bet(&mut state, &first_player, P::Call).unwrap();
+        let player = game_player_state(&state, &first_player).unwrap();
+        assert_eq!(player.pot, 40);
+        assert_eq!(player.pot_odds, None);
+    }
 
-        assert_eq!(cards_on_table(&state).len(), 5);
-        assert_eq!(state.round.pot, 60);
+    #[test]
+    fn two_player_game_tracks_session_net() {
+        let (state, (player_1, player_2)) = fixtures::start_two_player_game(GameFixture::Complete);
+        let session_net = |player_id| game_player_state(&state, player_id).unwrap().session_net;
 
-        accept_player_bet(&mut state, &player_1, P::RaiseTo(100)).unwrap();
-        assert_eq!(player_stake_in_round(&state, &player_1), 100);
+        assert!(session_net(&player_1) < 0);
+        assert!(session_net(&player_2) > 0);
+        assert_eq!(session_net(&player_1), -session_net(&player_2));
+    }
 
-        accept_player_bet(&mut state, &player_2, P::Call).unwrap();
-        assert_eq!(player_stake_in_round(&state, &player_2), 100);
+    #[test]
+    fn rebuy_does_not_change_session_net() {
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Complete);
+        let session_net = game_player_state(&state, &player_1).unwrap().session_net;
 
-        accept_player_bet(&mut state, &player_3, P::RaiseTo(200)).unwrap();
-        assert_eq!(player_stake_in_round(&state, &player_3), 200);
-        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
-        assert_eq!(player_stake_in_round(&state, &player_1), 200);
+        state.config = state.config.clone().with_rebuys();
+        rebuy_player(&mut state, &player_1).unwrap();
 
-        accept_player_bet(&mut state, &player_2, P::Call).unwrap();
-        assert_eq!(state.status, state::GameStatus::Complete);
+        let player = game_player_state(&state, &player_1).unwrap();
+        assert_eq!(player.balance, STARTING_BALANCE);
+        assert_eq!(player.session_net, session_net);
     }
 
     #[test]
-    fn two_player_game_ends_in_big_win_next_game_accepts_call_to_all_in() {
+    fn two_player_game_busted_player_can_rebuy() {
         let (mut state, (player_1, player_2)) =
-            fixtures::start_two_player_game(GameFixture::Round4);
-
-        assert_eq!(cards_on_table(&state).len(), 5);
+            fixtures::start_two_player_game(GameFixture::Round1);
 
-        // game 1, round 4
-        accept_player_bet(&mut state, &player_1, P::RaiseTo(500)).unwrap();
-        accept_player_bet(&mut state, &player_2, P::Call).unwrap();
+        let first_player = state.round.players_turn.clone().unwrap();
+        let second_player = match first_player == player_1 {
+            true => player_2.clone(),
+            false => player_1.clone(),
+        };
+        accept_player_bet(&mut state, &first_player, P::AllIn).unwrap();
+        accept_player_bet(&mut state, &second_player, P::Call).unwrap();
         assert_eq!(state.status, state::GameStatus::Complete);
+        assert_eq!(state.players.get(&player_1).unwrap().balance, 0);
 
-        let player_1_balance = {
-            let loser = state.players.get(&player_1).unwrap();
-            let winner = state.players.get(&player_2).unwrap();
-            assert!(winner.balance > loser.balance);
-            loser.balance
-        };
+        rebuy_player(&mut state, &player_1).unwrap_err();
+        state.config = state.config.clone().with_rebuys();
+        rebuy_player(&mut state, &player_1).unwrap();
+        rebuy_player(&mut state, &player_2).unwrap_err();
+        assert_eq!(
+            state.players.get(&player_1).unwrap().balance,
+            STARTING_BALANCE
+        );
 
-        // game 2, round 1
         start_game(&mut state).unwrap();
-        accept_player_bet(&mut state, &player_2, P::RaiseTo(player_1_balance)).unwrap();
-        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        let player_1 = state.players.get(&player_1).unwrap();
+        assert!(!player_1.folded);
+        assert!(player_1.stake > 0);
+        rebuy_player(&mut state, &player_2).unwrap_err();
     }
 
     #[test]
-    fn two_player_game_ends_in_big_win_next_game_accepts_call_to_above_all_in() {
+    fn two_player_game_losing_player_can_muck() {
         let (mut state, (player_1, player_2)) =
-            fixtures::start_two_player_game(GameFixture::Round4);
+            fixtures::start_two_player_game(GameFixture::Complete);
 
-        assert_eq!(cards_on_table(&state).len(), 5);
+        let completed = completed_game(&state).unwrap();
+        assert!(completed.player_cards.iter().all(Option::is_some));
 
-        // game 1, round 4
-        accept_player_bet(&mut state, &player_1, P::RaiseTo(500)).unwrap();
-        accept_player_bet(&mut state, &player_2, P::Call).unwrap();
-        assert_eq!(state.status, state::GameStatus::Complete);
+        set_player_muck(&mut state, &player_2).unwrap_err();
+        set_player_muck(&mut state, &player_1).unwrap();
 
-        let player_1_balance = {
-            let loser = state.players.get(&player_1).unwrap();
-            let winner = state.players.get(&player_2).unwrap();
-            assert!(winner.balance > loser.balance);
-            loser.balance
-        };
+        let completed = completed_game(&state).unwrap();
+        assert_eq!(completed.winner_name.as_deref(), Some("player_2"));
+        assert!(completed.player_cards[0].is_none());
+        assert!(completed.player_cards[1].is_some());
 
-        // game 2, round 1
+        start_game(&mut state).unwrap();
+        assert!(!state.players.get(&player_1).unwrap().muck);
+        let history = hand_history(&state).unwrap();
+        assert_eq!(history.showdown.len(), 1);
+        assert_eq!(history.showdown[0].name.as_deref(), Some("player_2"));
+    }
+
+    #[test]
+    fn two_player_game_auto_mucks_losing_hand() {
+        let mut state = state::State::default();
+        state.config = state.config.clone().with_auto_muck_losers();
+        let player_1 = fixtures::add_player(&mut state, "player_1").unwrap();
+        let player_2 = fixtures::add_player(&mut state, "player_2").unwrap();
         start_game(&mut state).unwrap();
         fixtures::deal_biased_deck(&mut state, &player_1, &player_2, true);
-        assert_eq!(state.status, state::GameStatus::Playing);
+        fixtures::progress_two_player_game(&mut state, GameFixture::Complete);
 
-        accept_player_bet(&mut state, &player_2, P::RaiseTo(player_1_balance + 100)).unwrap();
-        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        let completed = completed_game(&state).unwrap();
+        assert_eq!(completed.winner_name.as_deref(), Some("player_2"));
+        assert!(completed.player_cards[0].is_none());
+        assert!(completed.player_cards[1].is_some());
+        let showdown = &state.round.completed.as_ref().unwrap().showdown;
+        assert_eq!(showdown.len(), 1);
+        assert_eq!(showdown[0].0, player_2);
+
+        set_player_show(&mut state, &player_1).unwrap();
+        let completed = completed_game(&state).unwrap();
+        assert!(completed.player_cards.iter().all(Option::is_some));
+        assert_eq!(hand_history(&state).unwrap().showdown.len(), 2);
+    }
 
-        assert_eq!(cards_on_table(&state).len(), 5);
-        assert_eq!(state.status, state::GameStatus::Complete);
+    #[test]
+    fn two_player_game_rake_is_taken_from_pot() {
+        let mut state = state::State::default();
+        state.config = state.config.clone().with_rake(5, 50);
+        let player_1 = fixtures::add_player(&mut state, "player_1").unwrap();
+        let player_2 = fixtures::add_player(&mut state, "player_2").unwrap();
 
-        let loser = state.players.get(&player_1).unwrap();
-        let winner = state.players.get(&player_2).unwrap();
-        assert!(winner.balance > loser.balance);
-        assert_eq!(loser.balance, 0);
+        let mut rake_total = 0;
+        for _ in 0..2 {
+            start_game(&mut state).unwrap();
+            fixtures::deal_biased_deck(&mut state, &player_1, &player_2, true);
+            fixtures::progress_two_player_game(&mut state, GameFixture::Complete);
+
+            let pot: u64 = state.players.values().map(|p| p.stake).sum();
+            let rake = pot * 5 / 100;
+            rake_total += rake;
+            let completed = state.round.completed.as_ref().unwrap();
+            assert_eq!(completed.winners.len(), 1);
+            assert_eq!(completed.winners[0].winnings, pot - rake);
+            assert_eq!(completed_game(&state).unwrap().rake_taken, rake);
+        }
+        assert!(rake_total > 0);
+        assert_eq!(state.rake_total, rake_total);
+
+        let rake = state.config.rake().unwrap();
+        assert_eq!(rake.amount(10_000), 50);
     }
 
     #[test]
-    fn two_player_game_raising_round_one() {
-        let (mut state, (player_1, player_2)) =
-            fixtures::start_two_player_game(GameFixture::Round1);
+    fn two_player_game_admin_adjusts_balance_between_hands() {
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Round1);
+        adjust_balance(&mut state, &player_1, 500).unwrap_err();
+
+        let (mut state, (player_1, _)) = fixtures::start_two_player_game(GameFixture::Complete);
+        let balance = state.players.get(&player_1).unwrap().balance;
+        let session_net =
+            |state: &state::State| game_player_state(state, &player_1).unwrap().session_net;
+        let net = session_net(&state);
+        adjust_balance(&mut state, &player_1, 500).unwrap();
+        assert_eq!(state.players.get(&player_1).unwrap().balance, balance + 500);
+        assert_eq!(session_net(&state), net);
+        assert_eq!(
+            state.players.get(&player_1).unwrap().buy_in_total,
+            STARTING_BALANCE + 500
+        );
+        adjust_balance(&mut state, &player_1, -(balance as i64) - 501).unwrap_err();
 
-        assert_eq!(cards_on_table(&state).len(), 0);
+        let (_, event) = state.ticker.log().last().unwrap();
+        assert!(matches!(event, TickerEvent::BalanceAdjusted(id, 500) if *id == player_1));
+        let message = event.format(&state);
+        assert!(message.contains("adjusted by +") && message.ends_with("500"));
+    }
 
-        accept_player_bet(&mut state, &player_1, P::RaiseTo(BIG_BLIND * 2)).unwrap();
+    #[test]
+    fn two_player_game_reveals_deck_matching_commitment() {
+        let mut state = state::State::default();
+        let player_1 = fixtures::add_player(&mut state, "player_1").unwrap();
+        fixtures::add_player(&mut state, "player_2").unwrap();
+        start_game(&mut state).unwrap();
 
-        accept_player_bet(&mut state, &player_2, P::RaiseTo(BIG_BLIND * 3)).unwrap();
-        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
+        let commitment = deck_commitment(&state).unwrap();
+        assert!(completed_game(&state).is_none());
+        let player_id = state.round.players_turn.clone().unwrap();
+        accept_player_bet(&mut state, &player_id, P::Call).unwrap();
+        while state.status == state::GameStatus::Playing {
+            let player_id = state.round.players_turn.clone().unwrap();
+            accept_player_bet(&mut state, &player_id, P::Check).unwrap();
+        }
 
-        assert_eq!(cards_on_table(&state).len(), 3);
+        let reveal = completed_game(&state).unwrap().deck_reveal.unwrap();
+        let deck: Vec<_> = reveal
+            .deck
+            .iter()
+            .map(|&(suite, value)| cards::Card { suite, value })
+            .collect();
+        assert_eq!(deck_commitment_hash(&reveal.seed, &deck), commitment);
+
+        let player_1 = state.players.get(&player_1).unwrap();
+        let dealt: Vec<_> = player_1.cards.iter().map(|c| (c.suite, c.value)).collect();
+        assert_eq!(dealt, reveal.deck[..2]);
     }
 
     #[test]
-    fn two_player_game_raising_with_intermittent_calls_checking_balances() {
-        let (mut state, (player_1, player_2)) =
-            fixtures::start_two_player_game(GameFixture::Round1);
+    fn three_player_game_keeps_deck_hidden_when_a_player_folded() {
+        let mut state = state::State::default();
+        fixtures::add_player(&mut state, "player_1").unwrap();
+        fixtures::add_player(&mut state, "player_2").unwrap();
+        fixtures::add_player(&mut state, "player_3").unwrap();
+        start_game(&mut state).unwrap();
 
-        assert_eq!(cards_on_table(&state).len(), 0);
-        assert_eq!(
-            state.players.get(&player_1).unwrap().balance,
-            STARTING_BALANCE - SMALL_BLIND
-        );
-        assert_eq!(
-            state.players.get(&player_2).unwrap().balance,
-            STARTING_BALANCE - BIG_BLIND
-        );
+        let folded_player = state.round.players_turn.clone().unwrap();
+        fold_player(&mut state, &folded_player).unwrap();
+        let player_id = state.round.players_turn.clone().unwrap();
+        accept_player_bet(&mut state, &player_id, P::Call).unwrap();
+        while state.status == state::GameStatus::Playing {
+            let player_id = state.round.players_turn.clone().unwrap();
+            accept_player_bet(&mut state, &player_id, P::Check).unwrap();
+        }
 
-        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
-        assert_eq!(
-            state.players.get(&player_1).unwrap().balance,
-            STARTING_BALANCE - BIG_BLIND
-        );
-        accept_player_bet(&mut state, &player_2, P::RaiseTo(BIG_BLIND * 2)).unwrap();
-        assert_eq!(
-            state.players.get(&player_2).unwrap().balance,
-            STARTING_BALANCE - 40
-        );
+        let completed = completed_game(&state).unwrap();
+        let shown = completed
+            .player_cards
+            .iter()
+            .filter(|c| c.is_some())
+            .count();
+        assert_eq!(shown, 2);
+        assert!(completed.deck_reveal.is_none());
     }
 
     #[test]
-    fn three_player_game_folded_players_dont_have_turns_in_further_rounds() {
-        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
-        assert_eq!(cards_on_table(&state).len(), 0);
+    fn game_raises_blinds_on_schedule() {
+        let mut state = state::State::default();
+        let schedule = state::config::BlindSchedule::new(vec![(2, SMALL_BLIND * 2)]);
+        state.config = state.config.clone().with_blind_schedule(schedule);
+        fixtures::add_player(&mut state, "player_1").unwrap();
+        fixtures::add_player(&mut state, "player_2").unwrap();
 
-        accept_player_bet(&mut state, &player_3, P::Call).unwrap();
-        fold_player(&mut state, &player_1).expect("R2-P1");
-        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+        for _ in 0..2 {
+            start_game(&mut state).unwrap();
+            assert_eq!(state.blind_level, 0);
+            assert_eq!(state.round.pot, SMALL_BLIND + BIG_BLIND);
 
-        assert_eq!(cards_on_table(&state).len(), 3);
+            let player = state.round.players_turn.clone().unwrap();
+            fold_player(&mut state, &player).unwrap();
+            assert_eq!(state.status, state::GameStatus::Complete);
+        }
 
-        // ensure player 1 does not take a turn given they have folded
-        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+        start_game(&mut state).unwrap();
+        assert_eq!(state.blind_level, 1);
+        assert_eq!(small_blind(&state), SMALL_BLIND * 2);
+        assert_eq!(state.round.pot, (SMALL_BLIND + BIG_BLIND) * 2);
     }
 
     #[test]
-    fn three_player_game_raise_someone_over_all_in_completes() {
-        let (mut state, (player_1, player_2, player_3)) = fixtures::start_three_player_game();
-        let player_1 = state.players.get_mut(&player_1).unwrap();
-        player_1.balance = 100;
+    fn game_restarts_blind_schedule_after_reset_and_rematch() {
+        let mut state = state::State::default();
+        let schedule = state::config::BlindSchedule::new(vec![(1, SMALL_BLIND * 2)]);
+        state.config = state.config.clone().with_blind_schedule(schedule);
+        let add_players = |state: &mut state::State| {
+            fixtures::add_player(state, "player_1").unwrap();
+            fixtures::add_player(state, "player_2").unwrap();
+        };
+        let play_hand = |state: &mut state::State| {
+            start_game(state).unwrap();
+            let player = state.round.players_turn.clone().unwrap();
+            fold_player(state, &player).unwrap();
+        };
 
-        let player_1 = player_1.id.clone();
+        add_players(&mut state);
+        play_hand(&mut state);
+        play_hand(&mut state);
+        assert_eq!(small_blind(&state), SMALL_BLIND * 2);
+
+        rematch(&mut state).unwrap();
+        assert_eq!(state.blind_level, 0);
+        assert_eq!(state.round.pot, SMALL_BLIND + BIG_BLIND);
+        let player = state.round.players_turn.clone().unwrap();
+        fold_player(&mut state, &player).unwrap();
+        play_hand(&mut state);
+        assert_eq!(small_blind(&state), SMALL_BLIND * 2);
+
+        assert!(reset_idle_room(&mut state));
+        assert_eq!(state.config.small_blind(), SMALL_BLIND);
+        add_players(&mut state);
+        start_game(&mut state).unwrap();
+        assert_eq!(state.blind_level, 0);
+        assert_eq!(state.round.pot, SMALL_BLIND + BIG_BLIND);
+    }
 
-        assert_eq!(cards_on_table(&state).len(), 0);
+    #[test]
+    fn game_rejects_duplicate_player_names() {
+        let mut state = state::State::default();
+        fixtures::add_player(&mut state, "Alice").unwrap();
 
-        accept_player_bet(&mut state, &player_3, P::Call).unwrap();
-        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
-        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
+        let err = fixtures::add_player(&mut state, "alice").unwrap_err();
+        assert_eq!(err, "Name is already taken");
+        fixtures::add_player(&mut state, " Alice ").unwrap_err();
+        fixtures::add_player(&mut state, "alice2").unwrap();
+        assert_eq!(state.players.len(), 2);
+    }
 
-        assert_eq!(cards_on_table(&state).len(), 3);
+    #[test]
+    fn game_cannot_start_until_all_players_are_ready() {
+        let mut state = state::State::default();
+        let player_1 = add_new_player(&mut state, "player_1", state::PlayerId::default()).unwrap();
+        let player_2 = add_new_player(&mut state, "player_2", state::PlayerId::default()).unwrap();
 
-        accept_player_bet(&mut state, &player_1, P::Check).unwrap();
-        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
-        accept_player_bet(&mut state, &player_3, P::Check).unwrap();
+        set_player_ready(&mut state, &player_1).unwrap();
+        set_player_ready(&mut state, &player_1).unwrap_err();
+        assert_eq!(ready_count(&state), 1);
+        start_game(&mut state).unwrap_err();
+        assert_eq!(state.status, state::GameStatus::Joining);
 
-        assert_eq!(cards_on_table(&state).len(), 4);
+        set_player_ready(&mut state, &player_2).unwrap();
+        assert_eq!(ready_count(&state), 2);
+        start_game(&mut state).unwrap();
+        assert_eq!(state.status, state::GameStatus::Playing);
+    }
 
-        accept_player_bet(&mut state, &player_1, P::Check).unwrap();
-        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
-        accept_player_bet(&mut state, &player_3, P::Check).unwrap();
+    #[test]
+    fn game_can_be_forced_to_start_before_players_are_ready() {
+        let mut state = state::State::default();
+        add_new_player(&mut state, "player_1", state::PlayerId::default()).unwrap();
+        add_new_player(&mut state, "player_2", state::PlayerId::default()).unwrap();
 
-        assert_eq!(cards_on_table(&state).len(), 5);
+        start_game(&mut state).unwrap_err();
+        force_start_game(&mut state).unwrap();
+        assert_eq!(state.status, state::GameStatus::Playing);
+    }
 
-        accept_player_bet(&mut state, &player_1, P::Check).unwrap();
-        accept_player_bet(&mut state, &player_2, P::Check).unwrap();
-        accept_player_bet(&mut state, &player_3, P::RaiseTo(200)).unwrap();
-        accept_player_bet(&mut state, &player_1, P::Call).unwrap();
-        accept_player_bet(&mut state, &player_2, P::Call).unwrap();
+    #[test]
+    fn two_player_game_cannot_start_with_player_sitting_out() {
+        let (mut state, (_, player_2)) = fixtures::start_two_player_game(GameFixture::Round1);
+        state.status = state::GameStatus::Complete;
+        set_player_sitting_out(&mut state, &player_2, true).unwrap();
 
-        assert_eq!(state.status, state::GameStatus::Complete);
+        start_game(&mut state).unwrap_err();
     }
 
     mod fixtures {
@@ -1647,23 +5594,24 @@ mod tests {
                 return;
             }
 
-            accept_player_bet(state, &first_player, P::Check).unwrap();
+            // heads-up, the dealer acts first preflop but last after the flop
             accept_player_bet(state, &second_player, P::Check).unwrap();
+            accept_player_bet(state, &first_player, P::Check).unwrap();
             assert_eq!(cards_on_table(&state).len(), 4);
             if game_fixture == GameFixture::Round3 {
                 return;
             }
 
-            accept_player_bet(state, &first_player, P::Check).unwrap();
             accept_player_bet(state, &second_player, P::Check).unwrap();
+            accept_player_bet(state, &first_player, P::Check).unwrap();
 
             assert_eq!(cards_on_table(&state).len(), 5);
             if game_fixture == GameFixture::Round4 {
                 return;
             }
 
-            accept_player_bet(state, &first_player, P::Check).unwrap();
             accept_player_bet(state, &second_player, P::Check).unwrap();
+            accept_player_bet(state, &first_player, P::Check).unwrap();
 
             assert_eq!(state.status, state::GameStatus::Complete);
             if game_fixture == GameFixture::Complete {
@@ -1710,10 +5658,10 @@ mod tests {
 
             // higher value cards first
             let winner = state.players.get_mut(winner).unwrap();
-            winner.cards = (deck.pop(), deck.pop());
+            winner.cards = vec![deck.pop(), deck.pop()];
             // then lower value cards
             let loser = state.players.get_mut(loser).unwrap();
-            loser.cards = (deck.pop(), deck.pop());
+            loser.cards = vec![deck.pop(), deck.pop()];
 
             // set the round deck
             state.config = state.config.clone().with_card_deal_disabled();
@@ -1725,7 +5673,9 @@ mod tests {
             player_name: &str,
         ) -> Result<state::PlayerId, String> {
             let player_id = state::PlayerId::default();
-            super::add_new_player(state, player_name, player_id)
+            let player_id = super::add_new_player(state, player_name, player_id)?;
+            super::set_player_ready(state, &player_id)?;
+            Ok(player_id)
         }
     }
 }
@@ -20,6 +20,10 @@ pub fn create_application(state: state::SharedState) -> axum::Router {
         .route_layer(middleware::from_fn(layer::add_anonymous_player_id))
         .route_layer(middleware::from_fn(layer::track_router_metrics))
         .route("/health", axum::routing::get(|| async { "ok" }))
+        .route(
+            "/health/detailed",
+            axum::routing::get(routes::health_detailed).with_state(state.clone()),
+        )
         .nest_api_service("/docs", doc_routes::docs_routes(state.clone()))
         .nest_api_service("/metrics", metric_routes())
         .finish_api_with(&mut api, api_docs)
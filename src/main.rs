@@ -15,8 +15,22 @@ async fn main() {
     aide::gen::extract_schemas(true);
 
     // initialize state
-    let state = state::SharedState::default();
-    game::spawn_game_worker(state.clone());
+    let mut state = state::SharedState::default();
+    if let Some(max_rooms) = max_rooms() {
+        info!("Limiting the server to {} rooms", max_rooms);
+        state = state.with_max_rooms(max_rooms);
+    }
+    let mut config = state::config::RoomConfig::default();
+    if let Some(seed) = deck_seed() {
+        info!("Dealing from decks seeded with {}", seed);
+        config = config.with_deck_seed(seed);
+    }
+    if require_session_secret() {
+        info!("Requiring session secrets to fetch player cards");
+        config = config.with_session_secret_required();
+    }
+    state.set_default_config(config);
+    let (worker, shutdown) = game::spawn_game_worker(state.clone());
 
     // build our application with a route
     let app = flop_server::create_application(state);
@@ -30,8 +44,13 @@ async fn main() {
     info!("Example docs are accessible at {}", docs_url);
 
     axum::serve(listener, app.into_make_service())
+        .with_graceful_shutdown(shutdown.signal())
         .await
         .unwrap();
+
+    shutdown.shutdown();
+    _ = worker.await;
+    info!("Server shut down");
 }
 
 fn api_port() -> u16 {
@@ -41,6 +60,22 @@ fn api_port() -> u16 {
         .unwrap_or(5000)
 }
 
+fn deck_seed() -> Option<u64> {
+    std::env::var("DECK_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+}
+
+fn require_session_secret() -> bool {
+    std::env::var("REQUIRE_SESSION_SECRET").as_deref() == Ok("true")
+}
+
+fn max_rooms() -> Option<usize> {
+    std::env::var("MAX_ROOMS")
+        .ok()
+        .and_then(|max_rooms| max_rooms.parse().ok())
+}
+
 fn docs_url(listener: std::net::SocketAddr) -> String {
     match listener {
         std::net::SocketAddr::V4(addr) if addr.ip().is_unspecified() => {
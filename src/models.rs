@@ -7,6 +7,8 @@ use crate::cards::{CardSuite, CardValue};
 pub(crate) struct JoinRequest {
     pub(crate) name: String,
     pub(crate) room_code: Option<String>,
+    pub(crate) password: Option<String>,
+    pub(crate) reservation: Option<String>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -14,12 +16,17 @@ pub(crate) struct JoinRequest {
 pub(crate) struct JoinResponse {
     pub(crate) id: String,
     pub(crate) room_code: String,
+    pub(crate) session_secret: String,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct NewRoomRequest {
     pub(crate) name: String,
+    pub(crate) password: Option<String>,
+    pub(crate) currency: Option<String>,
+    pub(crate) deck_cut: Option<usize>,
+    pub(crate) auto_start_seconds: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -33,6 +40,7 @@ pub(crate) struct ResumeRequest {
 pub(crate) struct ResumeResponse {
     pub(crate) id: String,
     pub(crate) name: String,
+    pub(crate) session_secret: String,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -40,12 +48,15 @@ pub(crate) struct ResumeResponse {
 pub(crate) struct NewRoomResponse {
     pub(crate) id: String,
     pub(crate) room_code: String,
+    pub(crate) session_secret: String,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct CloseRoomRequest {
     pub(crate) room_code: Option<String>,
+    /// Start the game even if some players are not ready.
+    pub(crate) force: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -54,21 +65,56 @@ pub(crate) struct PeekRoomRequest {
     pub(crate) room_code: String,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReserveSeatRequest {
+    pub(crate) room_code: String,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReserveSeatResponse {
+    pub(crate) reservation: String,
+    pub(crate) expires_dt: u64,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct KnockRequest {
+    pub(crate) room_code: String,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RoomExistsResponse {
+    pub(crate) exists: bool,
+    pub(crate) joinable: bool,
+}
+
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct PeekRoomResponse {
     pub(crate) state: GamePhase,
     pub(crate) players_count: usize,
+    pub(crate) password_required: bool,
     pub(crate) can_resume: bool,
     pub(crate) resume_player_name: Option<String>,
 }
 
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ResumableSessions {
+    pub(crate) names: Vec<String>,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct PlayRequest {
     pub(crate) player_id: String,
     pub(crate) stake: u64,
     pub(crate) action: PlayAction,
+    /// Client generated UUID, a retried request with the same id is not applied twice.
+    pub(crate) action_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -76,10 +122,52 @@ pub(crate) struct PlayRequest {
 pub(crate) enum PlayAction {
     Check,
     Call,
+    BetTo,
     RaiseTo,
+    /// Raise by a fraction of the pot on top of the call, ignoring `stake`.
+    #[serde(rename_all = "camelCase")]
+    RaisePotFraction {
+        numerator: u64,
+        denominator: u64,
+    },
+    AllIn,
+    Fold,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QueueActionRequest {
+    pub(crate) action: QueuedAction,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum QueuedAction {
+    CheckFold,
     Fold,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StartVoteRequest {
+    pub(crate) player_id: String,
+    pub(crate) motion: VoteMotion,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum VoteMotion {
+    DoubleBlinds,
+    KickPlayer(String),
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CastVoteRequest {
+    pub(crate) player_id: String,
+    pub(crate) approve: bool,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct PlayerSendRequest {
@@ -99,6 +187,13 @@ pub(crate) struct PlayerAccount {
     pub(crate) account_id: String,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AdjustBalanceRequest {
+    pub(crate) player_id: String,
+    pub(crate) delta: i64,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct TransferRequest {
@@ -116,6 +211,8 @@ pub(crate) struct PairRequest {
 #[derive(Debug, Deserialize, Clone, schemars::JsonSchema)]
 pub struct PollQuery {
     pub since: Option<u64>,
+    /// How long to wait for an update in milliseconds, clamped to between
+    /// 100 and 30,000. Defaults to 5,000.
     pub timeout: Option<u64>,
 }
 
@@ -124,7 +221,7 @@ pub struct PollQuery {
 pub(crate) struct GamePlayerState {
     pub(crate) state: GamePhase,
     pub(crate) balance: u64,
-    pub(crate) cards: ((CardSuite, CardValue), (CardSuite, CardValue)),
+    pub(crate) cards: Vec<(CardSuite, CardValue)>,
     pub(crate) your_turn: bool,
     pub(crate) call_amount: u64,
     pub(crate) min_raise_to: u64,
@@ -132,6 +229,73 @@ pub(crate) struct GamePlayerState {
     pub(crate) turn_expires_dt: Option<u64>,
     pub(crate) last_update: u64,
     pub(crate) current_round_stake: u64,
+    pub(crate) session_net: i64,
+    pub(crate) pot: u64,
+    /// The share of the pot the player must put in to call, if they owe anything.
+    pub(crate) pot_odds: Option<f64>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HealthStatus {
+    pub(crate) worker_last_tick: u64,
+    pub(crate) room_count: usize,
+    pub(crate) uptime_ms: u64,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PlayerActions {
+    pub(crate) check: bool,
+    pub(crate) call: bool,
+    pub(crate) raise: bool,
+    pub(crate) fold: bool,
+    pub(crate) min_raise_to: u64,
+    pub(crate) max_raise_to: u64,
+}
+
+/// Approximate chances of the player's hand, as percentages.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PlayerEquity {
+    pub(crate) win: f64,
+    pub(crate) tie: f64,
+    pub(crate) lose: f64,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChatRequest {
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RoomChat {
+    pub(crate) messages: Vec<ChatMessage>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChatMessage {
+    pub(crate) name: String,
+    pub(crate) message: String,
+    pub(crate) sent_dt: u64,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ErrorResponse {
+    pub(crate) error: String,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PlayerStats {
+    pub(crate) hands_played: u64,
+    pub(crate) hands_won: u64,
+    pub(crate) biggest_pot: u64,
+    pub(crate) balance: u64,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -143,8 +307,17 @@ pub(crate) struct GameClientRoom {
     pub(crate) cards: Vec<(CardSuite, CardValue)>,
     pub(crate) completed: Option<CompletedGame>,
     pub(crate) ticker: Option<String>,
+    pub(crate) ticker_items: Vec<TickerEntry>,
     pub(crate) room_code: Option<String>,
     pub(crate) pair_screen_code: Option<String>,
+    pub(crate) dealer_index: usize,
+    pub(crate) ready_count: usize,
+    pub(crate) blind_level: usize,
+    pub(crate) small_blind: u64,
+    pub(crate) spectator_count: usize,
+    pub(crate) next_hand_at: Option<u64>,
+    /// SHA-256 commitment to this hand's deck, see `CompletedGame::deck_reveal`.
+    pub(crate) deck_commitment: Option<String>,
     pub(crate) last_update: u64,
 }
 
@@ -153,7 +326,23 @@ pub(crate) struct GameClientRoom {
 pub(crate) struct CompletedGame {
     pub(crate) winner_name: Option<String>,
     pub(crate) winning_hand: Option<String>,
-    pub(crate) player_cards: Vec<Option<((CardSuite, CardValue), (CardSuite, CardValue))>>,
+    pub(crate) tiebreak_cards: Option<Vec<CardValue>>,
+    /// The second runout when the hand was run twice, the first is the room's `cards`.
+    pub(crate) second_board: Option<Vec<(CardSuite, CardValue)>>,
+    pub(crate) player_cards: Vec<Option<Vec<(CardSuite, CardValue)>>>,
+    pub(crate) rake_taken: u64,
+    pub(crate) deck_reveal: Option<DeckReveal>,
+}
+
+/// The seed and deck behind a hand's deck commitment. Hashing the seed, a
+/// colon, then the deck serialized as compact JSON gives the commitment. The
+/// deck shows every hole card, so it is left out for spectators and whenever
+/// a hand was folded, mucked or hidden.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeckReveal {
+    pub(crate) seed: String,
+    pub(crate) deck: Vec<(CardSuite, CardValue)>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -162,10 +351,143 @@ pub(crate) struct GameClientPlayer {
     pub(crate) name: String,
     pub(crate) balance: u64,
     pub(crate) folded: bool,
+    pub(crate) all_in: bool,
+    pub(crate) sitting_out: bool,
+    pub(crate) ready: bool,
     pub(crate) emoji: Option<String>,
     pub(crate) photo: Option<String>,
     pub(crate) color_hue: u16,
     pub(crate) turn_expires_dt: Option<u64>,
+    pub(crate) online: bool,
+}
+
+/// A ticker item, as carried by the legacy `ticker` string without the
+/// delimiters.
+#[derive(Debug, Serialize, PartialEq, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TickerEntry {
+    pub(crate) seq: usize,
+    /// Milliseconds from now until the item shows, negative once showing.
+    pub(crate) start_offset_ms: i64,
+    pub(crate) duration_ms: u64,
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RoomConfig {
+    pub(crate) small_blind: u64,
+    pub(crate) big_blind: u64,
+    pub(crate) ante: u64,
+    pub(crate) starting_balance: u64,
+    pub(crate) max_players: usize,
+    pub(crate) turn_timeout_seconds: u64,
+    pub(crate) variant: GameVariant,
+}
+
+#[derive(Debug, Serialize, PartialEq, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum GameVariant {
+    Standard,
+    ShortDeck,
+    Omaha,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RoomDebug {
+    pub(crate) deck: Vec<(CardSuite, CardValue)>,
+    pub(crate) players: Vec<DebugPlayer>,
+    pub(crate) board: Vec<(CardSuite, CardValue)>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RoomList {
+    pub(crate) rooms: Vec<RoomSummary>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RoomSummary {
+    pub(crate) room_code: String,
+    pub(crate) state: GamePhase,
+    pub(crate) player_count: usize,
+    pub(crate) last_update: u64,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DebugPlayer {
+    pub(crate) name: String,
+    pub(crate) cards: Vec<(CardSuite, CardValue)>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HandHistory {
+    pub(crate) actions: Vec<HandHistoryAction>,
+    pub(crate) board: Vec<(CardSuite, CardValue)>,
+    pub(crate) second_board: Option<Vec<(CardSuite, CardValue)>>,
+    pub(crate) showdown: Vec<ShowdownHand>,
+    pub(crate) winners: Vec<HandHistoryWinner>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HandHistoryAction {
+    pub(crate) name: Option<String>,
+    pub(crate) street: Street,
+    pub(crate) action: HandActionKind,
+    pub(crate) amount: u64,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ShowdownHand {
+    pub(crate) name: Option<String>,
+    pub(crate) cards: Vec<(CardSuite, CardValue)>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HandHistoryWinner {
+    pub(crate) name: Option<String>,
+    pub(crate) winnings: u64,
+    pub(crate) hand: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LoggedEvent {
+    pub(crate) timestamp: u64,
+    pub(crate) event: &'static str,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Serialize, PartialEq, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+#[derive(Debug, Serialize, PartialEq, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum HandActionKind {
+    Ante,
+    SmallBlind,
+    BigBlind,
+    BringIn,
+    Straddle,
+    Check,
+    Call,
+    BetTo,
+    RaiseTo,
+    AllIn,
+    Fold,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -175,46 +497,56 @@ pub(crate) enum GamePhase {
     Offline,
     Idle,
     Waiting,
+    Paused,
     Playing,
     Complete,
 }
 
 pub mod headers {
-    pub(crate) struct RoomCodeHeader(pub(crate) String);
+    macro_rules! string_header {
+        ($header:ident, $name:literal) => {
+            pub(crate) struct $header(pub(crate) String);
 
-    static ROOM_CODE_HEADER_NAME: std::sync::OnceLock<axum::http::HeaderName> =
-        std::sync::OnceLock::new();
+            impl From<$header> for String {
+                fn from(header: $header) -> String {
+                    header.0
+                }
+            }
 
-    impl Into<String> for RoomCodeHeader {
-        fn into(self) -> String {
-            self.0
-        }
-    }
+            impl headers::Header for $header {
+                fn name() -> &'static axum::http::HeaderName {
+                    static NAME: std::sync::OnceLock<axum::http::HeaderName> =
+                        std::sync::OnceLock::new();
+                    NAME.get_or_init(|| axum::http::HeaderName::from_static($name))
+                }
+
+                fn decode<'i, I>(values: &mut I) -> Result<Self, headers::Error>
+                where
+                    Self: Sized,
+                    I: Iterator<Item = &'i axum::http::HeaderValue>,
+                {
+                    let value = values
+                        .next()
+                        .ok_or_else(|| headers::Error::invalid())?
+                        .to_str()
+                        .map_err(|_| headers::Error::invalid())?;
 
-    impl headers::Header for RoomCodeHeader {
-        fn name() -> &'static axum::http::HeaderName {
-            ROOM_CODE_HEADER_NAME.get_or_init(|| axum::http::HeaderName::from_static("room-code"))
-        }
-
-        fn decode<'i, I>(values: &mut I) -> Result<Self, headers::Error>
-        where
-            Self: Sized,
-            I: Iterator<Item = &'i axum::http::HeaderValue>,
-        {
-            let value = values
-                .next()
-                .ok_or_else(|| headers::Error::invalid())?
-                .to_str()
-                .map_err(|_| headers::Error::invalid())?;
-
-            Ok(Self(value.to_string()))
-        }
-
-        fn encode<E: Extend<axum::http::HeaderValue>>(&self, values: &mut E) {
-            match axum::http::HeaderValue::from_str(&self.0) {
-                Ok(value) => values.extend(std::iter::once(value)),
-                Err(_) => values.extend(std::iter::once(axum::http::HeaderValue::from_static(""))),
+                    Ok(Self(value.to_string()))
+                }
+
+                fn encode<E: Extend<axum::http::HeaderValue>>(&self, values: &mut E) {
+                    match axum::http::HeaderValue::from_str(&self.0) {
+                        Ok(value) => values.extend(std::iter::once(value)),
+                        Err(_) => {
+                            values.extend(std::iter::once(axum::http::HeaderValue::from_static("")))
+                        }
+                    }
+                }
             }
-        }
+        };
     }
+
+    string_header!(RoomCodeHeader, "room-code");
+    string_header!(AdminTokenHeader, "admin-token");
+    string_header!(SessionSecretHeader, "session-secret");
 }
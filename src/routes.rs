@@ -1,4 +1,7 @@
-use std::sync::{Arc, OnceLock};
+use std::{
+    convert::Infallible,
+    sync::{Arc, OnceLock},
+};
 
 use crate::{
     app_metrics::{metrics_labels, Metrics},
@@ -13,27 +16,152 @@ use aide::axum::{
 use autometrics::autometrics;
 use axum::{
     body,
-    extract::{Multipart, Path, Query, State},
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Multipart, Path, Query, State,
+    },
     http::{header, HeaderValue, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     Extension, Json,
 };
 use axum_extra::TypedHeader;
+use futures_util::{stream, Stream};
 use tracing::info;
 
-type JsonResult<T> = Result<Json<T>, StatusCode>;
+type JsonResult<T> = Result<Json<T>, ApiError>;
+
+const JPEG_MAGIC_BYTES: [u8; 3] = [0xFF, 0xD8, 0xFF];
+
+/// A rejected request, responding with a JSON body describing the reason.
+#[derive(Debug)]
+pub(crate) struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn bad_request(error: impl Into<String>) -> Self {
+        Self(StatusCode::BAD_REQUEST, error.into())
+    }
+}
+
+impl From<StatusCode> for ApiError {
+    fn from(status: StatusCode) -> Self {
+        let error = status.canonical_reason().unwrap_or_default();
+        Self(status, error.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let ApiError(status, error) = self;
+        (status, Json(models::ErrorResponse { error })).into_response()
+    }
+}
+
+impl aide::OperationOutput for ApiError {
+    type Inner = models::ErrorResponse;
+}
 
 pub(crate) fn api_routes(state: state::SharedState) -> ApiRouter {
     ApiRouter::new()
+        .api_route("/rooms", get_with(rooms, docs::rooms))
         .api_route("/room", get_with(room, docs::room))
+        .api_route(
+            "/room/:room_code/exists",
+            get_with(room_exists, docs::room_exists),
+        )
+        .api_route(
+            "/room/:room_code/spectate",
+            get_with(spectate_room, docs::spectate_room),
+        )
+        .route("/room/stream", axum::routing::get(room_stream))
+        .api_route("/room/chat", get_with(room_chat, docs::room_chat))
+        .api_route("/room/history", get_with(room_history, docs::room_history))
+        .api_route("/room/export", get_with(room_export, docs::room_export))
+        .api_route("/room/config", get_with(room_config, docs::room_config))
+        .api_route("/room/debug", get_with(room_debug, docs::room_debug))
         .api_route("/room/peek", post_with(peek_room, docs::peek_room))
+        .api_route(
+            "/room/resumable",
+            get_with(resumable_sessions, docs::resumable_sessions),
+        )
+        .api_route("/room/knock", post_with(knock_room, docs::knock_room))
+        .api_route("/room/reserve", post_with(reserve_seat, docs::reserve_seat))
         .api_route("/room/close", post_with(close_room, docs::close_room))
         .api_route("/room/reset", post_with(reset_room, docs::reset_room))
+        .api_route(
+            "/room/adjust",
+            post_with(adjust_balance, docs::adjust_balance),
+        )
+        .api_route(
+            "/room/force-advance",
+            post_with(force_advance_room, docs::force_advance_room),
+        )
+        .api_route(
+            "/room/reshuffle",
+            post_with(reshuffle_room, docs::reshuffle_room),
+        )
+        .api_route("/room/rematch", post_with(rematch_room, docs::rematch_room))
         .api_route("/pair", post_with(pair, docs::pair))
         .api_route("/player/:player_id", get_with(player, docs::player))
+        .route("/player/:player_id/ws", axum::routing::get(player_ws))
+        .api_route(
+            "/player/:player_id/actions",
+            get_with(player_actions, docs::player_actions),
+        )
+        .api_route(
+            "/player/:player_id/equity",
+            get_with(player_equity, docs::player_equity),
+        )
+        .api_route(
+            "/player/:player_id/stats",
+            get_with(player_stats, docs::player_stats),
+        )
         .api_route(
             "/player/:player_id/leave",
             post_with(player_leave, docs::player_leave),
         )
+        .api_route(
+            "/player/:player_id/sit-out",
+            post_with(player_sit_out, docs::player_sit_out),
+        )
+        .api_route(
+            "/player/:player_id/sit-in",
+            post_with(player_sit_in, docs::player_sit_in),
+        )
+        .api_route(
+            "/player/:player_id/ready",
+            post_with(player_ready, docs::player_ready),
+        )
+        .api_route(
+            "/player/:player_id/rebuy",
+            post_with(player_rebuy, docs::player_rebuy),
+        )
+        .api_route(
+            "/player/:player_id/muck",
+            post_with(player_muck, docs::player_muck),
+        )
+        .api_route(
+            "/player/:player_id/show",
+            post_with(player_show, docs::player_show),
+        )
+        .api_route(
+            "/player/:player_id/straddle",
+            post_with(player_straddle, docs::player_straddle),
+        )
+        .api_route(
+            "/player/:player_id/timebank",
+            post_with(player_timebank, docs::player_timebank),
+        )
+        .api_route(
+            "/player/:player_id/queue-action",
+            post_with(player_queue_action, docs::player_queue_action),
+        )
+        .api_route(
+            "/player/:player_id/chat",
+            post_with(player_chat, docs::player_chat),
+        )
         .api_route(
             "/player/:player_id/send",
             post_with(player_send, docs::player_send),
@@ -55,6 +183,8 @@ pub(crate) fn api_routes(state: state::SharedState) -> ApiRouter {
         .api_route("/join", post_with(join, docs::join))
         .api_route("/resume", post_with(resume, docs::resume))
         .api_route("/play", post_with(play, docs::play))
+        .api_route("/vote/start", post_with(start_vote, docs::start_vote))
+        .api_route("/vote/cast", post_with(cast_vote, docs::cast_vote))
         .with_state(state)
 }
 
@@ -71,7 +201,7 @@ pub(crate) async fn room(
     let room_code = match utils::wait_by_room_code(&state, query.clone(), room_code).await {
         Ok(room_code) => Some(room_code),
         Err(StatusCode::NOT_FOUND) => None,
-        Err(status) => return Err(status),
+        Err(status) => return Err(status.into()),
     };
     let state = match &room_code {
         Some(room_code) => state
@@ -87,7 +217,11 @@ pub(crate) async fn room(
             .clone(),
     };
 
+    let now = state::dt::Instant::default();
     let state = state.read().await;
+    if room_code.is_some() {
+        game::record_spectator(&state, &apid, now);
+    }
     let (room_code, pair_screen_code) = match state.status {
         state::GameStatus::Idle => utils::wait_by_screen_apid(&shared_state, query, &apid)
             .await
@@ -95,46 +229,345 @@ pub(crate) async fn room(
         _ => (room_code, None),
     };
 
-    let game_client_state = models::GameClientRoom {
-        state: game::game_phase(&state),
-        players: game::room_players(&state),
-        pot: state.round.pot,
-        cards: game::cards_on_table(&state),
-        completed: game::completed_game(&state),
-        ticker: game::ticker(&state),
-        room_code: room_code.map(|r| r.to_string()),
-        pair_screen_code: pair_screen_code.map(|c| c.to_string()),
-        last_update: state.last_update.as_u64(),
-    };
+    let game_client_state = game::room_view(
+        &state,
+        room_code.map(|r| r.to_string()),
+        pair_screen_code.map(|c| c.to_string()),
+        now,
+    );
 
     Ok(Json(game_client_state))
 }
 
+/// Streams a room event each time the room state changes, as an alternative to
+/// long-polling `GET /room`.
+pub(crate) async fn room_stream(
+    State(state): State<SharedState>,
+    room_code: Option<TypedHeader<models::headers::RoomCodeHeader>>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
+    let query = models::PollQuery {
+        since: None,
+        timeout: None,
+    };
+    let room_code = utils::wait_by_room_code(&state, query, room_code).await?;
+    let state = state
+        .get_room(&room_code)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    info!("Room {} stream opened", room_code.to_string());
+
+    let events = stream::unfold(None, move |since: Option<state::dt::Instant>| {
+        let (state, room_code) = (state.clone(), room_code.to_string());
+        async move {
+            if let Some(since) = since {
+                let rx = {
+                    let state = state.read().await;
+                    state.last_update.wait_for(since)
+                };
+                rx.await?;
+            }
+
+            let state = state.read().await;
+            let game_client_state =
+                game::room_view(&state, Some(room_code), None, state::dt::Instant::default());
+
+            let event = Event::default().json_data(&game_client_state).ok()?;
+            Some((Ok(event), Some(state.last_update.as_u64().into())))
+        }
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn room_chat(
+    State(state): State<SharedState>,
+    room_code: Option<TypedHeader<models::headers::RoomCodeHeader>>,
+) -> JsonResult<models::RoomChat> {
+    let room_code = room_code.map(|TypedHeader(room_code)| room_code.into());
+    let state = utils::query_room_state(&state, room_code).await?;
+    let state = state.read().await;
+
+    let messages = state
+        .chat
+        .iter()
+        .map(|chat| models::ChatMessage {
+            name: chat.name.clone(),
+            message: chat.message.clone(),
+            sent_dt: chat.sent.into(),
+        })
+        .collect();
+
+    Ok(Json(models::RoomChat { messages }))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn room_history(
+    State(state): State<SharedState>,
+    room_code: Option<TypedHeader<models::headers::RoomCodeHeader>>,
+) -> JsonResult<models::HandHistory> {
+    let room_code = room_code.map(|TypedHeader(room_code)| room_code.into());
+    let state = utils::query_room_state(&state, room_code).await?;
+    let state = state.read().await;
+
+    let history = game::hand_history(&state).ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(history))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn room_export(
+    State(state): State<SharedState>,
+    room_code: Option<TypedHeader<models::headers::RoomCodeHeader>>,
+) -> Result<(header::HeaderMap, String), StatusCode> {
+    let room_code = room_code.map(|TypedHeader(room_code)| room_code.into());
+    let state = utils::query_room_state(&state, room_code).await?;
+    let state = state.read().await;
+
+    let mut headers = header::HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/x-ndjson"),
+    );
+
+    Ok((headers, game::event_log(&state)))
+}
+
+/// Reports 503 when the game worker has not ticked recently.
+pub(crate) async fn health_detailed(
+    State(state): State<SharedState>,
+) -> (StatusCode, Json<models::HealthStatus>) {
+    let now = state::dt::Instant::default().as_u64();
+    let last_tick = state.last_worker_tick().as_u64();
+    let status = match now.saturating_sub(last_tick) {
+        elapsed if elapsed > state::WORKER_TICK_TIMEOUT_SECONDS * 1000 => {
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+        _ => StatusCode::OK,
+    };
+
+    let health = models::HealthStatus {
+        worker_last_tick: last_tick,
+        room_count: state.room_count(),
+        uptime_ms: now.saturating_sub(state.started().as_u64()),
+    };
+    (status, Json(health))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn rooms(
+    State(state): State<SharedState>,
+    admin_token: Option<TypedHeader<models::headers::AdminTokenHeader>>,
+) -> JsonResult<models::RoomList> {
+    utils::validate_admin_token(admin_token.map(|TypedHeader(token)| token.into()))?;
+
+    let mut rooms = Vec::new();
+    for (room_code, room) in state.iter_key_values().await {
+        let room = room.read().await;
+        if !room.disposed {
+            rooms.push(game::room_summary(&room_code, &room));
+        }
+    }
+
+    Ok(Json(models::RoomList { rooms }))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn room_config(
+    State(state): State<SharedState>,
+    room_code: Option<TypedHeader<models::headers::RoomCodeHeader>>,
+) -> JsonResult<models::RoomConfig> {
+    let room_code = room_code.map(|TypedHeader(room_code)| room_code.into());
+    let state = utils::query_room_state(&state, room_code).await?;
+    let state = state.read().await;
+
+    Ok(Json(game::room_config(&state)))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn room_debug(
+    State(state): State<SharedState>,
+    room_code: Option<TypedHeader<models::headers::RoomCodeHeader>>,
+) -> JsonResult<models::RoomDebug> {
+    if std::env::var("ENABLE_DEBUG_ROUTES").as_deref() != Ok("true") {
+        return Err(StatusCode::NOT_FOUND.into());
+    }
+
+    let room_code = room_code.map(|TypedHeader(room_code)| room_code.into());
+    let state = utils::query_room_state(&state, room_code).await?;
+    let state = state.read().await;
+
+    Ok(Json(game::room_debug(&state)))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn spectate_room(
+    State(state): State<SharedState>,
+    Path(room_code): Path<String>,
+    Query(query): Query<models::PollQuery>,
+) -> JsonResult<models::GameClientRoom> {
+    let state = utils::query_room_state(&state, Some(room_code.clone())).await?;
+    utils::wait_for_update(&state, query).await;
+    let state = state.read().await;
+
+    let room = game::room_view(
+        &state,
+        Some(room_code.to_ascii_uppercase()),
+        None,
+        state::dt::Instant::default(),
+    );
+    let players = room
+        .players
+        .into_iter()
+        .map(|player| models::GameClientPlayer {
+            turn_expires_dt: None,
+            ..player
+        })
+        .collect();
+    let completed = room.completed.map(|completed| models::CompletedGame {
+        player_cards: completed.player_cards.iter().map(|_| None).collect(),
+        tiebreak_cards: None,
+        deck_reveal: None,
+        ..completed
+    });
+
+    Ok(Json(models::GameClientRoom {
+        players,
+        completed,
+        ..room
+    }))
+}
+
 #[autometrics(ok_if = metrics::is_success)]
 pub(crate) async fn player(
     State(state): State<SharedState>,
     Path(player_id): Path<String>,
     Query(query): Query<models::PollQuery>,
+    session_secret: Option<TypedHeader<models::headers::SessionSecretHeader>>,
 ) -> JsonResult<models::GamePlayerState> {
+    let session_secret: Option<String> = session_secret.map(|TypedHeader(secret)| secret.into());
+    utils::validate_session_secret(&state, &player_id, session_secret.as_deref()).await?;
     let player = utils::wait_by_player_id(&state, query, &player_id).await?;
 
+    let room_state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let now = state::dt::Instant::default();
+
+    let state = room_state.read().await;
+    let game_player_state =
+        game::game_player_state(&state, &player.id).ok_or(StatusCode::NOT_FOUND)?;
+    let seen_stale = game::player_seen_stale(&state, &player.id, now);
+    drop(state);
+
+    if seen_stale {
+        game::record_player_seen(&mut *room_state.write().await, &player.id, now);
+    }
+
+    Ok(Json(game_player_state))
+}
+
+pub(crate) async fn player_ws(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+    session_secret: Option<TypedHeader<models::headers::SessionSecretHeader>>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, StatusCode> {
+    let session_secret: Option<String> = session_secret.map(|TypedHeader(secret)| secret.into());
+    let player =
+        utils::validate_session_secret(&state, &player_id, session_secret.as_deref()).await?;
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    info!("Player {} socket opened", player.id);
+
+    Ok(ws.on_upgrade(move |socket| player_socket(socket, state, player.id)))
+}
+
+async fn player_socket(mut socket: WebSocket, state: state::RoomState, player_id: state::PlayerId) {
+    let mut since: Option<state::dt::Instant> = None;
+    loop {
+        if let Some(since) = since {
+            let rx = {
+                let state = state.read().await;
+                state.last_update.wait_for(since)
+            };
+            tokio::select! {
+                changed = rx => if changed.is_none() {
+                    break;
+                },
+                message = socket.recv() => match message {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                },
+            }
+        }
+
+        let frame = {
+            let state = state.read().await;
+            game::game_player_state(&state, &player_id)
+        };
+        let Some(frame) = frame else {
+            info!("Player {} removed, closing socket", player_id);
+            break;
+        };
+        let Ok(text) = serde_json::to_string(&frame) else {
+            break;
+        };
+        if socket.send(Message::Text(text)).await.is_err() {
+            break;
+        }
+        since = Some(frame.last_update.into());
+    }
+
+    _ = socket.send(Message::Close(None)).await;
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn player_actions(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+) -> JsonResult<models::PlayerActions> {
+    let player = utils::validate_player(&player_id, &state).await?;
     let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
     let state = state.read().await;
 
-    let game_player_state = models::GamePlayerState {
-        state: game::game_phase(&state),
-        balance: player.balance,
-        cards: game::cards_in_hand(&state, &player.id).unwrap(),
-        your_turn: game::is_player_turn(&state, &player.id),
-        call_amount: game::call_amount(&state).unwrap_or(0),
-        min_raise_to: game::min_raise_to(&state),
-        players_count: state.players.len(),
-        turn_expires_dt: game::turn_expires_dt(&state, &player.id),
-        last_update: state.last_update.as_u64(),
-        current_round_stake: game::player_stake_in_round(&state, &player.id),
-    };
+    let actions = game::legal_actions(&state, &player.id).ok_or(StatusCode::NOT_FOUND)?;
 
-    Ok(Json(game_player_state))
+    Ok(Json(actions))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn player_equity(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+    session_secret: Option<TypedHeader<models::headers::SessionSecretHeader>>,
+) -> JsonResult<models::PlayerEquity> {
+    let session_secret: Option<String> = session_secret.map(|TypedHeader(secret)| secret.into());
+    let player =
+        utils::validate_session_secret(&state, &player_id, session_secret.as_deref()).await?;
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+
+    let hand = game::equity_hand(&*state.read().await, &player.id).map_err(|err| {
+        info!("Player {} failed to get equity: {}", player_id, err);
+        ApiError::bad_request(err)
+    })?;
+    let equity = tokio::task::spawn_blocking(move || game::simulate_equity(&hand))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(equity))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn player_stats(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+) -> JsonResult<models::PlayerStats> {
+    let player = utils::validate_player(&player_id, &state).await?;
+
+    Ok(Json(models::PlayerStats {
+        hands_played: player.stats.hands_played,
+        hands_won: player.stats.hands_won,
+        biggest_pot: player.stats.biggest_pot,
+        balance: player.balance,
+    }))
 }
 
 #[autometrics(ok_if = metrics::is_success)]
@@ -149,7 +582,7 @@ pub(crate) async fn player_leave(
 
     game::remove_player(&mut state, &player.id).map_err(|err| {
         info!("Player {} failed to leave: {}", player_id, err);
-        StatusCode::BAD_REQUEST
+        ApiError::bad_request(err)
     })?;
 
     shared_state.remove(&player.id).await;
@@ -160,6 +593,191 @@ pub(crate) async fn player_leave(
     Ok(Json(()))
 }
 
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn player_sit_out(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+) -> JsonResult<()> {
+    let player = utils::validate_player(&player_id, &state).await?;
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mut state = state.write().await;
+
+    game::set_player_sitting_out(&mut state, &player.id, true).map_err(|err| {
+        info!("Player {} failed to sit out: {}", player_id, err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+    info!("Player {} is sitting out", player_id);
+
+    Ok(Json(()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn player_sit_in(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+) -> JsonResult<()> {
+    let player = utils::validate_player(&player_id, &state).await?;
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mut state = state.write().await;
+
+    game::set_player_sitting_out(&mut state, &player.id, false).map_err(|err| {
+        info!("Player {} failed to sit in: {}", player_id, err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+    info!("Player {} is sitting back in", player_id);
+
+    Ok(Json(()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn player_ready(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+) -> JsonResult<()> {
+    let player = utils::validate_player(&player_id, &state).await?;
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mut state = state.write().await;
+
+    game::set_player_ready(&mut state, &player.id).map_err(|err| {
+        info!("Player {} failed to mark ready: {}", player_id, err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+    info!("Player {} is ready", player_id);
+
+    Ok(Json(()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn player_rebuy(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+) -> JsonResult<()> {
+    let player = utils::validate_player(&player_id, &state).await?;
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mut state = state.write().await;
+
+    game::rebuy_player(&mut state, &player.id).map_err(|err| {
+        info!("Player {} failed to rebuy: {}", player_id, err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+    info!("Player {} bought back in", player_id);
+
+    Ok(Json(()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn player_muck(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+) -> JsonResult<()> {
+    let player = utils::validate_player(&player_id, &state).await?;
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mut state = state.write().await;
+
+    game::set_player_muck(&mut state, &player.id).map_err(|err| {
+        info!("Player {} failed to muck: {}", player_id, err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+    info!("Player {} mucked their cards", player_id);
+
+    Ok(Json(()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn player_show(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+) -> JsonResult<()> {
+    let player = utils::validate_player(&player_id, &state).await?;
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mut state = state.write().await;
+
+    game::set_player_show(&mut state, &player.id).map_err(|err| {
+        info!("Player {} failed to show: {}", player_id, err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+    info!("Player {} showed their cards", player_id);
+
+    Ok(Json(()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn player_straddle(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+) -> JsonResult<()> {
+    let player = utils::validate_player(&player_id, &state).await?;
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mut state = state.write().await;
+
+    game::straddle(&mut state, &player.id).map_err(|err| {
+        info!("Player {} failed to straddle: {}", player_id, err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+    info!("Player {} posted a straddle", player_id);
+
+    Ok(Json(()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn player_timebank(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+) -> JsonResult<()> {
+    let player = utils::validate_player(&player_id, &state).await?;
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mut state = state.write().await;
+
+    game::use_time_bank(&mut state, &player.id).map_err(|err| {
+        info!("Player {} failed to use time bank: {}", player_id, err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+    info!("Player {} used their time bank", player_id);
+
+    Ok(Json(()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn player_queue_action(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+    Json(payload): Json<models::QueueActionRequest>,
+) -> JsonResult<()> {
+    let player = utils::validate_player(&player_id, &state).await?;
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mut state = state.write().await;
+
+    let action = match payload.action {
+        models::QueuedAction::CheckFold => state::QueuedAction::CheckFold,
+        models::QueuedAction::Fold => state::QueuedAction::Fold,
+    };
+    game::queue_player_action(&mut state, &player.id, action).map_err(|err| {
+        info!("Player {} failed to queue an action: {}", player_id, err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+    info!("Player {} queued {:?}", player_id, action);
+
+    Ok(Json(()))
+}
+
 #[autometrics(ok_if = metrics::is_success)]
 pub(crate) async fn player_send(
     State(state): State<SharedState>,
@@ -175,33 +793,53 @@ pub(crate) async fn player_send(
             "Player {} failed to send message: message is empty",
             player_id
         );
-        return Err(StatusCode::BAD_REQUEST);
-    }
-    use state::ticker::emoji::TickerEmoji;
-    let emoji = match payload.message.as_str() {
-        "👍" | ":+1:" => TickerEmoji::thumbs_up(),
-        "👎" | ":-1:" => TickerEmoji::thumbs_down(),
-        "👏" | ":clapping:" => TickerEmoji::clapping(),
-        "⏳" | ":time:" => TickerEmoji::time(),
-        "🤔" | ":thinking:" => TickerEmoji::thinking(),
-        "😂" | ":money:" => TickerEmoji::money(),
-        "😡" | ":angry:" => TickerEmoji::angry(),
-        _ => {
-            info!("Player {} failed to send message: invalid emoji", player_id);
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    };
-    state.players.get_mut(&player.id).unwrap().emoji =
-        Some((emoji.clone(), state::dt::Instant::default()));
-    state
-        .ticker
-        .emit(state::TickerEvent::PlayerSentEmoji(player.id, emoji));
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+    let emoji: state::ticker::emoji::TickerEmoji = payload.message.parse().map_err(|_| {
+        info!("Player {} failed to send message: invalid emoji", player_id);
+        StatusCode::BAD_REQUEST
+    })?;
+    game::send_emoji(&mut state, &player.id, emoji, state::dt::Instant::default()).map_err(
+        |err| {
+            info!("Player {} failed to send message: {}", player_id, err);
+            ApiError(StatusCode::TOO_MANY_REQUESTS, err)
+        },
+    )?;
 
     state.last_update.set_now();
     info!("Player {} sent message", player_id);
     Ok(Json(()))
 }
 
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn player_chat(
+    State(state): State<SharedState>,
+    Path(player_id): Path<String>,
+    Json(payload): Json<models::ChatRequest>,
+) -> JsonResult<()> {
+    let message = payload.message.trim();
+    if message.is_empty()
+        || message.chars().count() > state::CHAT_MESSAGE_MAX_LENGTH
+        || utils::has_hidden_chars(message)
+    {
+        info!("Player {} failed to chat: message is invalid", player_id);
+        return Err(ApiError::bad_request("Message is invalid"));
+    }
+
+    let player = utils::validate_player(&player_id, &state).await?;
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mut state = state.write().await;
+
+    game::send_chat_message(&mut state, &player.id, message).map_err(|err| {
+        info!("Player {} failed to chat: {}", player_id, err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+    info!("Player {} sent chat message", player_id);
+    Ok(Json(()))
+}
+
 #[autometrics(ok_if = metrics::is_success)]
 pub(crate) async fn get_player_transfer(
     State(state): State<SharedState>,
@@ -236,10 +874,13 @@ pub(crate) async fn post_player_transfer(
 
     if payload.amount == 0 {
         info!("Player {} failed to transfer: amount is zero", player_id);
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::bad_request("Amount cannot be zero"));
     }
 
-    game::transfer_funds(&mut state, &player.id, &payload).map_err(|_| StatusCode::BAD_REQUEST)?;
+    game::transfer_funds(&mut state, &player.id, &payload).map_err(|err| {
+        info!("Player {} failed to transfer: {}", player_id, err);
+        ApiError::bad_request(err)
+    })?;
 
     info!(
         "Player {} transferred {} to player {}",
@@ -254,9 +895,12 @@ pub(crate) async fn post_player_transfer(
 pub(crate) async fn get_player_photo(
     State(state): State<SharedState>,
     Path(token): Path<String>,
+    room_code: Option<TypedHeader<models::headers::RoomCodeHeader>>,
 ) -> Result<(header::HeaderMap, body::Bytes), StatusCode> {
-    // TODO: accept room code to prevent scanning all rooms
-    let state = {
+    let room_code = room_code.map(|TypedHeader(room_code)| room_code.into());
+    let state = if room_code.is_some() {
+        utils::query_room_state(&state, room_code).await?
+    } else {
         let mut matched = None;
         for room_state in state.iter().await {
             let state = room_state.read().await;
@@ -317,7 +961,7 @@ pub(crate) async fn post_player_photo(
     let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
     let player_id = player.id;
 
-    let field = multipart
+    let mut field = multipart
         .next_field()
         .await
         .map_err(|_| StatusCode::BAD_REQUEST)?
@@ -328,12 +972,31 @@ pub(crate) async fn post_player_photo(
             "Player {} failed to upload photo: invalid content type",
             player_id
         );
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
     }
     let name = field.name().unwrap().to_string();
-    let data = field.bytes().await.unwrap();
+
+    // read in chunks so an oversized upload is rejected before it is buffered
+    let max_photo_bytes = state.read().await.config.max_photo_bytes();
+    let mut data = Vec::new();
+    while let Some(chunk) = field
+        .chunk()
+        .await
+        .map_err(|err| ApiError(err.status(), err.body_text()))?
+    {
+        if data.len() + chunk.len() > max_photo_bytes {
+            info!("Player {} failed to upload photo: too large", player_id);
+            return Err(StatusCode::PAYLOAD_TOO_LARGE.into());
+        }
+        data.extend_from_slice(&chunk);
+    }
     let size = data.len();
 
+    if !data.starts_with(&JPEG_MAGIC_BYTES) {
+        info!("Player {} failed to upload photo: not a jpeg", player_id);
+        return Err(ApiError::bad_request("Photo must be a JPEG image"));
+    }
+
     let mut state = state.write().await;
     let player = state
         .players
@@ -341,7 +1004,7 @@ pub(crate) async fn post_player_photo(
         .ok_or(StatusCode::NOT_FOUND)?;
 
     let guid = state::token::Token::default();
-    player.photo = Some(state::PlayerPhoto(Arc::new(data), guid));
+    player.photo = Some(state::PlayerPhoto(Arc::new(data.into()), guid));
     state
         .ticker
         .emit(state::TickerEvent::PlayerPhotoUploaded(player_id.clone()));
@@ -361,10 +1024,31 @@ pub(crate) async fn play(
 ) -> JsonResult<()> {
     let player = utils::validate_player(&payload.player_id, &state).await?;
     let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let action_id = match &payload.action_id {
+        Some(action_id) => Some(
+            uuid::Uuid::try_parse(action_id)
+                .map_err(|_| ApiError::bad_request("Action id must be a UUID"))?,
+        ),
+        None => None,
+    };
     let mut state = state.write().await;
-    if let Err(err) = game::reset_ttl(&mut state, &player.id) {
+
+    let last_action_id = state.players.get(&player.id).and_then(|p| p.last_action_id);
+    if action_id.is_some() && last_action_id == action_id {
+        info!(
+            "Player {} repeated action {:?}, ignoring",
+            payload.player_id, action_id
+        );
+        return Ok(Json(()));
+    }
+
+    // acting out of turn or after the turn expired is a race with the other
+    // players rather than a bad request
+    if let Err(err) = game::check_players_turn(&state, &player.id)
+        .and_then(|_| game::reset_ttl(&mut state, &player.id))
+    {
         info!("Player {} failed to play: {}", payload.player_id, err);
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError(StatusCode::CONFLICT, err));
     }
 
     let result = match payload.action {
@@ -374,11 +1058,27 @@ pub(crate) async fn play(
         models::PlayAction::Call => {
             game::accept_player_bet(&mut state, &player.id, state::BetAction::Call)
         }
+        models::PlayAction::BetTo => game::accept_player_bet(
+            &mut state,
+            &player.id,
+            state::BetAction::BetTo(payload.stake),
+        ),
         models::PlayAction::RaiseTo => game::accept_player_bet(
             &mut state,
             &player.id,
             state::BetAction::RaiseTo(payload.stake),
         ),
+        models::PlayAction::RaisePotFraction {
+            numerator,
+            denominator,
+        } => game::pot_fraction_raise_to(&state, &player.id, numerator, denominator).and_then(
+            |raise_to| {
+                game::accept_player_bet(&mut state, &player.id, state::BetAction::RaiseTo(raise_to))
+            },
+        ),
+        models::PlayAction::AllIn => {
+            game::accept_player_bet(&mut state, &player.id, state::BetAction::AllIn)
+        }
         models::PlayAction::Fold => game::fold_player(&mut state, &player.id),
     };
 
@@ -387,11 +1087,67 @@ pub(crate) async fn play(
             "Player {} tried to play, but failed: {}",
             payload.player_id, err
         );
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::bad_request(err));
     }
 
+    if let Some(player) = state.players.get_mut(&player.id) {
+        player.last_action_id = action_id;
+    }
+
+    state.last_update.set_now();
+    info!("Player {} played round", payload.player_id);
+    Ok(Json(()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn start_vote(
+    State(state): State<SharedState>,
+    Json(payload): Json<models::StartVoteRequest>,
+) -> JsonResult<()> {
+    let player = utils::validate_player(&payload.player_id, &state).await?;
+    let shared_state = state.clone();
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mut state = state.write().await;
+
+    let motion = match payload.motion {
+        models::VoteMotion::DoubleBlinds => state::vote::Motion::DoubleBlinds,
+        models::VoteMotion::KickPlayer(player_id) => state::vote::Motion::KickPlayer(
+            player_id
+                .parse()
+                .map_err(|_| ApiError::bad_request("Invalid player id"))?,
+        ),
+    };
+
+    game::start_vote(&mut state, &player.id, motion.clone()).map_err(|err| {
+        info!("Player {} failed to start vote: {}", payload.player_id, err);
+        ApiError::bad_request(err)
+    })?;
+    game::unregister_kicked_player(&state, &shared_state, Some(motion)).await;
+
+    state.last_update.set_now();
+    info!("Player {} started a vote", payload.player_id);
+    Ok(Json(()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn cast_vote(
+    State(state): State<SharedState>,
+    Json(payload): Json<models::CastVoteRequest>,
+) -> JsonResult<()> {
+    let player = utils::validate_player(&payload.player_id, &state).await?;
+    let shared_state = state.clone();
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mut state = state.write().await;
+
+    let motion = state.vote.as_ref().map(|vote| vote.motion.clone());
+    game::cast_vote(&mut state, &player.id, payload.approve).map_err(|err| {
+        info!("Player {} failed to cast vote: {}", payload.player_id, err);
+        ApiError::bad_request(err)
+    })?;
+    game::unregister_kicked_player(&state, &shared_state, motion).await;
+
     state.last_update.set_now();
-    info!("Player {} played round", payload.player_id);
+    info!("Player {} cast a vote", payload.player_id);
     Ok(Json(()))
 }
 
@@ -401,18 +1157,38 @@ pub(crate) async fn join(
     Extension(layer::Apid(apid)): Extension<layer::Apid>,
     Json(payload): Json<models::JoinRequest>,
 ) -> JsonResult<models::JoinResponse> {
-    if payload.name.is_empty()
-        || payload.name.len() > 24
-        || payload.name.contains(|c: char| c.is_control())
-    {
+    if !utils::is_valid_name(&payload.name) {
         info!("Player failed to join: name is invalid");
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::bad_request("Name is invalid"));
     }
 
     let req_room_code: Option<state::room::RoomCode> = match payload.room_code {
         Some(room_code) => Some(room_code.parse().map_err(|_| StatusCode::BAD_REQUEST)?),
         None => None,
     };
+    if let Some(room_code) = &req_room_code {
+        let room = state
+            .get_room(room_code)
+            .await
+            .ok_or(StatusCode::NOT_FOUND)?;
+        let room = room.read().await;
+        if !room.config.check_password(payload.password.as_deref()) {
+            info!("Player failed to join room {:?}: wrong password", room_code);
+            return Err(ApiError(
+                StatusCode::FORBIDDEN,
+                "Wrong room password".to_string(),
+            ));
+        }
+    }
+
+    if req_room_code.is_none()
+        && state.get_default_room_code().await.is_none()
+        && !state.has_room_capacity().await
+    {
+        info!("Player failed to join default room: room limit reached");
+        return Err(StatusCode::SERVICE_UNAVAILABLE.into());
+    }
+
     let player_id = state::PlayerId::default();
     info!("Player {} joining room = {:?}", player_id, req_room_code);
     let room_code = state
@@ -435,11 +1211,17 @@ pub(crate) async fn join(
         .ok_or(StatusCode::NOT_FOUND)?;
     let mut state = state.write().await;
 
-    let id = match game::add_new_player(&mut state, &payload.name, player_id) {
+    let added = match payload.reservation.as_deref() {
+        Some(reservation) => {
+            game::add_reserved_player(&mut state, &payload.name, player_id, reservation)
+        }
+        None => game::add_new_player(&mut state, &payload.name, player_id),
+    };
+    let id = match added {
         Ok(id) => id,
         Err(err) => {
             info!("Player failed to join: {}", err);
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::bad_request(err));
         }
     };
 
@@ -450,9 +1232,16 @@ pub(crate) async fn join(
     info!("Player {} joined with name '{}'", id, payload.name);
     Metrics::c_players_total_incr();
 
+    let session_secret = state
+        .players
+        .get(&id)
+        .map(|player| player.session_secret.to_string())
+        .unwrap_or_default();
+
     Ok(Json(models::JoinResponse {
         id: id.to_string(),
         room_code: room_code.to_string(),
+        session_secret,
     }))
 }
 
@@ -468,30 +1257,36 @@ pub(crate) async fn resume(
     let room_state = utils::query_room_state(&state, payload.room_code.clone()).await?;
     let mut state = room_state.write().await;
 
-    let player = {
-        match state.players.promote_dormant(&apid) {
-            Some(player) => {
-                let room_code = payload
-                    .room_code
-                    .as_ref()
-                    .and_then(|room_code| room_code.parse().ok());
+    // a player who is still seated only lost their connection, so they keep
+    // their place in the hand; only players who left are folded on return
+    if let Some(player) = state.players.get_non_dormant(&apid).cloned() {
+        info!("Player {} reconnected", player.id);
+        return Ok(Json(models::ResumeResponse {
+            id: player.id.to_string(),
+            name: player.name,
+            session_secret: player.session_secret.to_string(),
+        }));
+    }
 
-                _ = shared_state.join_room(&player.id, room_code.as_ref()).await;
+    let player = state
+        .players
+        .promote_dormant(&apid)
+        .ok_or(StatusCode::NOT_FOUND)?;
 
-                state
-                    .players
-                    .get_mut(&player.id)
-                    .expect("player not found")
-                    .folded = true;
+    let room_code = payload
+        .room_code
+        .as_ref()
+        .and_then(|room_code| room_code.parse().ok());
 
-                Metrics::c_players_total_incr();
+    _ = shared_state.join_room(&player.id, room_code.as_ref()).await;
 
-                Some(player)
-            }
-            None => state.players.get_non_dormant(&apid).cloned(),
-        }
-    }
-    .ok_or_else(|| StatusCode::NOT_FOUND)?;
+    state
+        .players
+        .get_mut(&player.id)
+        .expect("player not found")
+        .folded = true;
+
+    Metrics::c_players_total_incr();
 
     state
         .ticker
@@ -503,6 +1298,7 @@ pub(crate) async fn resume(
     Ok(Json(models::ResumeResponse {
         id: player.id.to_string(),
         name: player.name,
+        session_secret: player.session_secret.to_string(),
     }))
 }
 
@@ -512,6 +1308,15 @@ pub(crate) async fn new_room(
     Extension(layer::Apid(apid)): Extension<layer::Apid>,
     Json(payload): Json<models::NewRoomRequest>,
 ) -> JsonResult<models::NewRoomResponse> {
+    if !utils::is_valid_name(&payload.name) {
+        info!("Failed to create room: name is invalid");
+        return Err(ApiError::bad_request("Name is invalid"));
+    }
+    if !state.has_room_capacity().await {
+        info!("Failed to create room: room limit reached");
+        return Err(StatusCode::SERVICE_UNAVAILABLE.into());
+    }
+
     let player_id = state::PlayerId::default();
     info!("Creating new room for player {}", player_id);
 
@@ -526,11 +1331,24 @@ pub(crate) async fn new_room(
         .ok_or(StatusCode::NOT_FOUND)?;
     let mut state = state.write().await;
 
+    if let Some(password) = payload.password.filter(|p| !p.is_empty()) {
+        state.config = state.config.clone().with_password(&password);
+    }
+    if let Some(currency) = payload.currency.filter(|c| !c.is_empty()) {
+        state.config = state.config.clone().with_currency(&currency);
+    }
+    if let Some(deck_cut) = payload.deck_cut {
+        state.config = state.config.clone().with_deck_cut(deck_cut);
+    }
+    if let Some(auto_start_seconds) = payload.auto_start_seconds {
+        state.config = state.config.clone().with_auto_start(auto_start_seconds);
+    }
+
     let id = match game::add_new_player(&mut state, &payload.name, player_id) {
         Ok(id) => id,
         Err(err) => {
             info!("Player failed to join: {}", err);
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::bad_request(err));
         }
     };
 
@@ -541,12 +1359,44 @@ pub(crate) async fn new_room(
     info!("Player {} joined with name '{}'", id, payload.name);
     Metrics::c_players_total_incr();
 
+    let session_secret = state
+        .players
+        .get(&id)
+        .map(|player| player.session_secret.to_string())
+        .unwrap_or_default();
+
     Ok(Json(models::NewRoomResponse {
         id: id.to_string(),
         room_code: room_code.to_string(),
+        session_secret,
     }))
 }
 
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn room_exists(
+    State(state): State<SharedState>,
+    Path(room_code): Path<String>,
+) -> JsonResult<models::RoomExistsResponse> {
+    let room_code: Option<state::room::RoomCode> = room_code.parse().ok();
+    let room = match &room_code {
+        Some(room_code) => state.get_room(room_code).await,
+        None => None,
+    };
+
+    let response = match room {
+        Some(room) => models::RoomExistsResponse {
+            exists: true,
+            joinable: game::is_joinable(&*room.read().await),
+        },
+        None => models::RoomExistsResponse {
+            exists: false,
+            joinable: false,
+        },
+    };
+
+    Ok(Json(response))
+}
+
 #[autometrics(ok_if = metrics::is_success)]
 pub(crate) async fn peek_room(
     State(state): State<SharedState>,
@@ -565,6 +1415,7 @@ pub(crate) async fn peek_room(
     let peek = models::PeekRoomResponse {
         state: game::game_phase(&state),
         players_count: state.players.len(),
+        password_required: state.config.has_password(),
         can_resume: resume_player_name.is_some(),
         resume_player_name,
     };
@@ -572,18 +1423,88 @@ pub(crate) async fn peek_room(
     Ok(Json(peek))
 }
 
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn resumable_sessions(
+    State(state): State<SharedState>,
+    Extension(layer::Apid(apid)): Extension<layer::Apid>,
+    room_code: Option<TypedHeader<models::headers::RoomCodeHeader>>,
+) -> JsonResult<models::ResumableSessions> {
+    let room_code = room_code.map(|TypedHeader(room_code)| room_code.into());
+    let state = utils::query_room_state(&state, room_code).await?;
+    let state = state.read().await;
+
+    // only players of the room, seated or not, may see who can come back
+    let in_room = state.players.get_non_dormant(&apid).is_some()
+        || state.players.peek_dormant(&apid).is_some();
+    if !in_room {
+        return Err(StatusCode::FORBIDDEN.into());
+    }
+
+    let names = state.players.dormant().map(|p| p.name.clone()).collect();
+
+    Ok(Json(models::ResumableSessions { names }))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn reserve_seat(
+    State(state): State<SharedState>,
+    Json(payload): Json<models::ReserveSeatRequest>,
+) -> JsonResult<models::ReserveSeatResponse> {
+    let state = utils::query_room_state(&state, Some(payload.room_code)).await?;
+    let mut state = state.write().await;
+
+    let (reservation, expiry) = game::reserve_seat(&mut state, state::dt::Instant::default())
+        .map_err(|err| {
+            info!("Failed to reserve seat: {}", err);
+            ApiError::bad_request(err)
+        })?;
+
+    info!("Seat reserved until {}", expiry.as_u64());
+
+    Ok(Json(models::ReserveSeatResponse {
+        reservation,
+        expires_dt: expiry.as_u64(),
+    }))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn knock_room(
+    State(state): State<SharedState>,
+    Extension(layer::Apid(apid)): Extension<layer::Apid>,
+    Json(payload): Json<models::KnockRequest>,
+) -> JsonResult<()> {
+    let state = utils::query_room_state(&state, Some(payload.room_code)).await?;
+    let mut state = state.write().await;
+
+    game::knock(&mut state, &apid, state::dt::Instant::default()).map_err(|err| {
+        info!("Visitor {} failed to knock: {}", apid, err);
+        ApiError(StatusCode::TOO_MANY_REQUESTS, err)
+    })?;
+
+    state.last_update.set_now();
+    info!("Visitor {} knocked", apid);
+
+    Ok(Json(()))
+}
+
 #[autometrics(ok_if = metrics::is_success)]
 pub(crate) async fn close_room(
     State(state): State<SharedState>,
     json: Option<Json<models::CloseRoomRequest>>,
 ) -> JsonResult<()> {
-    let room_code = json.and_then(|Json(payload)| payload.room_code);
+    let (room_code, force) = json
+        .map(|Json(payload)| (payload.room_code, payload.force.unwrap_or(false)))
+        .unwrap_or_default();
     let state = utils::query_room_state(&state, room_code).await?;
     let mut state = state.write().await;
 
-    game::start_game(&mut state).map_err(|err| {
+    let start_game = match force {
+        true => game::force_start_game,
+        false => game::start_game,
+    };
+    start_game(&mut state).map_err(|err| {
         info!("Failed to close room: {}", err);
-        StatusCode::BAD_REQUEST
+        ApiError::bad_request(err)
     })?;
 
     state.last_update.set_now();
@@ -593,22 +1514,107 @@ pub(crate) async fn close_room(
 }
 
 #[autometrics(ok_if = metrics::is_success)]
-pub(crate) async fn reset_room(
+pub(crate) async fn adjust_balance(
+    State(state): State<SharedState>,
+    admin_token: Option<TypedHeader<models::headers::AdminTokenHeader>>,
+    Json(payload): Json<models::AdjustBalanceRequest>,
+) -> JsonResult<()> {
+    utils::validate_admin_token(admin_token.map(|TypedHeader(token)| token.into()))?;
+
+    let player = utils::validate_player(&payload.player_id, &state).await?;
+    let state = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+    let mut state = state.write().await;
+
+    game::adjust_balance(&mut state, &player.id, payload.delta).map_err(|err| {
+        info!("Failed to adjust balance of {}: {}", player.id, err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+    info!("Adjusted balance of {} by {}", player.id, payload.delta);
+
+    Ok(Json(()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn force_advance_room(
     State(state): State<SharedState>,
+    admin_token: Option<TypedHeader<models::headers::AdminTokenHeader>>,
     room_code: Option<TypedHeader<models::headers::RoomCodeHeader>>,
 ) -> JsonResult<()> {
+    utils::validate_admin_token(admin_token.map(|TypedHeader(token)| token.into()))?;
+
     let room_code = room_code.map(|TypedHeader(room_code)| room_code.into());
     let state = utils::query_room_state(&state, room_code).await?;
     let mut state = state.write().await;
 
-    *state = state::State::default();
+    game::force_advance(&mut state, state::dt::Instant::default()).map_err(|err| {
+        info!("Failed to force advance room: {}", err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+
+    info!("Room turn force advanced");
+    Ok(Json(()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn reset_room(
+    State(state): State<SharedState>,
+    room_code: Option<TypedHeader<models::headers::RoomCodeHeader>>,
+) -> JsonResult<()> {
+    let room_code = room_code.map(|TypedHeader(room_code)| room_code.into());
+    let state = utils::query_room_state(&state, room_code).await?;
+    let mut state = state.write().await;
 
+    state.reset();
     state.last_update.set_now();
 
     info!("Game reset");
     Ok(Json(()))
 }
 
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn reshuffle_room(
+    State(state): State<SharedState>,
+    room_code: Option<TypedHeader<models::headers::RoomCodeHeader>>,
+) -> JsonResult<()> {
+    let room_code = room_code.map(|TypedHeader(room_code)| room_code.into());
+    let state = utils::query_room_state(&state, room_code).await?;
+    let mut state = state.write().await;
+
+    game::reshuffle(&mut state).map_err(|err| {
+        info!("Failed to reshuffle: {}", err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+
+    info!("Deck reshuffled");
+    Ok(Json(()))
+}
+
+#[autometrics(ok_if = metrics::is_success)]
+pub(crate) async fn rematch_room(
+    State(state): State<SharedState>,
+    room_code: Option<TypedHeader<models::headers::RoomCodeHeader>>,
+) -> JsonResult<()> {
+    let room_code = room_code.map(|TypedHeader(room_code)| room_code.into());
+    let state = utils::query_room_state(&state, room_code).await?;
+    let mut state = state.write().await;
+
+    game::rematch(&mut state).map_err(|err| {
+        info!("Failed to start rematch: {}", err);
+        ApiError::bad_request(err)
+    })?;
+
+    state.last_update.set_now();
+
+    info!("Rematch started");
+    Ok(Json(()))
+}
+
 #[autometrics(ok_if = metrics::is_success)]
 pub(crate) async fn pair(
     State(state): State<SharedState>,
@@ -652,9 +1658,35 @@ mod utils {
 
     use crate::{
         app_metrics::{metrics_labels, Metrics},
-        models, state,
+        game, models, state,
     };
 
+    const POLL_TIMEOUT_DEFAULT_MS: u64 = 5_000;
+    const POLL_TIMEOUT_MIN_MS: u64 = 100;
+    const POLL_TIMEOUT_MAX_MS: u64 = 30_000;
+
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty() && name.len() <= 24 && !has_hidden_chars(name)
+    }
+
+    /// Control characters, along with zero-width and bidi override characters
+    /// that can reorder or hide text on the big screen. Zero-width joiners are
+    /// allowed, as emoji sequences rely on them.
+    pub fn has_hidden_chars(text: &str) -> bool {
+        text.chars().any(|c| {
+            c.is_control()
+                || matches!(
+                    c,
+                    '\u{200B}'
+                        | '\u{200E}'..='\u{200F}'
+                        | '\u{202A}'..='\u{202E}'
+                        | '\u{2060}'
+                        | '\u{2066}'..='\u{2069}'
+                        | '\u{FEFF}'
+                )
+        })
+    }
+
     #[autometrics]
     pub async fn validate_player(
         player_id: &str,
@@ -674,6 +1706,39 @@ mod utils {
         }
     }
 
+    /// Validates the player, and their session secret when the room requires
+    /// one to see their cards.
+    pub async fn validate_session_secret(
+        state: &state::SharedState,
+        player_id: &str,
+        session_secret: Option<&str>,
+    ) -> Result<state::Player, StatusCode> {
+        let player = validate_player(player_id, state).await?;
+        let room = state.get(&player.id).await.ok_or(StatusCode::NOT_FOUND)?;
+        let room = room.read().await;
+
+        game::check_session_secret(&room, &player.id, session_secret).map_err(|err| {
+            info!("Player {} failed: {}", player.id, err);
+            StatusCode::UNAUTHORIZED
+        })?;
+
+        Ok(player)
+    }
+
+    /// Admin routes are hidden unless `ADMIN_TOKEN` is set, and then require
+    /// it in the `admin-token` header.
+    pub fn validate_admin_token(admin_token: Option<String>) -> Result<(), StatusCode> {
+        let expected = std::env::var("ADMIN_TOKEN").unwrap_or_default();
+        if expected.is_empty() {
+            return Err(StatusCode::NOT_FOUND);
+        }
+        if admin_token.as_deref() != Some(expected.as_str()) {
+            info!("Admin request rejected: invalid admin token");
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+        Ok(())
+    }
+
     pub async fn query_room_state(
         state: &state::SharedState,
         room_code: Option<String>,
@@ -779,7 +1844,7 @@ mod utils {
         Ok((room_code, pair_screen_code))
     }
 
-    async fn wait_for_update(state: &state::RoomState, query: models::PollQuery) {
+    pub async fn wait_for_update(state: &state::RoomState, query: models::PollQuery) {
         if let Some(last_update) = query.since {
             let rx = {
                 let state = state.read().await;
@@ -811,7 +1876,9 @@ mod utils {
     }
 
     async fn sleep_from_timeout_query(timeout: Option<u64>) {
-        let timeout_ms = timeout.unwrap_or(5_000);
+        let timeout_ms = timeout
+            .unwrap_or(POLL_TIMEOUT_DEFAULT_MS)
+            .clamp(POLL_TIMEOUT_MIN_MS, POLL_TIMEOUT_MAX_MS);
         let timeout = std::time::Duration::from_millis(timeout_ms);
         tokio::time::sleep(timeout).await;
     }
@@ -820,10 +1887,26 @@ mod utils {
 mod metrics {
     use axum::http::StatusCode;
 
-    pub fn is_success<T>(response: &Result<T, StatusCode>) -> bool {
+    pub trait ErrorStatus {
+        fn status(&self) -> StatusCode;
+    }
+
+    impl ErrorStatus for StatusCode {
+        fn status(&self) -> StatusCode {
+            *self
+        }
+    }
+
+    impl ErrorStatus for super::ApiError {
+        fn status(&self) -> StatusCode {
+            self.0
+        }
+    }
+
+    pub fn is_success<T, E: ErrorStatus>(response: &Result<T, E>) -> bool {
         !matches!(
-            response.as_ref().err(),
-            Some(&StatusCode::OK) | Some(&StatusCode::NOT_FOUND)
+            response.as_ref().err().map(ErrorStatus::status),
+            Some(StatusCode::OK) | Some(StatusCode::NOT_FOUND)
         )
     }
 }
@@ -835,16 +1918,122 @@ pub mod docs {
         op.description("Get the current state of the game room.")
     }
 
+    pub fn spectate_room(op: TransformOperation) -> TransformOperation {
+        op.description("Watch the game room without a seat. Hole cards are never revealed.")
+    }
+
     pub fn player(op: TransformOperation) -> TransformOperation {
-        op.description("Get the current state of a player.")
+        op.description(
+            "Get the current state of a player. Rooms that require it also need the \
+            `session-secret` header issued when the player joined.",
+        )
+    }
+
+    pub fn player_stats(op: TransformOperation) -> TransformOperation {
+        op.description("Get the player's game statistics for this session.")
     }
 
     pub fn player_leave(op: TransformOperation) -> TransformOperation {
         op.description("Leave the game room.")
     }
 
+    pub fn player_sit_out(op: TransformOperation) -> TransformOperation {
+        op.description("Sit out of the game room from the next game, keeping seat and balance.")
+    }
+
+    pub fn player_sit_in(op: TransformOperation) -> TransformOperation {
+        op.description("Sit back in to the game room from the next game.")
+    }
+
+    pub fn player_ready(op: TransformOperation) -> TransformOperation {
+        op.description("Mark the player as ready for the game to start.")
+    }
+
+    pub fn player_muck(op: TransformOperation) -> TransformOperation {
+        op.description("Hide the player's losing cards once the game is complete.")
+    }
+
+    pub fn player_show(op: TransformOperation) -> TransformOperation {
+        op.description("Reveal the player's losing cards when the room auto-mucks losing hands.")
+    }
+
+    pub fn player_straddle(op: TransformOperation) -> TransformOperation {
+        op.description(
+            "Post a straddle of twice the big blind. Only allowed for the player left of the \
+            big blind, before any action preflop.",
+        )
+    }
+
+    pub fn player_timebank(op: TransformOperation) -> TransformOperation {
+        op.description("Extend the player's current turn by the room's time bank, once per hand.")
+    }
+
+    pub fn player_queue_action(op: TransformOperation) -> TransformOperation {
+        op.description(
+            "Queue a fold or check-fold to play automatically when it is the player's turn. \
+            A queued fold is dropped if the player can check for free.",
+        )
+    }
+
+    pub fn player_actions(op: TransformOperation) -> TransformOperation {
+        op.description(
+            "Get which actions the player can take right now, with the range of \
+            stakes they can raise to.",
+        )
+    }
+
+    pub fn player_equity(op: TransformOperation) -> TransformOperation {
+        op.description(
+            "Estimate the player's chances to win, tie or lose the hand from their \
+            cards and the board, against random hands for each opponent.",
+        )
+    }
+
+    pub fn player_rebuy(op: TransformOperation) -> TransformOperation {
+        op.description("Top the player back up to the starting balance between games.")
+    }
+
+    pub fn player_chat(op: TransformOperation) -> TransformOperation {
+        op.description("Send a chat message to the game room.")
+    }
+
+    pub fn room_chat(op: TransformOperation) -> TransformOperation {
+        op.description("Get the recent chat messages for the game room.")
+    }
+
+    pub fn rooms(op: TransformOperation) -> TransformOperation {
+        op.description(
+            "List every active room with its phase and player count. \
+            Requires the `admin-token` header to match `ADMIN_TOKEN`.",
+        )
+    }
+
+    pub fn room_config(op: TransformOperation) -> TransformOperation {
+        op.description("Get the blinds, starting balance and other rules of the game room.")
+    }
+
+    pub fn room_debug(op: TransformOperation) -> TransformOperation {
+        op.description(
+            "Get the deck order, hole cards and board of the game room. \
+            Only available when `ENABLE_DEBUG_ROUTES=true` is set.",
+        )
+    }
+
+    pub fn room_history(op: TransformOperation) -> TransformOperation {
+        op.description("Get the hand history of the last completed game in the room.")
+    }
+
+    pub fn room_export(op: TransformOperation) -> TransformOperation {
+        op.description(
+            "Export every recorded event in the game room as newline-delimited JSON, \
+            including events no longer shown on the ticker.",
+        )
+    }
+
     pub fn player_send(op: TransformOperation) -> TransformOperation {
-        op.description("Send a message to the game room.")
+        op.description(
+            "Send an emoji to the game room, limited to one every couple of seconds per player.",
+        )
     }
 
     pub fn get_player_transfer(op: TransformOperation) -> TransformOperation {
@@ -864,33 +2053,100 @@ pub mod docs {
     }
 
     pub fn play(op: TransformOperation) -> TransformOperation {
-        op.description("Play a round.")
+        op.description(
+            "Play a round. Responds with 409 if it is not the player's turn \
+            and 400 if the action is not valid.",
+        )
+    }
+
+    pub fn start_vote(op: TransformOperation) -> TransformOperation {
+        op.description("Start a vote on a motion for the game room.")
+    }
+
+    pub fn cast_vote(op: TransformOperation) -> TransformOperation {
+        op.description("Cast a vote on the motion currently in progress.")
     }
 
     pub fn new_room(op: TransformOperation) -> TransformOperation {
-        op.description("Create and join a new game room.")
+        op.description(
+            "Create and join a new game room. \
+            Responds with 503 if the server has reached its room limit.",
+        )
     }
 
     pub fn join(op: TransformOperation) -> TransformOperation {
-        op.description("Join the game room.")
+        op.description("Join the game room. Responds with 403 if the room password does not match.")
     }
 
     pub fn resume(op: TransformOperation) -> TransformOperation {
         op.description("Resume previous session in the game room.")
     }
 
+    pub fn resumable_sessions(op: TransformOperation) -> TransformOperation {
+        op.description(
+            "List the names of players who left the game room and can resume their session. \
+            Only available to players of the room.",
+        )
+    }
+
+    pub fn room_exists(op: TransformOperation) -> TransformOperation {
+        op.description(
+            "Check whether a room code exists and has a free seat, without revealing who is in it.",
+        )
+    }
+
     pub fn peek_room(op: TransformOperation) -> TransformOperation {
         op.description("Peek at the game room from join code.")
     }
 
+    pub fn reserve_seat(op: TransformOperation) -> TransformOperation {
+        op.description(
+            "Hold a seat in the game room for 20 seconds. \
+            Pass the returned reservation when joining to claim it.",
+        )
+    }
+
+    pub fn knock_room(op: TransformOperation) -> TransformOperation {
+        op.description(
+            "Nudge the game room from its join code without taking a seat. \
+            Responds with 429 if the visitor knocked in the last 30 seconds.",
+        )
+    }
+
     pub fn close_room(op: TransformOperation) -> TransformOperation {
-        op.description("Close the game room for new players to join and start the game.")
+        op.description(
+            "Close the game room for new players to join and start the game. \
+            All seated players must be ready unless `force` is set.",
+        )
+    }
+
+    pub fn adjust_balance(op: TransformOperation) -> TransformOperation {
+        op.description(
+            "Correct a player's balance by a signed amount between hands, recorded in the event \
+            log. The correction counts towards the player's buy-in, so their session net is \
+            unchanged. Requires the `admin-token` header to match `ADMIN_TOKEN`.",
+        )
+    }
+
+    pub fn force_advance_room(op: TransformOperation) -> TransformOperation {
+        op.description(
+            "Move a stuck turn on, folding a player whose turn expired or skipping one who \
+            has left. Requires the `admin-token` header to match `ADMIN_TOKEN`.",
+        )
     }
 
     pub fn reset_room(op: TransformOperation) -> TransformOperation {
         op.description("Reset the game room.")
     }
 
+    pub fn reshuffle_room(op: TransformOperation) -> TransformOperation {
+        op.description("Reshuffle the deck between hands. Rejected while a hand is in play.")
+    }
+
+    pub fn rematch_room(op: TransformOperation) -> TransformOperation {
+        op.description("Start a rematch with the same players and fresh balances.")
+    }
+
     pub fn pair(op: TransformOperation) -> TransformOperation {
         op.description("Pairs a big screen with a room.")
     }
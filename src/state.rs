@@ -1,4 +1,10 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 use crate::cards::{self, Card, Deck};
 
@@ -19,6 +25,10 @@ pub struct SharedState {
     registry: Arc<RwLock<room::RoomRegistry>>,
     big_screens: Arc<RwLock<screens::BigScreenRegistry>>,
     default_config: Arc<std::sync::RwLock<Option<config::RoomConfig>>>,
+    hand_results: results::HandResults,
+    max_rooms: Option<usize>,
+    worker_tick: Arc<AtomicU64>,
+    started: Instant,
 }
 
 impl SharedState {
@@ -44,7 +54,7 @@ impl SharedState {
                 }
 
                 let mut rooms = self.states.write().unwrap();
-                let state = Arc::new(RwLock::new(self.default_state()));
+                let state = Arc::new(RwLock::new(self.default_state(room)));
                 rooms.insert(room.clone(), state.clone());
                 state
             }
@@ -65,7 +75,7 @@ impl SharedState {
     pub async fn create_room(&self, player_id: &PlayerId) -> room::RoomCode {
         let mut rooms = self.registry.write().await;
         let code = rooms.create_room(player_id);
-        let state = Arc::new(RwLock::new(self.default_state()));
+        let state = Arc::new(RwLock::new(self.default_state(&code)));
 
         let mut inner = self.states.write().unwrap();
         inner.insert(code.clone(), state);
@@ -113,6 +123,24 @@ impl SharedState {
             .into_iter()
     }
 
+    pub fn room_count(&self) -> usize {
+        self.states.read().unwrap().len()
+    }
+
+    /// Marks the game worker as alive, called on every pass of its loop.
+    pub fn record_worker_tick(&self) {
+        let now = Instant::default();
+        self.worker_tick.store(now.into(), Ordering::Relaxed);
+    }
+
+    pub fn last_worker_tick(&self) -> Instant {
+        self.worker_tick.load(Ordering::Relaxed).into()
+    }
+
+    pub fn started(&self) -> Instant {
+        self.started
+    }
+
     pub async fn cleanup(&self) {
         let mut rooms = self.states.write().unwrap().clone();
         let mut to_remove = Vec::new();
@@ -126,7 +154,7 @@ impl SharedState {
 
             let now = Instant::default().as_u64();
             let last_update = state.last_update.as_u64();
-            let room_expires_at = last_update + GAME_IDLE_TIMEOUT_SECONDS * 1000;
+            let room_expires_at = last_update + state.config.idle_timeout_seconds() * 1000;
 
             if room_expires_at < now {
                 to_remove.push(room_code.clone());
@@ -185,13 +213,17 @@ impl SharedState {
         code: &screens::PairScreenCode,
         room_code: &room::RoomCode,
     ) -> Result<(), ()> {
-        self.get_room(room_code).await.ok_or(())?;
+        let room = self.get_room(room_code).await.ok_or(())?;
 
         let mut big_screens = self.big_screens.write().await;
         let screen = big_screens.get_mut(code).ok_or(())?;
 
         screen.room_code = Some(room_code.clone());
         screen.last_update.set_now();
+        let apid = screen.apid.clone();
+        drop(big_screens);
+
+        room.write().await.screen_apids.insert(apid);
 
         Ok(())
     }
@@ -201,16 +233,45 @@ impl SharedState {
         *default_config = Some(config);
     }
 
-    fn default_state(&self) -> State {
-        match self.default_config.read() {
-            Ok(config) => {
-                let config = config.as_ref().cloned().unwrap_or_default();
-                let mut state = State::default();
-                state.config = config;
-                state
-            }
-            Err(_) => State::default(),
+    /// Reports every completed hand in every room to the given sink.
+    pub fn with_hand_result_sink(mut self, sink: impl results::HandResultSink + 'static) -> Self {
+        self.hand_results = results::HandResults::new(sink);
+        self
+    }
+
+    /// Caps how many rooms may exist at once.
+    pub fn with_max_rooms(mut self, max_rooms: usize) -> Self {
+        self.max_rooms = Some(max_rooms);
+        self
+    }
+
+    /// Whether another room may be created, cleaning up expired rooms first
+    /// when the cap has been reached.
+    pub async fn has_room_capacity(&self) -> bool {
+        let Some(max_rooms) = self.max_rooms else {
+            return true;
+        };
+        if self.registry.read().await.room_count() < max_rooms {
+            return true;
+        }
+
+        self.cleanup().await;
+        self.registry.read().await.room_count() < max_rooms
+    }
+
+    fn default_state(&self, room_code: &room::RoomCode) -> State {
+        let mut state = State {
+            room_code: Some(room_code.clone()),
+            hand_results: self.hand_results.clone(),
+            ..State::default()
+        };
+        if let Ok(config) = self.default_config.read() {
+            state.config = config.as_ref().cloned().unwrap_or_default();
         }
+        state
+            .ticker
+            .set_timeout_seconds(state.config.ticker_timeout_seconds());
+        state
     }
 }
 
@@ -295,6 +356,10 @@ pub mod room {
         pub fn room_exists(&self, room: &RoomCode) -> bool {
             self.rooms.contains(room)
         }
+
+        pub fn room_count(&self) -> usize {
+            self.rooms.len()
+        }
     }
 
     #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -454,36 +519,154 @@ pub const STARTING_BALANCE: u64 = 1000;
 pub const SMALL_BLIND: u64 = 10;
 pub const BIG_BLIND: u64 = SMALL_BLIND * 2;
 pub const PLAYER_EMOJI_TIMEOUT_SECONDS: u64 = 5;
+pub const PLAYER_EMOJI_COOLDOWN_SECONDS: u64 = 2;
 pub const TICKER_ITEM_TIMEOUT_SECONDS: u64 = 10;
 pub const TICKER_ITEM_GAP_MILLISECONDS: u64 = 500;
+pub const TICKER_MAX_ITEMS: usize = 20;
 pub const PLAYER_TURN_TIMEOUT_SECONDS: u64 = 60;
+pub const MIN_PLAYER_TURN_TIMEOUT_SECONDS: u64 = 10;
+pub const MAX_PLAYER_TURN_TIMEOUT_SECONDS: u64 = 300;
+pub const PLAYER_TIME_BANK_SECONDS: u64 = 30;
+pub const DEFAULT_CURRENCY_SYMBOL: &str = "£";
+pub const PLAYER_TURN_WARNING_SECONDS: u64 = 10;
 pub const GAME_IDLE_TIMEOUT_SECONDS: u64 = 300;
 pub const ROOM_CODE_LENGTH: usize = 4;
 pub const PAIR_SCREEN_CODE_LENGTH: usize = 6;
 pub const MAX_PLAYERS: usize = 10;
+pub const MAX_DORMANT_PLAYERS: usize = 20;
+pub const MAX_PLAYER_PHOTO_BYTES: usize = 2 * 1024 * 1024;
+pub const VOTE_TIMEOUT_SECONDS: u64 = 30;
+pub const KNOCK_COOLDOWN_SECONDS: u64 = 30;
+pub const SEAT_RESERVATION_SECONDS: u64 = 20;
+pub const SPECTATOR_TTL_SECONDS: u64 = 30;
+pub const PLAYER_ONLINE_SECONDS: u64 = 10;
+pub const PLAYER_SEEN_REFRESH_SECONDS: u64 = 1;
+pub const EQUITY_SIMULATIONS: usize = 1000;
+pub const WORKER_TICK_TIMEOUT_SECONDS: u64 = 5;
+pub const CHAT_HISTORY_LENGTH: usize = 50;
+pub const EVENT_LOG_LENGTH: usize = 1000;
+pub const CHAT_MESSAGE_MAX_LENGTH: usize = 140;
 
 #[derive(Debug, Default)]
 pub struct State {
+    pub room_code: Option<room::RoomCode>,
     pub players: Players,
     pub round: Round,
     pub last_update: dt::SignalInstant,
     pub ticker: ticker::Ticker,
     pub status: GameStatus,
     pub config: config::RoomConfig,
+    pub vote: Option<vote::Vote>,
+    pub last_completed: Option<CompletedRound>,
+    pub chat: VecDeque<ChatMessage>,
+    /// When each visitor apid last knocked, for rate limiting.
+    pub knocks: HashMap<String, dt::Instant>,
+    /// Seats held for visitors who have not joined yet, by token and expiry.
+    pub reservations: HashMap<String, dt::Instant>,
+    pub spectators: Spectators,
+    /// Apids of big screens paired with the room, which aren't spectators.
+    pub screen_apids: HashSet<String>,
+    pub hands_started: u64,
+    pub blind_level: usize,
+    /// The small blind once the blind schedule or a vote has raised it from the config's.
+    pub small_blind: Option<u64>,
+    /// A small blind voted in, which takes effect when the next hand starts.
+    pub next_small_blind: Option<u64>,
+    pub hand_results: results::HandResults,
+    /// Chips raked from pots since the room opened.
+    pub rake_total: u64,
     pub disposed: bool,
 }
 
+/// Visitor apids recently polling the room, by expiry. Kept behind a lock of
+/// its own so that recording a poll doesn't need to write to the room.
+#[derive(Debug, Default)]
+pub struct Spectators(std::sync::Mutex<HashMap<String, Instant>>);
+
+impl Spectators {
+    pub fn record(&self, apid: &str, expiry: Instant) {
+        let mut spectators = self.0.lock().unwrap();
+        spectators.insert(apid.to_string(), expiry);
+    }
+
+    pub fn count(&self, now: Instant) -> usize {
+        let spectators = self.0.lock().unwrap();
+        spectators.values().filter(|expiry| **expiry >= now).count()
+    }
+
+    pub fn has_expired(&self, now: Instant) -> bool {
+        let spectators = self.0.lock().unwrap();
+        spectators.values().any(|expiry| *expiry < now)
+    }
+
+    pub fn expire(&self, now: Instant) {
+        let mut spectators = self.0.lock().unwrap();
+        spectators.retain(|_, expiry| *expiry >= now);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.lock().unwrap().is_empty()
+    }
+}
+
+/// A hash of the shuffled deck, published when the hand is dealt and checked
+/// against the seed and deck revealed once the hand is complete.
+#[derive(Debug, Clone)]
+pub struct DeckCommitment {
+    /// Hex encoded server seed, kept secret until the hand is complete.
+    pub seed: String,
+    /// The deck in deal order, top card first.
+    pub deck: Vec<Card>,
+    /// Hex encoded SHA-256 of the seed, a colon, then the deck as JSON.
+    pub hash: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatMessage {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub message: String,
+    pub sent: dt::Instant,
+}
+
 #[derive(Debug, Default)]
 pub struct Round {
     pub pot: u64,
     pub deck: Deck,
     pub cards_on_table: Vec<Card>,
     pub players_turn: Option<PlayerId>,
+    pub players_turn_warned: bool,
     pub raises: Vec<(PlayerId, u64)>,
     pub calls: Vec<(PlayerId, u64)>,
+    /// Players who acted since the last full raise, and so cannot re-raise
+    /// when only facing a short all-in.
+    pub acted_since_full_raise: Vec<PlayerId>,
+    /// The player who posted the big blind this hand, who gets the option to
+    /// raise if nobody else has.
+    pub big_blind: Option<PlayerId>,
+    /// The player who straddled under the gun this hand, if any.
+    pub straddle: Option<PlayerId>,
+    /// Set when the deck was reshuffled between hands, so the next hand is
+    /// dealt from it rather than a new deck.
+    pub reshuffled: bool,
+    pub deck_commitment: Option<DeckCommitment>,
+    pub history: Vec<HandHistoryItem>,
     pub completed: Option<CompletedRound>,
 }
 
+impl State {
+    /// Clears the room back to a fresh game, keeping its code and the config it
+    /// was opened with, so any raised blinds drop back to the config's.
+    pub fn reset(&mut self) {
+        *self = State {
+            room_code: self.room_code.take(),
+            config: std::mem::take(&mut self.config),
+            hand_results: self.hand_results.clone(),
+            ..State::default()
+        };
+    }
+}
+
 impl Into<RoomState> for State {
     fn into(self) -> RoomState {
         Arc::new(RwLock::new(self))
@@ -505,20 +688,78 @@ pub struct Player {
     pub id: PlayerId,
     pub emoji: Option<(ticker::emoji::TickerEmoji, dt::Instant)>,
     pub funds_token: token::Token,
+    /// Handed out only to whoever joined, so knowing the player id alone
+    /// isn't enough to see their cards when the room requires it.
+    pub session_secret: token::Token,
     pub balance: u64,
+    /// The starting balance plus any rebuys, used to work out the session net.
+    pub buy_in_total: u64,
     pub stake: u64,
     pub folded: bool,
+    pub all_in: bool,
+    pub sitting_out: bool,
+    pub ready: bool,
+    pub muck: bool,
+    pub time_bank_used: bool,
+    /// Chips sent to other players this game, which runs across every hand
+    /// until a rematch resets the balances.
+    pub transferred: u64,
+    pub queued_action: Option<QueuedAction>,
+    pub last_action_id: Option<uuid::Uuid>,
+    pub stats: PlayerStats,
+    pub seat: usize,
     pub photo: Option<PlayerPhoto>,
     pub ttl: Option<dt::Instant>,
+    /// When the player last polled their own state, used to show who is online.
+    pub last_seen: Option<dt::Instant>,
     pub apid: String,
-    pub cards: (Card, Card),
+    pub cards: Vec<Card>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PlayerStats {
+    pub hands_played: u64,
+    pub hands_won: u64,
+    pub biggest_pot: u64,
+}
+
+impl PlayerStats {
+    pub fn record_win(&mut self, winnings: u64) {
+        self.hands_won += 1;
+        self.biggest_pot = self.biggest_pot.max(winnings);
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct CompletedRound {
     pub winners: Vec<RoundWinner>,
-    pub best_hand: Option<(Vec<PlayerId>, cards::HandStrength)>,
+    pub best_hand: Option<(Vec<PlayerId>, cards::EvaluatedHand)>,
     pub hide_cards: bool,
+    pub history: Vec<HandHistoryItem>,
+    pub board: Vec<Card>,
+    /// The second runout when the hand was run twice.
+    pub second_board: Option<Vec<Card>>,
+    pub showdown: Vec<(PlayerId, Vec<Card>)>,
+    pub rake: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct HandHistoryItem {
+    pub player_id: PlayerId,
+    pub cards_on_table: usize,
+    pub action: HandAction,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandAction {
+    Ante,
+    SmallBlind,
+    BigBlind,
+    BringIn,
+    Straddle,
+    Bet(BetAction),
+    Fold,
 }
 
 #[derive(Debug, Clone)]
@@ -533,16 +774,27 @@ pub struct RoundWinner {
 pub enum GameStatus {
     #[default]
     Joining,
+    /// A running game lost players and is waiting for more to resume.
+    Paused,
     Playing,
     Complete,
     Idle,
 }
 
+/// An action a player queues to be played automatically on their turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueuedAction {
+    CheckFold,
+    Fold,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BetAction {
     Check,
     Call,
+    BetTo(u64),
     RaiseTo(u64),
+    AllIn,
 }
 
 mod id {
@@ -606,6 +858,15 @@ pub mod token {
         }
     }
 
+    impl Token {
+        /// A full length token, for values that are used as a credential.
+        pub fn secret() -> Self {
+            Self {
+                value: uuid::Uuid::new_v4().simple().to_string(),
+            }
+        }
+    }
+
     impl Display for Token {
         #[inline]
         fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -636,7 +897,11 @@ pub mod dt {
         }
 
         fn now_ms() -> u64 {
-            SystemTime::now()
+            // a paused tokio clock runs ahead of the system clock once tests advance it
+            let ahead = tokio::time::Instant::now()
+                .into_std()
+                .saturating_duration_since(std::time::Instant::now());
+            (SystemTime::now() + ahead)
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
                 .as_millis() as u64
@@ -743,12 +1008,64 @@ pub mod dt {
     }
 }
 
+pub mod results {
+    use std::sync::Arc;
+
+    use super::{Card, PlayerId, RoundWinner};
+
+    /// The outcome of a completed hand.
+    #[derive(Debug, Clone)]
+    pub struct HandResult {
+        pub winners: Vec<RoundWinner>,
+        pub pot: u64,
+        pub board: Vec<Card>,
+        pub second_board: Option<Vec<Card>>,
+        pub hands: Vec<(PlayerId, Vec<Card>)>,
+    }
+
+    /// Receives the result of every completed hand, e.g. to persist them.
+    pub trait HandResultSink: Send + Sync {
+        fn record(&self, result: HandResult);
+    }
+
+    struct NoopSink;
+
+    impl HandResultSink for NoopSink {
+        fn record(&self, _result: HandResult) {}
+    }
+
+    #[derive(Clone)]
+    pub struct HandResults(Arc<dyn HandResultSink>);
+
+    impl HandResults {
+        pub fn new(sink: impl HandResultSink + 'static) -> Self {
+            Self(Arc::new(sink))
+        }
+
+        pub fn record(&self, result: HandResult) {
+            self.0.record(result);
+        }
+    }
+
+    impl Default for HandResults {
+        fn default() -> Self {
+            Self::new(NoopSink)
+        }
+    }
+
+    impl std::fmt::Debug for HandResults {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_tuple("HandResults").finish()
+        }
+    }
+}
+
 pub mod ticker {
-    use std::borrow::Cow;
+    use std::{borrow::Cow, collections::VecDeque};
 
     use crate::cards;
 
-    use super::{dt::Instant, BetAction, PlayerId};
+    use super::{dt::Instant, vote::Motion, BetAction, PlayerId};
     static TICKER_DISABLED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
 
     #[derive(Debug, Clone)]
@@ -756,14 +1073,29 @@ pub mod ticker {
         GameStarted,
         PlayerJoined(PlayerId),
         PlayerTurnTimeout(String),
+        PlayerTurnWarning(PlayerId),
+        ActionOn(PlayerId),
+        PlayerUsedTimeBank(PlayerId),
         PlayerLeft(String),
+        PlayerKicked(String),
         PlayerResumed(PlayerId),
+        PlayerSatOut(PlayerId),
+        PlayerSatIn(PlayerId),
+        PlayerReady(PlayerId),
+        PlayerRebought(PlayerId),
+        PlayerMucked(PlayerId),
         PlayerFolded(PlayerId),
         PlayerBet(PlayerId, BetAction),
         DealerRotated(PlayerId),
+        AntePosted(u64),
+        BlindsRaised(u64),
         SmallBlindPosted(PlayerId),
         BigBlindPosted(PlayerId),
+        BringInPosted(PlayerId),
+        StraddlePosted(PlayerId),
         CardsDealtToTable(usize),
+        RunoutDealt(usize, usize),
+        DeckReshuffled,
         RoundComplete,
         Winner(PlayerId, cards::HandStrength),
         SplitPotWinners(Vec<PlayerId>, cards::HandStrength),
@@ -771,9 +1103,56 @@ pub mod ticker {
         PlayerPhotoUploaded(PlayerId),
         PlayerSentEmoji(PlayerId, emoji::TickerEmoji),
         PlayerTransferredBalance(PlayerId, PlayerId, u64),
+        BalanceAdjusted(PlayerId, i64),
+        VoteStarted(PlayerId, Motion),
+        VoteCompleted(Motion, bool),
+        Knock,
     }
 
     impl TickerEvent {
+        /// The name of the event, stable across changes to its formatting.
+        pub fn kind(&self) -> &'static str {
+            match self {
+                Self::GameStarted => "GameStarted",
+                Self::DeckReshuffled => "DeckReshuffled",
+                Self::PlayerJoined(_) => "PlayerJoined",
+                Self::PlayerTurnTimeout(_) => "PlayerTurnTimeout",
+                Self::PlayerTurnWarning(_) => "PlayerTurnWarning",
+                Self::ActionOn(_) => "ActionOn",
+                Self::PlayerUsedTimeBank(_) => "PlayerUsedTimeBank",
+                Self::PlayerLeft(_) => "PlayerLeft",
+                Self::PlayerKicked(_) => "PlayerKicked",
+                Self::PlayerResumed(_) => "PlayerResumed",
+                Self::PlayerSatOut(_) => "PlayerSatOut",
+                Self::PlayerSatIn(_) => "PlayerSatIn",
+                Self::PlayerReady(_) => "PlayerReady",
+                Self::PlayerRebought(_) => "PlayerRebought",
+                Self::PlayerMucked(_) => "PlayerMucked",
+                Self::PlayerFolded(_) => "PlayerFolded",
+                Self::PlayerBet(..) => "PlayerBet",
+                Self::DealerRotated(_) => "DealerRotated",
+                Self::AntePosted(_) => "AntePosted",
+                Self::BlindsRaised(_) => "BlindsRaised",
+                Self::SmallBlindPosted(_) => "SmallBlindPosted",
+                Self::BigBlindPosted(_) => "BigBlindPosted",
+                Self::BringInPosted(_) => "BringInPosted",
+                Self::StraddlePosted(_) => "StraddlePosted",
+                Self::CardsDealtToTable(_) => "CardsDealtToTable",
+                Self::RunoutDealt(..) => "RunoutDealt",
+                Self::RoundComplete => "RoundComplete",
+                Self::Winner(..) => "Winner",
+                Self::SplitPotWinners(..) => "SplitPotWinners",
+                Self::PaidPot(..) => "PaidPot",
+                Self::PlayerPhotoUploaded(_) => "PlayerPhotoUploaded",
+                Self::PlayerSentEmoji(..) => "PlayerSentEmoji",
+                Self::PlayerTransferredBalance(..) => "PlayerTransferredBalance",
+                Self::BalanceAdjusted(..) => "BalanceAdjusted",
+                Self::VoteStarted(..) => "VoteStarted",
+                Self::VoteCompleted(..) => "VoteCompleted",
+                Self::Knock => "Knock",
+            }
+        }
+
         pub fn format(&self, state: &super::State) -> String {
             fn format_player_action(
                 state: &super::State,
@@ -789,40 +1168,81 @@ pub mod ticker {
                     None => return format!("Previous player {}", player_id),
                 }
             }
+            let currency = state.config.currency_symbol();
             match self {
                 Self::GameStarted => "Game started".to_string(),
+                Self::DeckReshuffled => "The deck was reshuffled".to_string(),
                 Self::PlayerJoined(player_id) => {
                     format_player_action(state, player_id, "joined the game")
                 }
                 Self::PlayerTurnTimeout(player_name) => {
                     format!("Player {} timed out", player_name)
                 }
+                Self::PlayerTurnWarning(player_id) => {
+                    format_player_action(state, player_id, "is running out of time")
+                }
+                Self::ActionOn(player_id) => format_player_action(state, player_id, "is up"),
+                Self::PlayerUsedTimeBank(player_id) => {
+                    format_player_action(state, player_id, "used their time bank")
+                }
                 Self::PlayerLeft(player_name) => {
                     format!("Player {} left the game", player_name)
                 }
+                Self::PlayerKicked(player_name) => {
+                    format!("Player {} was kicked by vote", player_name)
+                }
                 Self::PlayerResumed(player_id) => {
                     format_player_action(state, player_id, "rejoined the game")
                 }
+                Self::PlayerSatOut(player_id) => {
+                    format_player_action(state, player_id, "is sitting out")
+                }
+                Self::PlayerSatIn(player_id) => {
+                    format_player_action(state, player_id, "is back in the game")
+                }
+                Self::PlayerReady(player_id) => format_player_action(state, player_id, "is ready"),
+                Self::PlayerRebought(player_id) => {
+                    format_player_action(state, player_id, "bought back in")
+                }
+                Self::PlayerMucked(player_id) => {
+                    format_player_action(state, player_id, "mucked their cards")
+                }
                 Self::PlayerFolded(player_id) => format_player_action(state, player_id, "folded"),
                 Self::PlayerBet(player_id, action) => {
                     let action: Cow<'static, str> = match action {
                         BetAction::Check => "checked".into(),
                         BetAction::Call => "called".into(),
-                        BetAction::RaiseTo(amount) => format!("raised to £{}", amount).into(),
+                        BetAction::BetTo(amount) => format!("bet {}{}", currency, amount).into(),
+                        BetAction::RaiseTo(amount) => {
+                            format!("raised to {}{}", currency, amount).into()
+                        }
+                        BetAction::AllIn => "went all-in".into(),
                     };
                     format_player_action(state, player_id, &action)
                 }
                 Self::DealerRotated(player_id) => {
                     format_player_action(state, player_id, "is the next dealer")
                 }
+                Self::AntePosted(ante) => format!("All players posted an ante of {}", ante),
+                Self::BlindsRaised(small_blind) => {
+                    format!("Blinds raised to {}/{}", small_blind, small_blind * 2)
+                }
                 Self::SmallBlindPosted(player_id) => {
                     format_player_action(state, player_id, "posted the small blind")
                 }
                 Self::BigBlindPosted(player_id) => {
                     format_player_action(state, player_id, "posted the big blind")
                 }
+                Self::BringInPosted(player_id) => {
+                    format_player_action(state, player_id, "posted the bring-in")
+                }
+                Self::StraddlePosted(player_id) => {
+                    format_player_action(state, player_id, "posted a straddle")
+                }
                 Self::CardsDealtToTable(1) => "Dealt another card".to_string(),
                 Self::CardsDealtToTable(count) => format!("Dealt {} cards to table", count),
+                Self::RunoutDealt(run, 1) => format!("Dealt another card to board {}", run),
+                Self::RunoutDealt(run, count) => format!("Dealt {} cards to board {}", count, run),
                 Self::RoundComplete => "Round complete".to_string(),
                 Self::Winner(player_id, strength) => {
                     format_player_action(state, player_id, &format!("won with {}", strength))
@@ -847,7 +1267,7 @@ pub mod ticker {
                         .get(player_id)
                         .map(|p| p.name.as_str())
                         .unwrap_or_default();
-                    format!("Player {} won £{} from pot", player, amount)
+                    format!("Player {} won {}{} from pot", player, currency, amount)
                 }
                 Self::PlayerPhotoUploaded(player_id) => {
                     format_player_action(state, player_id, "added a photo")
@@ -871,14 +1291,35 @@ pub mod ticker {
                         .get(to)
                         .map(|p| p.name.as_str())
                         .unwrap_or_default();
-                    format!("Player {} transferred £{} to {}", from, amount, to)
+                    format!(
+                        "Player {} transferred {}{} to {}",
+                        from, currency, amount, to
+                    )
+                }
+                Self::BalanceAdjusted(player_id, delta) => {
+                    let sign = if *delta < 0 { "-" } else { "+" };
+                    let action = format!(
+                        "had their balance adjusted by {}{}{}",
+                        sign,
+                        currency,
+                        delta.unsigned_abs()
+                    );
+                    format_player_action(state, player_id, &action)
+                }
+                Self::VoteStarted(player_id, motion) => {
+                    format_player_action(state, player_id, &format!("started a vote to {}", motion))
                 }
+                Self::VoteCompleted(motion, true) => format!("Vote to {} passed", motion),
+                Self::VoteCompleted(motion, false) => format!("Vote to {} failed", motion),
+                Self::Knock => "Someone is knocking to join".to_string(),
             }
         }
     }
 
     pub mod emoji {
-        #[derive(Debug, Clone, Copy)]
+        use std::str::FromStr;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
         pub struct TickerEmoji(char);
 
         impl std::fmt::Display for TickerEmoji {
@@ -915,6 +1356,28 @@ pub mod ticker {
             pub fn angry() -> Self {
                 Self('😡')
             }
+
+            pub fn laughing() -> Self {
+                Self('😂')
+            }
+        }
+
+        impl FromStr for TickerEmoji {
+            type Err = ();
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    "👍" | ":+1:" => Ok(Self::thumbs_up()),
+                    "👎" | ":-1:" => Ok(Self::thumbs_down()),
+                    "👏" | ":clapping:" => Ok(Self::clapping()),
+                    "⏳" | ":time:" => Ok(Self::time()),
+                    "🤔" | ":thinking:" => Ok(Self::thinking()),
+                    "💰" | ":money:" => Ok(Self::money()),
+                    "😡" | ":angry:" => Ok(Self::angry()),
+                    "😂" | ":joy:" => Ok(Self::laughing()),
+                    _ => Err(()),
+                }
+            }
         }
     }
 
@@ -926,14 +1389,34 @@ pub mod ticker {
         pub payload: TickerEvent,
     }
 
-    #[derive(Debug, Default)]
+    #[derive(Debug)]
     pub struct Ticker {
         events: Vec<TickerItem>,
+        /// Every emitted event, kept after expiry up to `EVENT_LOG_LENGTH`.
+        log: VecDeque<(Instant, TickerEvent)>,
         counter: usize,
         last_event: Option<Instant>,
+        timeout_seconds: u64,
+    }
+
+    impl Default for Ticker {
+        fn default() -> Self {
+            Self {
+                events: Vec::new(),
+                log: VecDeque::new(),
+                counter: 0,
+                last_event: None,
+                timeout_seconds: super::TICKER_ITEM_TIMEOUT_SECONDS,
+            }
+        }
     }
 
     impl Ticker {
+        /// Sets how long items emitted from now on stay on the ticker.
+        pub fn set_timeout_seconds(&mut self, timeout_seconds: u64) {
+            self.timeout_seconds = timeout_seconds;
+        }
+
         pub fn emit(&mut self, event: TickerEvent) {
             self.emit_with_delay(event, 0);
         }
@@ -947,8 +1430,12 @@ pub mod ticker {
             } else {
                 instant
             };
-            let end = start + super::TICKER_ITEM_TIMEOUT_SECONDS * 1000;
+            let end = start + self.timeout_seconds * 1000;
             let (start, end): (Instant, Instant) = (start.into(), end.into());
+            if self.log.len() >= super::EVENT_LOG_LENGTH {
+                self.log.pop_front();
+            }
+            self.log.push_back((start, event.clone()));
             self.events.push(TickerItem {
                 seq_index: self.counter,
                 start,
@@ -980,6 +1467,10 @@ pub mod ticker {
             self.events.iter()
         }
 
+        pub fn log(&self) -> impl Iterator<Item = &(Instant, TickerEvent)> {
+            self.log.iter()
+        }
+
         pub fn active_items(&self, now: Instant) -> impl Iterator<Item = &TickerItem> {
             self.events.iter().filter(move |item| {
                 item.start.as_u64() <= now.as_u64() && item.end.as_u64() > now.as_u64()
@@ -987,7 +1478,7 @@ pub mod ticker {
         }
 
         pub fn timeout_ms(&self) -> u64 {
-            super::TICKER_ITEM_TIMEOUT_SECONDS * 1000
+            self.timeout_seconds * 1000
         }
     }
 
@@ -1008,6 +1499,25 @@ pub mod ticker {
             assert_eq!(ticker.events.len(), 2);
         }
 
+        #[test]
+        fn ticker_clears_expired_items_after_configured_timeout() {
+            let mut short_ticker = Ticker::default();
+            short_ticker.set_timeout_seconds(2);
+            short_ticker.emit(TickerEvent::GameStarted);
+            let mut default_ticker = Ticker::default();
+            default_ticker.emit(TickerEvent::GameStarted);
+
+            let after_short = Instant::from(Instant::default().as_u64() + 2_500);
+            short_ticker.clear_expired_items(after_short);
+            default_ticker.clear_expired_items(after_short);
+            assert_eq!(short_ticker.events.len(), 0);
+            assert_eq!(default_ticker.events.len(), 1);
+
+            let after_default = Instant::from(Instant::default().as_u64() + 10_500);
+            default_ticker.clear_expired_items(after_default);
+            assert_eq!(default_ticker.events.len(), 0);
+        }
+
         #[test]
         fn ticker_clears_expired_items() {
             let mut ticker = Ticker::default();
@@ -1022,6 +1532,18 @@ pub mod ticker {
             assert_eq!(ticker.events.len(), 1);
         }
 
+        #[test]
+        fn ticker_log_keeps_expired_items() {
+            let mut ticker = Ticker::default();
+            ticker.emit(TickerEvent::GameStarted);
+
+            let later = Instant::default().as_u64() + 120_000;
+            ticker.clear_expired_items(Instant::from(later));
+
+            assert_eq!(ticker.events.len(), 0);
+            assert_eq!(ticker.log().count(), 1);
+        }
+
         #[test]
         fn ticker_checks_for_expired_items() {
             let mut ticker = Ticker::default();
@@ -1052,6 +1574,35 @@ pub mod ticker {
             assert_eq!(active_items, 3);
         }
 
+        #[test]
+        fn ticker_emoji_parses_each_accepted_message() {
+            use emoji::TickerEmoji;
+
+            let cases = [
+                ("👍", TickerEmoji::thumbs_up()),
+                (":+1:", TickerEmoji::thumbs_up()),
+                ("👎", TickerEmoji::thumbs_down()),
+                (":-1:", TickerEmoji::thumbs_down()),
+                ("👏", TickerEmoji::clapping()),
+                (":clapping:", TickerEmoji::clapping()),
+                ("⏳", TickerEmoji::time()),
+                (":time:", TickerEmoji::time()),
+                ("🤔", TickerEmoji::thinking()),
+                (":thinking:", TickerEmoji::thinking()),
+                ("💰", TickerEmoji::money()),
+                (":money:", TickerEmoji::money()),
+                ("😡", TickerEmoji::angry()),
+                (":angry:", TickerEmoji::angry()),
+                ("😂", TickerEmoji::laughing()),
+                (":joy:", TickerEmoji::laughing()),
+            ];
+
+            for (message, expected) in cases {
+                assert_eq!(message.parse::<TickerEmoji>(), Ok(expected), "{}", message);
+            }
+            assert_eq!("🙂".parse::<TickerEmoji>(), Err(()));
+        }
+
         #[test]
         fn ticker_emits_events_with_gap() {
             let mut ticker = Ticker::default();
@@ -1070,10 +1621,39 @@ pub mod ticker {
     }
 }
 
+pub mod vote {
+    use std::collections::HashMap;
+
+    use super::{dt, PlayerId};
+
+    #[derive(Debug, Clone)]
+    pub struct Vote {
+        pub motion: Motion,
+        pub started_by: PlayerId,
+        pub end_time: dt::Instant,
+        pub votes: HashMap<PlayerId, bool>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Motion {
+        DoubleBlinds,
+        KickPlayer(PlayerId),
+    }
+
+    impl std::fmt::Display for Motion {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Motion::DoubleBlinds => write!(f, "double the blinds"),
+                Motion::KickPlayer(_) => write!(f, "kick a player"),
+            }
+        }
+    }
+}
+
 mod players {
-    use std::collections::VecDeque;
+    use std::collections::{HashSet, VecDeque};
 
-    use super::{Player, PlayerId};
+    use super::{Player, PlayerId, MAX_DORMANT_PLAYERS};
 
     #[derive(Debug)]
     struct DormantPlayer(Player);
@@ -1101,7 +1681,14 @@ mod players {
         pub fn remove(&mut self, id: &PlayerId) -> Option<Player> {
             let idx = self.0.iter().position(|(pid, _)| pid == id)?;
             let player = self.0.remove(idx).map(|(_, p)| p)?;
-            self.1.push(DormantPlayer(player.clone()));
+
+            // only the most recent players are kept for resuming, without their photos
+            let mut dormant = player.clone();
+            dormant.photo = None;
+            self.1.push(DormantPlayer(dormant));
+            if self.1.len() > MAX_DORMANT_PLAYERS {
+                self.1.remove(0);
+            }
 
             Some(player)
         }
@@ -1169,24 +1756,143 @@ mod players {
             )
         }
 
+        /// Players who left and have not taken their seat back, most recent first.
+        pub fn dormant(&self) -> impl Iterator<Item = &Player> {
+            let mut seen = HashSet::new();
+            self.1
+                .iter()
+                .rev()
+                .map(|DormantPlayer(d)| d)
+                .filter(move |d| seen.insert(d.id.clone()))
+                .filter(|d| self.get(&d.id).is_none())
+        }
+
         pub fn get_non_dormant(&self, apid: &str) -> Option<&Player> {
             self.0
                 .iter()
                 .find_map(|(_, p)| if p.apid == apid { Some(p) } else { None })
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::{game, state::State};
+
+        #[test]
+        fn players_dormant_list_is_bounded() {
+            let mut state = State::default();
+            let mut apids = vec![];
+            for i in 0..MAX_DORMANT_PLAYERS + 10 {
+                state.round.deck = crate::cards::Deck::ordered();
+                let name = format!("player_{}", i);
+                let id = game::add_new_player(&mut state, &name, PlayerId::default()).unwrap();
+                let player = state.players.remove(&id).unwrap();
+                apids.push(player.apid);
+            }
+
+            assert_eq!(state.players.1.len(), MAX_DORMANT_PLAYERS);
+            assert!(state
+                .players
+                .1
+                .iter()
+                .all(|DormantPlayer(p)| p.photo.is_none()));
+
+            assert!(state.players.peek_dormant(&apids[0]).is_none());
+            let recent = state.players.promote_dormant(apids.last().unwrap());
+            assert!(recent.is_some());
+            assert_eq!(state.players.len(), 1);
+        }
+    }
 }
 
 pub mod config {
     use super::*;
 
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub enum BettingLimit {
+        #[default]
+        NoLimit,
+        PotLimit,
+        FixedLimit,
+    }
+
+    /// The forced bet that opens each hand.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub enum ForcedBet {
+        #[default]
+        Blinds,
+        /// A single forced bet of the given amount from the first player, in
+        /// place of the small and big blinds.
+        BringIn(u64),
+    }
+
+    /// Blind levels as `(after_n_hands, small_blind)` pairs, where each level
+    /// applies once that many hands have been played.
+    #[derive(Debug, Default, Clone)]
+    pub struct BlindSchedule(Vec<(u64, u64)>);
+
+    impl BlindSchedule {
+        pub fn new(mut levels: Vec<(u64, u64)>) -> Self {
+            levels.sort_by_key(|(after_n_hands, _)| *after_n_hands);
+            Self(levels)
+        }
+
+        /// The level reached after `hands_played` hands, numbered from 1 as
+        /// level 0 is the room's initial small blind.
+        pub fn level(&self, hands_played: u64) -> Option<(usize, u64)> {
+            self.0
+                .iter()
+                .enumerate()
+                .rev()
+                .find(|(_, (after_n_hands, _))| *after_n_hands <= hands_played)
+                .map(|(idx, (_, small_blind))| (idx + 1, *small_blind))
+        }
+    }
+
+    /// A cut of each pot taken by the house, as a percentage up to a cap.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Rake {
+        pub percent: u64,
+        pub cap: u64,
+    }
+
+    impl Rake {
+        pub fn amount(&self, pot: u64) -> u64 {
+            (pot * self.percent / 100).min(self.cap)
+        }
+    }
+
     #[derive(Debug, Clone)]
     pub struct RoomConfig {
         small_blind: u64,
         max_players: usize,
         starting_balance: u64,
+        turn_timeout_seconds: u64,
+        time_bank_seconds: u64,
+        ticker_timeout_seconds: u64,
+        idle_timeout_seconds: u64,
+        betting_limit: BettingLimit,
+        forced_bet: ForcedBet,
+        variant: cards::Variant,
+        ante: u64,
+        blind_schedule: BlindSchedule,
+        rebuys_enabled: bool,
+        rake: Option<Rake>,
+        min_hands_before_leaving: Option<u64>,
+        auto_muck_losers: bool,
+        run_it_twice: bool,
+        auto_start_seconds: Option<u64>,
+        deck_seed: Option<u64>,
+        deck_cut: Option<usize>,
+        transfer_limit: Option<u64>,
+        max_photo_bytes: usize,
+        currency_symbol: String,
+        password: Option<RoomPassword>,
         ticker_disabled: bool,
         card_deal_disabled: bool,
+        dealer_rotation_disabled: bool,
+        session_secret_required: bool,
     }
 
     impl RoomConfig {
@@ -1226,6 +1932,231 @@ pub mod config {
             self
         }
 
+        pub fn turn_timeout_seconds(&self) -> u64 {
+            self.turn_timeout_seconds
+        }
+
+        pub fn with_turn_timeout(mut self, turn_timeout_seconds: u64) -> Self {
+            self.turn_timeout_seconds = turn_timeout_seconds.clamp(
+                MIN_PLAYER_TURN_TIMEOUT_SECONDS,
+                MAX_PLAYER_TURN_TIMEOUT_SECONDS,
+            );
+            self
+        }
+
+        pub fn time_bank_seconds(&self) -> u64 {
+            self.time_bank_seconds
+        }
+
+        /// Extra seconds a player can add to their turn once per hand, or 0 to disable.
+        pub fn with_time_bank(mut self, time_bank_seconds: u64) -> Self {
+            self.time_bank_seconds = time_bank_seconds;
+            self
+        }
+
+        pub fn ticker_timeout_seconds(&self) -> u64 {
+            self.ticker_timeout_seconds
+        }
+
+        /// How long each item stays on the room's ticker.
+        pub fn with_ticker_timeout(mut self, ticker_timeout_seconds: u64) -> Self {
+            assert!(ticker_timeout_seconds > 0);
+            self.ticker_timeout_seconds = ticker_timeout_seconds;
+            self
+        }
+
+        pub fn idle_timeout_seconds(&self) -> u64 {
+            self.idle_timeout_seconds
+        }
+
+        /// How long a waiting room may sit without updates before it is reset.
+        pub fn with_idle_timeout(mut self, idle_timeout_seconds: u64) -> Self {
+            assert!(idle_timeout_seconds > 0);
+            self.idle_timeout_seconds = idle_timeout_seconds;
+            self
+        }
+
+        pub fn betting_limit(&self) -> BettingLimit {
+            self.betting_limit
+        }
+
+        pub fn with_betting_limit(mut self, betting_limit: BettingLimit) -> Self {
+            self.betting_limit = betting_limit;
+            self
+        }
+
+        pub fn forced_bet(&self) -> ForcedBet {
+            self.forced_bet
+        }
+
+        pub fn with_forced_bet(mut self, forced_bet: ForcedBet) -> Self {
+            if let ForcedBet::BringIn(amount) = forced_bet {
+                assert!(amount > 0);
+            }
+            self.forced_bet = forced_bet;
+            self
+        }
+
+        pub fn variant(&self) -> cards::Variant {
+            self.variant
+        }
+
+        pub fn with_variant(mut self, variant: cards::Variant) -> Self {
+            self.variant = variant;
+            self
+        }
+
+        pub fn ante(&self) -> u64 {
+            self.ante
+        }
+
+        pub fn with_ante(mut self, ante: u64) -> Self {
+            assert!(ante < self.starting_balance);
+            self.ante = ante;
+            self
+        }
+
+        pub fn blind_schedule(&self) -> &BlindSchedule {
+            &self.blind_schedule
+        }
+
+        pub fn with_blind_schedule(mut self, blind_schedule: BlindSchedule) -> Self {
+            self.blind_schedule = blind_schedule;
+            self
+        }
+
+        pub fn rebuys_enabled(&self) -> bool {
+            self.rebuys_enabled
+        }
+
+        pub fn with_rebuys(mut self) -> Self {
+            self.rebuys_enabled = true;
+            self
+        }
+
+        pub fn rake(&self) -> Option<Rake> {
+            self.rake
+        }
+
+        /// Takes `percent` of each pot that reaches a showdown, up to `cap` chips.
+        pub fn with_rake(mut self, percent: u64, cap: u64) -> Self {
+            assert!(percent <= 100);
+            self.rake = Some(Rake { percent, cap });
+            self
+        }
+
+        pub fn min_hands_before_leaving(&self) -> Option<u64> {
+            self.min_hands_before_leaving
+        }
+
+        /// Stops players leaving with winnings until they have played this
+        /// many hands, so they can't hit and run.
+        pub fn with_min_hands_before_leaving(mut self, hands: u64) -> Self {
+            self.min_hands_before_leaving = Some(hands);
+            self
+        }
+
+        pub fn auto_muck_losers(&self) -> bool {
+            self.auto_muck_losers
+        }
+
+        /// Hides losing hands at showdown unless the player chooses to show them.
+        pub fn with_auto_muck_losers(mut self) -> Self {
+            self.auto_muck_losers = true;
+            self
+        }
+
+        pub fn run_it_twice(&self) -> bool {
+            self.run_it_twice
+        }
+
+        /// Deals the rest of the board twice when every player is all-in,
+        /// splitting the pot between the two runs.
+        pub fn with_run_it_twice(mut self) -> Self {
+            self.run_it_twice = true;
+            self
+        }
+
+        pub fn auto_start_seconds(&self) -> Option<u64> {
+            self.auto_start_seconds
+        }
+
+        /// Starts the next hand automatically this many seconds after the
+        /// previous one completes.
+        pub fn with_auto_start(mut self, auto_start_seconds: u64) -> Self {
+            self.auto_start_seconds = Some(auto_start_seconds);
+            self
+        }
+
+        pub fn deck_seed(&self) -> Option<u64> {
+            self.deck_seed
+        }
+
+        /// Shuffles every hand from a fixed seed, offset by the hand number.
+        pub fn with_deck_seed(mut self, deck_seed: u64) -> Self {
+            self.deck_seed = Some(deck_seed);
+            self
+        }
+
+        pub fn deck_cut(&self) -> Option<usize> {
+            self.deck_cut
+        }
+
+        /// Cuts every shuffled deck at the same position before dealing.
+        pub fn with_deck_cut(mut self, deck_cut: usize) -> Self {
+            self.deck_cut = Some(deck_cut);
+            self
+        }
+
+        pub fn transfer_limit(&self) -> Option<u64> {
+            self.transfer_limit
+        }
+
+        /// Caps how much each player can transfer to others per game. The
+        /// count carries over between hands and only resets on a rematch.
+        pub fn with_transfer_limit(mut self, transfer_limit: u64) -> Self {
+            self.transfer_limit = Some(transfer_limit);
+            self
+        }
+
+        pub fn max_photo_bytes(&self) -> usize {
+            self.max_photo_bytes
+        }
+
+        pub fn with_max_photo_bytes(mut self, max_photo_bytes: usize) -> Self {
+            assert!(max_photo_bytes > 0);
+            self.max_photo_bytes = max_photo_bytes;
+            self
+        }
+
+        pub fn currency_symbol(&self) -> &str {
+            &self.currency_symbol
+        }
+
+        /// Symbol shown before amounts in ticker messages.
+        pub fn with_currency(mut self, currency_symbol: &str) -> Self {
+            self.currency_symbol = currency_symbol.to_string();
+            self
+        }
+
+        pub fn has_password(&self) -> bool {
+            self.password.is_some()
+        }
+
+        /// Rooms without a password accept any attempt.
+        pub fn check_password(&self, password: Option<&str>) -> bool {
+            match (&self.password, password) {
+                (None, _) => true,
+                (Some(expected), Some(password)) => expected.matches(password),
+                (Some(_), None) => false,
+            }
+        }
+
+        pub fn with_password(mut self, password: &str) -> Self {
+            self.password = Some(RoomPassword::new(password));
+            self
+        }
+
         pub fn ticker_disabled(&self) -> bool {
             self.ticker_disabled
         }
@@ -1248,6 +2179,62 @@ pub mod config {
             self.card_deal_disabled = true;
             self
         }
+
+        pub fn dealer_rotation_disabled(&self) -> bool {
+            self.dealer_rotation_disabled
+        }
+
+        /// Keeps the dealer and blinds in the same seats every hand, so that
+        /// scripted scenarios play out the same way.
+        pub fn with_dealer_rotation_disabled(mut self) -> Self {
+            self.dealer_rotation_disabled = true;
+            self
+        }
+
+        pub fn session_secret_required(&self) -> bool {
+            self.session_secret_required
+        }
+
+        /// Requires the session secret issued at join to fetch a player's
+        /// cards, rather than accepting the player id on its own.
+        pub fn with_session_secret_required(mut self) -> Self {
+            self.session_secret_required = true;
+            self
+        }
+    }
+
+    /// Salted hash of a room password, the plain text is never stored.
+    #[derive(Clone)]
+    pub struct RoomPassword {
+        salt: [u8; 16],
+        hash: [u8; 32],
+    }
+
+    impl RoomPassword {
+        pub fn new(password: &str) -> Self {
+            let salt = rand::random();
+            let hash = Self::digest(&salt, password);
+            Self { salt, hash }
+        }
+
+        pub fn matches(&self, password: &str) -> bool {
+            Self::digest(&self.salt, password) == self.hash
+        }
+
+        fn digest(salt: &[u8; 16], password: &str) -> [u8; 32] {
+            use sha2::{Digest, Sha256};
+
+            let mut hasher = Sha256::new();
+            hasher.update(salt);
+            hasher.update(password.as_bytes());
+            hasher.finalize().into()
+        }
+    }
+
+    impl std::fmt::Debug for RoomPassword {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("RoomPassword").finish_non_exhaustive()
+        }
     }
 
     impl Default for RoomConfig {
@@ -1256,8 +2243,31 @@ pub mod config {
                 small_blind: SMALL_BLIND,
                 max_players: MAX_PLAYERS,
                 starting_balance: STARTING_BALANCE,
+                turn_timeout_seconds: PLAYER_TURN_TIMEOUT_SECONDS,
+                time_bank_seconds: PLAYER_TIME_BANK_SECONDS,
+                ticker_timeout_seconds: TICKER_ITEM_TIMEOUT_SECONDS,
+                idle_timeout_seconds: GAME_IDLE_TIMEOUT_SECONDS,
+                betting_limit: BettingLimit::NoLimit,
+                forced_bet: ForcedBet::Blinds,
+                variant: cards::Variant::Standard,
+                ante: 0,
+                blind_schedule: BlindSchedule::default(),
+                rebuys_enabled: false,
+                rake: None,
+                min_hands_before_leaving: None,
+                auto_muck_losers: false,
+                run_it_twice: false,
+                auto_start_seconds: None,
+                deck_seed: None,
+                deck_cut: None,
+                transfer_limit: None,
+                max_photo_bytes: MAX_PLAYER_PHOTO_BYTES,
+                currency_symbol: DEFAULT_CURRENCY_SYMBOL.to_string(),
+                password: None,
                 ticker_disabled: ticker::is_disabled(),
                 card_deal_disabled: false,
+                dealer_rotation_disabled: false,
+                session_secret_required: false,
             }
         }
     }
@@ -25,6 +25,10 @@ pub mod fixtures {
             player_ids.push(player.player_id);
         }
 
+        for player_id in &player_ids {
+            client::player_ready(server, player_id).await;
+        }
+
         client::start_game(server, &room_code).await;
 
         StartedGame {
@@ -68,7 +72,7 @@ pub mod fixtures {
         panic!("Round did not end");
     }
 
-    async fn get_active_player(server: &TestServer, game: &StartedGame) -> LittleScreen {
+    pub async fn get_active_player(server: &TestServer, game: &StartedGame) -> LittleScreen {
         for player_id in &game.player_ids {
             let little_screen = client::get_little_screen(server, player_id).await;
             if little_screen.your_turn {
@@ -90,7 +94,7 @@ pub mod fixtures {
 
     async fn game_over(server: &TestServer, game: &StartedGame) -> bool {
         let big_screen = client::get_big_screen(server, Some(&game.room_code)).await;
-        big_screen.state == "complete" || big_screen.state == "waiting"
+        matches!(big_screen.state.as_str(), "complete" | "waiting" | "paused")
     }
 
     mod state {
@@ -113,25 +117,43 @@ pub mod server {
     const PORT: std::sync::OnceLock<Option<u16>> = std::sync::OnceLock::new();
 
     pub fn new_mock_app_server() -> (TestServer, WorkerHandle) {
-        new_app_server(false)
+        new_app_server(false, default_config())
+    }
+
+    pub fn new_mock_app_server_with_config(
+        config: state::config::RoomConfig,
+    ) -> (TestServer, WorkerHandle) {
+        new_app_server(false, config)
     }
 
     pub fn new_http_app_server() -> (TestServer, WorkerHandle) {
-        new_app_server(true)
+        new_app_server(true, default_config())
+    }
+
+    pub fn default_config() -> state::config::RoomConfig {
+        state::config::RoomConfig::default()
+            .with_ticker_disabled()
+            .with_starting_balance(10_000)
+    }
+
+    pub fn new_app_server(
+        use_http: bool,
+        config: state::config::RoomConfig,
+    ) -> (TestServer, WorkerHandle) {
+        new_app_server_with_state(use_http, state::SharedState::default(), config)
     }
 
-    pub fn new_app_server(use_http: bool) -> (TestServer, WorkerHandle) {
+    pub fn new_app_server_with_state(
+        use_http: bool,
+        state: state::SharedState,
+        config: state::config::RoomConfig,
+    ) -> (TestServer, WorkerHandle) {
         _ = tracing_subscriber::fmt::try_init();
 
         info!("Starting test server");
 
-        let state = state::SharedState::default();
-        state.set_default_config(
-            state::config::RoomConfig::default()
-                .with_ticker_disabled()
-                .with_starting_balance(10_000),
-        );
-        let handle = game::spawn_game_worker(state.clone());
+        state.set_default_config(config);
+        let (handle, shutdown) = game::spawn_game_worker(state.clone());
         let app = flop_server::create_application(state.clone());
 
         info!("Test server initialized");
@@ -151,7 +173,7 @@ pub mod server {
             maybe_api_port().map_or_else(|| "<none>".to_string(), |port| port.to_string())
         );
 
-        (test_server, WorkerHandle(handle, state))
+        (test_server, WorkerHandle(handle, shutdown, state))
     }
 
     fn maybe_api_port() -> Option<u16> {
@@ -160,7 +182,11 @@ pub mod server {
             .and_then(|port| port.parse().ok())
     }
 
-    pub struct WorkerHandle(tokio::task::JoinHandle<()>, state::SharedState);
+    pub struct WorkerHandle(
+        tokio::task::JoinHandle<()>,
+        game::ShutdownHandle,
+        state::SharedState,
+    );
 
     impl WorkerHandle {
         pub async fn abort(self) {
@@ -168,14 +194,27 @@ pub mod server {
             assert!(self.0.await.unwrap_err().is_cancelled());
         }
 
-        pub fn state(&self) -> &state::SharedState {
+        /// Signals shutdown and waits for the worker loop to exit on its own.
+        pub async fn shutdown(self) {
+            self.1.shutdown();
+            self.0.await.unwrap();
+        }
+
+        pub fn shutdown_handle(&self) -> &flop_server::game::ShutdownHandle {
             &self.1
         }
+
+        pub fn state(&self) -> &state::SharedState {
+            &self.2
+        }
     }
 }
 
 pub mod client {
-    use axum_test::TestServer;
+    use axum_test::{
+        multipart::{MultipartForm, Part},
+        TestServer,
+    };
     use models::*;
     use serde_json::json;
 
@@ -195,6 +234,18 @@ pub mod client {
         }
     }
 
+    pub async fn spectate_room(server: &TestServer, room_code: &str) -> BigScreen {
+        let response = requests::spectate_room(server, room_code)
+            .await
+            .json::<Json>();
+
+        BigScreen {
+            raw: response.clone(),
+            state: response["state"].as_str().unwrap().to_string(),
+            players: response["players"].as_array().unwrap().to_vec(),
+        }
+    }
+
     pub async fn get_little_screen(server: &TestServer, player_id: &str) -> LittleScreen {
         let response = requests::get_little_screen(server, player_id)
             .await
@@ -211,10 +262,110 @@ pub mod client {
         }
     }
 
+    pub async fn upload_player_photo(server: &TestServer, player_id: &str, photo: &[u8]) {
+        let part = Part::bytes(photo.to_vec()).mime_type("image/jpeg");
+        requests::upload_player_photo(server, player_id)
+            .multipart(MultipartForm::new().add_part("photo", part))
+            .await;
+    }
+
+    /// Opens the room event stream over a raw connection, returning a reader for
+    /// each `data:` line as it arrives. Requires an http transport server.
+    pub async fn open_room_stream(
+        server: &TestServer,
+        room_code: &str,
+    ) -> tokio::io::Lines<tokio::io::BufReader<tokio::net::TcpStream>> {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+
+        let url = server.server_address().expect("http transport required");
+        let address = format!("{}:{}", url.host_str().unwrap(), url.port().unwrap());
+        let mut stream = tokio::net::TcpStream::connect(address).await.unwrap();
+        let request = format!(
+            "GET /api/v1/room/stream HTTP/1.1\r\nHost: localhost\r\nroom-code: {}\r\n\r\n",
+            room_code
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        tokio::io::BufReader::new(stream).lines()
+    }
+
+    pub async fn next_room_stream_event(
+        lines: &mut tokio::io::Lines<tokio::io::BufReader<tokio::net::TcpStream>>,
+    ) -> Json {
+        loop {
+            let line = lines.next_line().await.unwrap().expect("stream closed");
+            if let Some(data) = line.strip_prefix("data: ") {
+                return serde_json::from_str(data).unwrap();
+            }
+        }
+    }
+
+    /// Opens the player's little-screen socket. Requires an http transport server.
+    pub async fn open_player_socket(
+        server: &TestServer,
+        player_id: &str,
+    ) -> axum_test::TestWebSocket {
+        server
+            .get_websocket(&format!("/api/v1/player/{}/ws", player_id))
+            // 101 Switching Protocols falls outside the 2xx success range
+            .expect_failure()
+            .await
+            .into_websocket()
+            .await
+    }
+
+    pub async fn send_chat(server: &TestServer, player_id: &str, message: &str) {
+        requests::send_chat(server, player_id)
+            .json(&json!({
+                "message": message,
+            }))
+            .await;
+    }
+
+    pub async fn get_room_chat(server: &TestServer, room_code: &str) -> Vec<Json> {
+        let response = requests::get_room_chat(server, room_code)
+            .await
+            .json::<Json>();
+
+        response["messages"].as_array().unwrap().to_vec()
+    }
+
+    pub async fn get_resumable_names(server: &TestServer, apid: &str, room_code: &str) -> Json {
+        requests::get_resumable_sessions(server, apid, room_code)
+            .await
+            .json::<Json>()["names"]
+            .clone()
+    }
+
+    pub async fn room_exists(server: &TestServer, room_code: &str) -> Json {
+        requests::room_exists(server, room_code)
+            .await
+            .json::<Json>()
+    }
+
+    pub async fn get_room_config(server: &TestServer, room_code: &str) -> Json {
+        requests::get_room_config(server, room_code)
+            .await
+            .json::<Json>()
+    }
+
+    pub async fn export_room(server: &TestServer, room_code: &str) -> Vec<Json> {
+        requests::export_room(server, room_code)
+            .await
+            .text()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
     pub async fn leave_room(server: &TestServer, player_id: &str) {
         requests::leave_room(server, player_id).await;
     }
 
+    pub async fn player_ready(server: &TestServer, player_id: &str) {
+        requests::player_ready(server, player_id).await;
+    }
+
     pub async fn create_room(server: &TestServer, player_name: &str) -> CreatedRoom {
         let response = requests::create_room(server)
             .json(&json!({
@@ -277,6 +428,24 @@ pub mod client {
             .await;
     }
 
+    pub async fn start_kick_vote(server: &TestServer, player_id: &str, target_id: &str) {
+        requests::start_vote(server)
+            .json(&json!({
+                "playerId": player_id,
+                "motion": { "kickPlayer": target_id },
+            }))
+            .await;
+    }
+
+    pub async fn cast_vote(server: &TestServer, player_id: &str, approve: bool) {
+        requests::cast_vote(server)
+            .json(&json!({
+                "playerId": player_id,
+                "approve": approve,
+            }))
+            .await;
+    }
+
     pub async fn player_check(server: &TestServer, player_id: &str) {
         requests::play_turn(server)
             .json(&json!({
@@ -297,6 +466,16 @@ pub mod client {
             .await;
     }
 
+    pub async fn player_fold(server: &TestServer, player_id: &str) {
+        requests::play_turn(server)
+            .json(&json!({
+                "playerId": player_id,
+                "stake": 0,
+                "action": "fold",
+            }))
+            .await;
+    }
+
     pub mod requests {
         use axum_test::{TestRequest, TestServer};
 
@@ -308,12 +487,68 @@ pub mod client {
                 .get("/api/v1/room")
                 .add_header("room-code", room_code)
         }
+        pub fn spectate_room(server: &TestServer, room_code: &str) -> TestRequest {
+            server.get(&format!("/api/v1/room/{}/spectate", room_code))
+        }
         pub fn get_little_screen(server: &TestServer, player_id: &str) -> TestRequest {
             server.get(&format!("/api/v1/player/{}", player_id))
         }
+        pub fn upload_player_photo(server: &TestServer, player_id: &str) -> TestRequest {
+            server.post(&format!("/api/v1/player/{}/photo", player_id))
+        }
+        pub fn get_player_photo(server: &TestServer, photo_url: &str) -> TestRequest {
+            server.get(&format!("/api/v1/{}", photo_url))
+        }
+        pub fn send_chat(server: &TestServer, player_id: &str) -> TestRequest {
+            server.post(&format!("/api/v1/player/{}/chat", player_id))
+        }
+        pub fn get_room_debug(server: &TestServer, room_code: &str) -> TestRequest {
+            server
+                .get("/api/v1/room/debug")
+                .add_header("room-code", room_code)
+        }
+        pub fn get_health_detailed(server: &TestServer) -> TestRequest {
+            server.get("/health/detailed")
+        }
+        pub fn get_rooms(server: &TestServer, admin_token: &str) -> TestRequest {
+            server
+                .get("/api/v1/rooms")
+                .add_header("admin-token", admin_token)
+        }
+        pub fn export_room(server: &TestServer, room_code: &str) -> TestRequest {
+            server
+                .get("/api/v1/room/export")
+                .add_header("room-code", room_code)
+        }
+        pub fn get_resumable_sessions(
+            server: &TestServer,
+            apid: &str,
+            room_code: &str,
+        ) -> TestRequest {
+            server
+                .get("/api/v1/room/resumable")
+                .add_header("room-code", room_code)
+                .add_cookie(("apid", apid).into())
+        }
+        pub fn room_exists(server: &TestServer, room_code: &str) -> TestRequest {
+            server.get(&format!("/api/v1/room/{}/exists", room_code))
+        }
+        pub fn get_room_config(server: &TestServer, room_code: &str) -> TestRequest {
+            server
+                .get("/api/v1/room/config")
+                .add_header("room-code", room_code)
+        }
+        pub fn get_room_chat(server: &TestServer, room_code: &str) -> TestRequest {
+            server
+                .get("/api/v1/room/chat")
+                .add_header("room-code", room_code)
+        }
         pub fn leave_room(server: &TestServer, player_id: &str) -> TestRequest {
             server.post(&format!("/api/v1/player/{}/leave", player_id))
         }
+        pub fn player_ready(server: &TestServer, player_id: &str) -> TestRequest {
+            server.post(&format!("/api/v1/player/{}/ready", player_id))
+        }
         pub fn create_room(server: &TestServer) -> TestRequest {
             server.post("/api/v1/new")
         }
@@ -331,6 +566,12 @@ pub mod client {
         pub fn play_turn(server: &TestServer) -> TestRequest {
             server.post("/api/v1/play")
         }
+        pub fn start_vote(server: &TestServer) -> TestRequest {
+            server.post("/api/v1/vote/start")
+        }
+        pub fn cast_vote(server: &TestServer) -> TestRequest {
+            server.post("/api/v1/vote/cast")
+        }
     }
 
     pub mod models {
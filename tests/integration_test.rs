@@ -73,8 +73,34 @@ async fn it_should_remove_players_from_game_on_leave() {
         .await
         .state;
 
-    // the game should be stopped and wait for more players
-    assert_eq!(status, "waiting");
+    // the game should be paused and wait for more players
+    assert_eq!(status, "paused");
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_list_resumable_sessions() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let mut game = fixtures::start_full_game(&server, 3).await;
+    let host_apid = game.player_apids.get(&game.player_ids[1]).unwrap().clone();
+    let leaving_player_id = game.player_ids.remove(0);
+    client::leave_room(&server, &leaving_player_id).await;
+
+    let names = client::get_resumable_names(&server, &host_apid, &game.room_code).await;
+    assert_eq!(names, serde_json::json!(["player1"]));
+
+    client::requests::get_resumable_sessions(&server, "stranger", &game.room_code)
+        .expect_failure()
+        .await
+        .assert_status_forbidden();
+
+    let leaving_player_apid = game.player_apids.get(&leaving_player_id).unwrap();
+    client::resume_session(&server, leaving_player_apid, &game.room_code).await;
+
+    let names = client::get_resumable_names(&server, &host_apid, &game.room_code).await;
+    assert_eq!(names, serde_json::json!([]));
 
     handle.abort().await;
 }
@@ -238,3 +264,712 @@ async fn it_play_4p_game_many_times() {
 
     handle.abort().await;
 }
+
+#[tokio::test]
+async fn it_should_seat_players_with_configured_starting_balance() {
+    let config = server::default_config().with_starting_balance(5_000);
+    let (server, handle) = server::new_mock_app_server_with_config(config);
+
+    let player1 = client::create_room(&server, "player1").await;
+    let player2 = client::join_room(&server, "player2", &player1.room_code).await;
+
+    for player_id in [&player1.player_id, &player2.player_id] {
+        let little_screen = client::get_little_screen(&server, player_id).await;
+        assert_eq!(little_screen.balance, 5_000);
+    }
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_never_show_hole_cards_to_spectators() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let game = fixtures::start_full_game(&server, 2).await;
+
+    let spectator = client::spectate_room(&server, &game.room_code).await;
+    assert_eq!(spectator.state, "playing");
+    assert_eq!(spectator.players.len(), 2);
+    for player in &spectator.players {
+        assert_eq!(player["turnExpiresDt"], serde_json::Value::Null);
+    }
+
+    fixtures::play_rounds_until_winner(&server, &game).await;
+
+    let big_screen = client::get_big_screen(&server, Some(&game.room_code)).await;
+    let spectator = client::spectate_room(&server, &game.room_code).await;
+    assert_eq!(spectator.state, big_screen.state);
+
+    let player_cards = spectator.raw["completed"]["playerCards"]
+        .as_array()
+        .expect("player_cards is not an array");
+    assert_eq!(player_cards.len(), 2);
+    assert!(player_cards.iter().all(|cards| cards.is_null()));
+    assert_eq!(
+        spectator.raw["completed"]["tiebreakCards"],
+        serde_json::Value::Null
+    );
+
+    fn contains(value: &serde_json::Value, needle: &serde_json::Value) -> bool {
+        match value {
+            _ if value == needle => true,
+            serde_json::Value::Array(items) => items.iter().any(|v| contains(v, needle)),
+            serde_json::Value::Object(fields) => fields.values().any(|v| contains(v, needle)),
+            _ => false,
+        }
+    }
+    for player_id in &game.player_ids {
+        let little_screen = client::get_little_screen(&server, player_id).await;
+        let hole_cards = little_screen.raw["cards"].as_array().unwrap();
+        assert!(!hole_cards.is_empty());
+        for card in hole_cards {
+            assert!(!contains(&spectator.raw, card), "spectator saw {}", card);
+        }
+    }
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_fetch_player_photo_by_room_code() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let room = client::create_room(&server, "player_1").await;
+    let other_room = client::create_room(&server, "player_2").await;
+
+    let photo = b"\xFF\xD8\xFFnot really a jpeg";
+    client::upload_player_photo(&server, &room.player_id, photo).await;
+
+    let big_screen = client::get_big_screen(&server, Some(&room.room_code)).await;
+    let photo_url = big_screen.players[0]["photo"].as_str().unwrap();
+
+    let response = client::requests::get_player_photo(&server, photo_url)
+        .add_header("room-code", &room.room_code)
+        .await;
+    assert_eq!(response.as_bytes().as_ref(), photo);
+
+    client::requests::get_player_photo(&server, photo_url)
+        .add_header("room-code", &other_room.room_code)
+        .expect_failure()
+        .await
+        .assert_status_not_found();
+
+    handle.abort().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn it_should_stream_room_updates_when_player_joins() {
+    let (server, handle) = server::new_http_app_server();
+
+    let room = client::create_room(&server, "player_1").await;
+    let mut events = client::open_room_stream(&server, &room.room_code).await;
+
+    let timeout = std::time::Duration::from_secs(5);
+    let event = tokio::time::timeout(timeout, client::next_room_stream_event(&mut events))
+        .await
+        .expect("no initial room event");
+    assert_eq!(event["players"].as_array().unwrap().len(), 1);
+
+    client::join_room(&server, "player_2", &room.room_code).await;
+
+    let event = tokio::time::timeout(timeout, client::next_room_stream_event(&mut events))
+        .await
+        .expect("no room event after join");
+    assert_eq!(event["players"].as_array().unwrap().len(), 2);
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_return_error_reason_for_illegal_check() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let game = fixtures::start_full_game(&server, 2).await;
+    let mut current_player = None;
+    for player_id in &game.player_ids {
+        let little_screen = client::get_little_screen(&server, player_id).await;
+        if little_screen.your_turn {
+            current_player = Some(player_id.clone());
+        }
+    }
+    let current_player = current_player.expect("no player has the first turn");
+
+    let response = client::requests::play_turn(&server)
+        .json(&serde_json::json!({
+            "playerId": current_player,
+            "stake": 0,
+            "action": "check",
+        }))
+        .expect_failure()
+        .await;
+
+    response.assert_status_bad_request();
+    let body = response.json::<serde_json::Value>();
+    assert_eq!(body["error"], "Cannot check after a raise");
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_export_room_events_as_ndjson() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let game = fixtures::start_full_game(&server, 2).await;
+    fixtures::play_rounds_until_winner(&server, &game).await;
+
+    let events = client::export_room(&server, &game.room_code).await;
+    let logged = |kind: &str| events.iter().any(|event| event["event"] == kind);
+    assert!(logged("GameStarted"));
+    assert!(logged("SmallBlindPosted"));
+    assert!(logged("PaidPot"));
+    assert!(events.iter().all(|event| event["timestamp"].is_u64()));
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_require_session_secret_for_player_cards_when_enabled() {
+    let config = server::default_config().with_session_secret_required();
+    let (server, handle) = server::new_mock_app_server_with_config(config);
+
+    let room = client::create_room(&server, "player_1").await;
+    let session_secret = room.raw["sessionSecret"].as_str().unwrap();
+
+    client::requests::get_little_screen(&server, &room.player_id)
+        .expect_failure()
+        .await
+        .assert_status_unauthorized();
+    client::requests::get_little_screen(&server, &room.player_id)
+        .add_header("session-secret", "not-the-secret")
+        .expect_failure()
+        .await
+        .assert_status_unauthorized();
+    client::requests::get_little_screen(&server, &room.player_id)
+        .add_header("session-secret", session_secret)
+        .await
+        .assert_status_ok();
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_report_whether_room_exists_and_is_joinable() {
+    let config = server::default_config().with_max_players(2);
+    let (server, handle) = server::new_mock_app_server_with_config(config);
+
+    let room = client::create_room(&server, "player_1").await;
+    let response = client::room_exists(&server, &room.room_code).await;
+    assert_eq!(
+        response,
+        serde_json::json!({ "exists": true, "joinable": true })
+    );
+
+    client::join_room(&server, "player_2", &room.room_code).await;
+    let response = client::room_exists(&server, &room.room_code).await;
+    assert_eq!(
+        response,
+        serde_json::json!({ "exists": true, "joinable": false })
+    );
+
+    let response = client::room_exists(&server, "ZZZZ").await;
+    assert_eq!(
+        response,
+        serde_json::json!({ "exists": false, "joinable": false })
+    );
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_report_room_config() {
+    let config = server::default_config().with_small_blind(25);
+    let (server, handle) = server::new_mock_app_server_with_config(config);
+
+    let room = client::create_room(&server, "player_1").await;
+
+    let config = client::get_room_config(&server, &room.room_code).await;
+    assert_eq!(config["smallBlind"], 25);
+    assert_eq!(config["bigBlind"], 50);
+    assert_eq!(config["startingBalance"], 10_000);
+    assert_eq!(config["variant"], "standard");
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_reject_names_with_bidi_overrides() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let room = client::create_room(&server, "player_1").await;
+
+    client::requests::join_room(&server)
+        .json(&serde_json::json!({
+            "name": "evil\u{202E}gnp.exe",
+            "roomCode": room.room_code,
+        }))
+        .expect_failure()
+        .await
+        .assert_status_bad_request();
+
+    client::join_room(&server, "Zoë 🎉", &room.room_code).await;
+    let big_screen = client::get_big_screen(&server, Some(&room.room_code)).await;
+    assert!(big_screen.players.iter().any(|p| p["name"] == "Zoë 🎉"));
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_list_chat_messages_in_order() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let room = client::create_room(&server, "player_1").await;
+    let player_2 = client::join_room(&server, "player_2", &room.room_code).await;
+
+    client::send_chat(&server, &room.player_id, "hello").await;
+    client::send_chat(&server, &player_2.player_id, "hi there").await;
+
+    let messages = client::get_room_chat(&server, &room.room_code).await;
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0]["name"], "player_1");
+    assert_eq!(messages[0]["message"], "hello");
+    assert_eq!(messages[1]["name"], "player_2");
+    assert_eq!(messages[1]["message"], "hi there");
+
+    client::requests::send_chat(&server, &room.player_id)
+        .json(&serde_json::json!({ "message": "x".repeat(141) }))
+        .expect_failure()
+        .await
+        .assert_status_bad_request();
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_require_password_for_private_room() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let response = client::requests::create_room(&server)
+        .json(&serde_json::json!({
+            "name": "player_1",
+            "password": "hunter2",
+        }))
+        .await;
+    let room_code = response.json::<serde_json::Value>()["roomCode"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    client::requests::join_room(&server)
+        .json(&serde_json::json!({
+            "name": "player_2",
+            "roomCode": room_code,
+            "password": "wrong",
+        }))
+        .expect_failure()
+        .await
+        .assert_status_forbidden();
+
+    client::requests::join_room(&server)
+        .json(&serde_json::json!({
+            "name": "player_2",
+            "roomCode": room_code,
+        }))
+        .expect_failure()
+        .await
+        .assert_status_forbidden();
+
+    client::requests::join_room(&server)
+        .json(&serde_json::json!({
+            "name": "player_2",
+            "roomCode": room_code,
+            "password": "hunter2",
+        }))
+        .await
+        .assert_status_ok();
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_expose_remaining_deck_on_debug_route() {
+    let (server, handle) = server::new_mock_app_server();
+    std::env::set_var("ENABLE_DEBUG_ROUTES", "true");
+
+    let game = fixtures::start_full_game(&server, 3).await;
+
+    let debug = client::requests::get_room_debug(&server, &game.room_code)
+        .await
+        .json::<serde_json::Value>();
+    assert_eq!(debug["deck"].as_array().unwrap().len(), 52 - 3 * 2);
+    assert_eq!(debug["players"].as_array().unwrap().len(), 3);
+    assert!(debug["board"].as_array().unwrap().is_empty());
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_resume_seated_player_without_folding() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let game = fixtures::start_full_game(&server, 2).await;
+    let player_id = game.player_ids[0].clone();
+    let balance = client::get_little_screen(&server, &player_id).await.balance;
+
+    let apid = game.player_apids.get(&player_id).unwrap();
+    let session = client::resume_session(&server, apid, &game.room_code).await;
+    assert_eq!(session.player_id, player_id);
+
+    let little_screen = client::get_little_screen(&server, &player_id).await;
+    assert_eq!(little_screen.balance, balance);
+
+    let big_screen = client::spectate_room(&server, &game.room_code).await;
+    assert_eq!(big_screen.state, "playing");
+    let player = big_screen
+        .players
+        .iter()
+        .find(|p| p["name"] == "player1")
+        .unwrap();
+    assert_eq!(player["folded"], false);
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_count_hands_played_in_metrics() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let game = fixtures::start_full_game(&server, 2).await;
+    fixtures::play_rounds_until_winner(&server, &game).await;
+
+    // a hand won by a fold is paid out without a showdown
+    let folded_game = fixtures::start_full_game(&server, 2).await;
+    let active_player = fixtures::get_active_player(&server, &folded_game).await;
+    client::player_fold(&server, &active_player.player_id).await;
+    let big_screen = client::get_big_screen(&server, Some(&folded_game.room_code)).await;
+    assert_eq!(big_screen.state, "complete");
+
+    let metrics = server.get("/metrics").await.text();
+    for room_code in [&game.room_code, &folded_game.room_code] {
+        let counter = format!("hands_played_total{{room_code=\"{}\"}}", room_code);
+        assert!(metrics.contains(&counter), "missing {} in metrics", counter);
+        let histogram = format!("pot_size_chips_count{{room_code=\"{}\"}}", room_code);
+        assert!(
+            metrics.contains(&histogram),
+            "missing {} in metrics",
+            histogram
+        );
+    }
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_return_conflict_for_out_of_turn_play() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let game = fixtures::start_full_game(&server, 2).await;
+    let active_player = fixtures::get_active_player(&server, &game).await;
+    let waiting_player = game
+        .player_ids
+        .iter()
+        .find(|id| **id != active_player.player_id)
+        .unwrap();
+
+    let response = client::requests::play_turn(&server)
+        .json(&serde_json::json!({
+            "playerId": waiting_player,
+            "stake": 0,
+            "action": "call",
+        }))
+        .expect_failure()
+        .await;
+    response.assert_status(axum::http::StatusCode::CONFLICT);
+    assert_eq!(
+        response.json::<serde_json::Value>()["error"],
+        "Not your turn"
+    );
+
+    client::requests::play_turn(&server)
+        .json(&serde_json::json!({
+            "playerId": active_player.player_id,
+            "stake": 0,
+            "action": "raiseTo",
+        }))
+        .expect_failure()
+        .await
+        .assert_status_bad_request();
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_reject_raise_below_current_stake() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let game = fixtures::start_full_game(&server, 2).await;
+    let active_player = fixtures::get_active_player(&server, &game).await;
+    let pot = client::spectate_room(&server, &game.room_code).await.raw["pot"]
+        .as_u64()
+        .unwrap();
+
+    client::requests::play_turn(&server)
+        .json(&serde_json::json!({
+            "playerId": active_player.player_id,
+            "stake": 1,
+            "action": "raiseTo",
+        }))
+        .expect_failure()
+        .await
+        .assert_status_bad_request();
+
+    let room = client::spectate_room(&server, &game.room_code).await;
+    assert_eq!(room.raw["pot"].as_u64().unwrap(), pot);
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_apply_repeated_play_action_once() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let game = fixtures::start_full_game(&server, 2).await;
+    let active_player = fixtures::get_active_player(&server, &game).await;
+    let pot = client::spectate_room(&server, &game.room_code).await.raw["pot"]
+        .as_u64()
+        .unwrap();
+
+    let raise_to = active_player.min_raise_to;
+    let play = serde_json::json!({
+        "playerId": active_player.player_id,
+        "stake": raise_to,
+        "action": "raiseTo",
+        "actionId": "4f0c3c8e-5d3b-4a43-9d0e-6f1a7b2c9e10",
+    });
+    client::requests::play_turn(&server)
+        .json(&play)
+        .await
+        .assert_status_ok();
+    client::requests::play_turn(&server)
+        .json(&play)
+        .await
+        .assert_status_ok();
+
+    let new_pot = client::spectate_room(&server, &game.room_code).await.raw["pot"]
+        .as_u64()
+        .unwrap();
+    assert_eq!(new_pot, pot + raise_to - active_player.current_round_stake);
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_reject_oversized_or_invalid_photos() {
+    use axum_test::multipart::{MultipartForm, Part};
+
+    let config = server::default_config().with_max_photo_bytes(1024);
+    let (server, handle) = server::new_mock_app_server_with_config(config);
+
+    let room = client::create_room(&server, "player_1").await;
+    let upload = |photo: Vec<u8>| {
+        let part = Part::bytes(photo).mime_type("image/jpeg");
+        client::requests::upload_player_photo(&server, &room.player_id)
+            .multipart(MultipartForm::new().add_part("photo", part))
+            .expect_failure()
+    };
+
+    let mut oversized = vec![0xFF, 0xD8, 0xFF];
+    oversized.resize(2048, 0);
+    upload(oversized)
+        .await
+        .assert_status(axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+
+    upload(b"GIF89a".to_vec()).await.assert_status_bad_request();
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_stop_worker_and_server_on_shutdown() {
+    let (_, handle) = server::new_mock_app_server();
+
+    let app = flop_server::create_application(handle.state().clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let signal = handle.shutdown_handle().signal();
+    let serve = tokio::spawn(async move {
+        axum::serve(listener, app.into_make_service())
+            .with_graceful_shutdown(signal)
+            .await
+    });
+
+    drop(tokio::net::TcpStream::connect(addr).await.unwrap());
+
+    handle.shutdown().await;
+    serve.await.unwrap().unwrap();
+
+    assert!(tokio::net::TcpStream::connect(addr).await.is_err());
+}
+
+#[tokio::test]
+async fn it_should_list_rooms_for_admins() {
+    let (server, handle) = server::new_mock_app_server();
+    std::env::set_var("ADMIN_TOKEN", "letmein");
+
+    let room_1 = client::create_room(&server, "player_1").await;
+    client::join_room(&server, "player_2", &room_1.room_code).await;
+    let room_2 = client::create_room(&server, "player_3").await;
+
+    client::requests::get_rooms(&server, "wrong")
+        .expect_failure()
+        .await
+        .assert_status_unauthorized();
+
+    let rooms = client::requests::get_rooms(&server, "letmein")
+        .await
+        .json::<serde_json::Value>();
+    let rooms = rooms["rooms"].as_array().unwrap();
+    let player_count = |room_code: &str| {
+        rooms
+            .iter()
+            .find(|room| room["roomCode"] == room_code)
+            .map(|room| room["playerCount"].as_u64().unwrap())
+    };
+    assert_eq!(player_count(&room_1.room_code), Some(2));
+    assert_eq!(player_count(&room_2.room_code), Some(1));
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_report_worker_liveness_on_detailed_health() {
+    let (server, handle) = server::new_mock_app_server();
+    client::create_room(&server, "player_1").await;
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+    let health = client::requests::get_health_detailed(&server)
+        .await
+        .json::<serde_json::Value>();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    let last_tick = health["workerLastTick"].as_u64().unwrap();
+    assert!(now - last_tick < 5000);
+    assert!(health["uptimeMs"].as_u64().unwrap() > 0);
+    assert_eq!(health["roomCount"], 1);
+
+    handle.abort().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn it_should_push_player_state_over_websocket() {
+    let (server, handle) = server::new_http_app_server();
+
+    let player_1 = client::create_room(&server, "player_1").await;
+    let player_2 = client::join_room(&server, "player_2", &player_1.room_code).await;
+    let player_ids = [player_1.player_id, player_2.player_id];
+
+    let mut sockets = Vec::new();
+    for player_id in &player_ids {
+        let mut socket = client::open_player_socket(&server, player_id).await;
+        let frame = socket.receive_json::<serde_json::Value>().await;
+        assert_eq!(frame["yourTurn"], false);
+        sockets.push(socket);
+    }
+
+    for player_id in &player_ids {
+        client::player_ready(&server, player_id).await;
+    }
+    client::start_game(&server, &player_1.room_code).await;
+
+    let active_player = futures_util::future::join_all(
+        player_ids
+            .iter()
+            .map(|player_id| client::get_little_screen(&server, player_id)),
+    )
+    .await
+    .into_iter()
+    .position(|little_screen| little_screen.your_turn)
+    .unwrap();
+
+    let socket = &mut sockets[active_player];
+    let your_turn = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        loop {
+            let frame = socket.receive_json::<serde_json::Value>().await;
+            if frame["yourTurn"] == true {
+                return frame;
+            }
+        }
+    })
+    .await
+    .expect("no frame with yourTurn");
+    assert_eq!(your_turn["state"], "playing");
+
+    handle.abort().await;
+}
+
+#[tokio::test]
+async fn it_should_remove_kicked_player_from_room_and_registry() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let game = fixtures::start_full_game(&server, 3).await;
+    let kicked_player_id = &game.player_ids[2];
+
+    client::start_kick_vote(&server, &game.player_ids[0], kicked_player_id).await;
+    client::cast_vote(&server, &game.player_ids[1], true).await;
+    client::cast_vote(&server, kicked_player_id, false).await;
+
+    client::requests::get_little_screen(&server, kicked_player_id)
+        .expect_failure()
+        .await
+        .assert_status_not_found();
+
+    let big_screen = client::get_big_screen(&server, Some(&game.room_code)).await;
+    assert_eq!(big_screen.players.len(), 2);
+
+    let kicked_player_id = kicked_player_id.parse().unwrap();
+    assert!(handle.state().get(&kicked_player_id).await.is_none());
+
+    handle.abort().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn it_should_reject_new_rooms_over_the_limit_until_expired_rooms_are_cleaned_up() {
+    let state = flop_server::state::SharedState::default().with_max_rooms(1);
+    let config = server::default_config().with_idle_timeout(1);
+    let (server, handle) = server::new_app_server_with_state(false, state, config);
+
+    client::create_room(&server, "player1").await;
+
+    client::requests::create_room(&server)
+        .json(&serde_json::json!({ "name": "player2" }))
+        .expect_failure()
+        .await
+        .assert_status_service_unavailable();
+
+    tokio::time::advance(std::time::Duration::from_millis(2_500)).await;
+    client::create_room(&server, "player2").await;
+
+    handle.abort().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn it_should_clamp_room_poll_timeout() {
+    let (server, handle) = server::new_mock_app_server();
+
+    let room = client::create_room(&server, "player1").await;
+    let last_update = client::get_big_screen(&server, Some(&room.room_code))
+        .await
+        .raw["lastUpdate"]
+        .as_u64()
+        .unwrap();
+
+    let started = tokio::time::Instant::now();
+    client::requests::get_big_screen_with_room_code(&server, &room.room_code)
+        .add_query_param("since", last_update)
+        .add_query_param("timeout", 10 * 60 * 1000)
+        .await;
+    assert!(started.elapsed() <= std::time::Duration::from_secs(31));
+
+    handle.abort().await;
+}